@@ -2,7 +2,7 @@ use std::ops::Range;
 
 use leptos::prelude::*;
 use leptos_pagination::{
-    MemoryLoader, {PaginatedFor, PaginationNext, PaginationPages, PaginationPrev, PaginationState},
+    MemoryLoader, {PaginatedFor, PaginationNext, PaginationPages, PaginationPrev, PaginationState, PaginationStateStoreFields},
 };
 use leptos_pagination_examples::data::{Book, BOOKS};
 
@@ -18,7 +18,7 @@ pub fn App() -> impl IntoView {
     let state = PaginationState::new_store();
 
     view! {
-        <ul class="m-10 text-sm bg-white rounded-md border border-gray-300 dark:bg-gray-800 dark:border-gray-700 overflow-clip">
+        <ul id=state.list_id().get_untracked() class="m-10 text-sm bg-white rounded-md border border-gray-300 dark:bg-gray-800 dark:border-gray-700 overflow-clip">
             <PaginatedFor loader=BookLoader query=() state item_count_per_page=5 let:idx_book>
                 <li class="p-2 bg-white border-b border-gray-200 dark:bg-gray-800 dark:border-gray-700">
                     <h3 class="font-bold text-gray-900 dark:text-white">{idx_book.1.title}</h3>
@@ -4,6 +4,7 @@ mod models;
 use leptos::{either::EitherOf3, prelude::*};
 use leptos_pagination::{
     Loading, PaginatedFor, PaginationNext, PaginationPages, PaginationPrev, PaginationState,
+    PaginationStateStoreFields,
 };
 use loader::{BreweryLoader, BreweryQuery, Column, SortDirection};
 
@@ -30,7 +31,7 @@ pub fn App() -> impl IntoView {
             </div>
         </div>
 
-        <ul class="m-10 text-sm bg-white rounded-md border border-gray-200 dark:bg-gray-800 dark:border-gray-700 overflow-clip">
+        <ul id=state.list_id().get_untracked() class="m-10 text-sm bg-white rounded-md border border-gray-200 dark:bg-gray-800 dark:border-gray-700 overflow-clip">
             <PaginatedFor loader=BreweryLoader query state item_count_per_page=5 let:idx_brewery>
                 <li class="p-2 border-b border-gray-200 dark:border-gray-700">
                     <h3 class="font-bold text-gray-900 dark:text-white">
@@ -65,7 +65,7 @@ fn HomePage() -> impl IntoView {
                 </label>
             </div>
 
-            <div class="grid grid-cols-4 gap-4 p-5 min-h-0 text-sm text-left text-gray-500 dark:text-gray-400 grow">
+            <div id=state.list_id().get_untracked() class="grid grid-cols-4 gap-4 p-5 min-h-0 text-sm text-left text-gray-500 dark:text-gray-400 grow">
                 <PaginatedFor
                     loader=CustomerLoader
                     state
@@ -1,16 +1,37 @@
-use leptos::prelude::*;
+use leptos::{prelude::*, wasm_bindgen::JsCast};
 use reactive_stores::Store;
 
 use crate::{
+    LoadMoreState, LoadMoreStateStoreFields, Loading, NumberFormatter, PageIndex,
     PaginationControls, PaginationState, PaginationStateStoreFields, UsePaginationControlsOptions,
-    use_pagination_controls,
+    common_page_ranges, page_button_focus_target, use_page_keys, use_pagination_controls,
 };
 
 /// A component that renders pagination page controls.
 ///
 /// ## Example
 ///
+/// All texts can be customized, for example to localize the controls.
+///
 /// ```
+/// # use leptos::prelude::*;
+/// # use leptos_pagination::{PageIndex, PaginationNext, PaginationPages, PaginationPrev, PaginationState};
+/// #
+/// # #[component]
+/// # pub fn App() -> impl IntoView {
+/// let state = PaginationState::new_store();
+///
+/// view! {
+///     <PaginationPrev state label="Vorherige Seite">"Zurück"</PaginationPrev>
+///     <PaginationPages
+///         state
+///         separator="…"
+///         page_formatter=|page: PageIndex| page.number().to_string()
+///         page_label=|page: PageIndex| format!("Seite {}", page.number())
+///     />
+///     <PaginationNext state label="Nächste Seite">"Weiter"</PaginationNext>
+/// }
+/// # }
 /// ```
 #[component]
 pub fn PaginationPages(
@@ -42,6 +63,11 @@ pub fn PaginationPages(
     #[prop(into, optional)]
     anchor_class: Signal<String>,
 
+    /// The class that is added to the `<a>` element of a page whose items are already loaded,
+    /// so users see which pages are displayed instantly. See [`PaginationState::pages_cached`].
+    #[prop(into, optional)]
+    cached_class: Signal<String>,
+
     /// The class of the `<li>` element that wraps the `<a>` element that represents a page.
     #[prop(into, optional)]
     li_class: Signal<String>,
@@ -59,7 +85,71 @@ pub fn PaginationPages(
     /// The class of the `<div>` element that contains the separator.
     #[prop(into, optional)]
     separator_class: Signal<String>,
+
+    /// Formats the page numbers.
+    ///
+    /// Defaults to [`format_number`](crate::format_number) which formats according to the user's locale
+    /// if the feature `intl` is enabled.
+    #[prop(into, optional)]
+    number_formatter: NumberFormatter,
+
+    /// Formats the visible label of a page button, for example as Roman numerals or "Seite 3".
+    /// This is used instead of `number_formatter`.
+    ///
+    /// Together with `separator`, `page_label` and `announcement` every text of the controls can
+    /// be localized.
+    #[prop(into, optional)]
+    page_formatter: Option<Callback<PageIndex, String>>,
+
+    /// The number of page buttons to show while the page count isn't known yet.
+    ///
+    /// This keeps the layout from shifting when the page count arrives.
+    /// Default is `None` which renders no buttons until then.
+    #[prop(optional)]
+    placeholder_page_count: Option<usize>,
+
+    /// Render the controls invisible (`visibility: hidden`) while the page count isn't known yet.
+    ///
+    /// Together with `placeholder_page_count` this reserves the space of the controls without
+    /// showing buttons for pages that might not exist.
+    #[prop(optional)]
+    hide_until_count: bool,
+
+    /// Slot that is rendered instead of the controls while the page count isn't known yet.
+    /// This is recommended to be used to show a loading skeleton.
+    #[prop(optional)]
+    loading: Option<Loading>,
+
+    /// Load a page in the background when its button is hovered or focused so it's displayed
+    /// instantly when clicked.
+    #[prop(optional)]
+    prefetch: bool,
+
+    /// Navigate to the next and previous page with the PageDown and PageUp keys anywhere on the
+    /// page, except in text fields.
+    ///
+    /// The page buttons themselves can always be navigated with the arrow keys, Home and End.
+    #[prop(optional)]
+    page_keys: bool,
+
+    /// The accessible label of a page button. Default is "Page 3".
+    #[prop(into, optional)]
+    page_label: Option<Callback<PageIndex, String>>,
+
+    /// Announce page changes to screen reader users with an invisible `aria-live` region.
+    #[prop(optional)]
+    announce_page_changes: bool,
+
+    /// The text that is announced when `announce_page_changes` is enabled. It's called with the
+    /// current page and the page count if it's known. Default is "Page 3 of 10" with the numbers
+    /// formatted by `number_formatter`.
+    #[prop(into, optional)]
+    announcement: Option<Callback<(PageIndex, Option<usize>), String>>,
 ) -> impl IntoView {
+    if page_keys {
+        use_page_keys(state);
+    }
+
     let PaginationControls {
         current_page,
         start_range,
@@ -68,50 +158,207 @@ pub fn PaginationPages(
         show_separator_before,
         show_separator_after,
         page_count_error,
+        is_page_count_known,
     } = use_pagination_controls(
         state,
         UsePaginationControlsOptions::default()
             .display_page_count(display_page_count)
-            .margin_page_count(margin_page_count),
+            .margin_page_count(margin_page_count)
+            .placeholder_page_count(placeholder_page_count),
     );
 
+    let has_loading = loading.is_some();
+    let hidden = Signal::derive(move || hide_until_count && !is_page_count_known.get());
+    let visibility = move || hidden.get().then_some("hidden");
+    let number_formatter = StoredValue::new(number_formatter);
+
+    let announcement_view = move || {
+        announce_page_changes.then(|| {
+            let text = move || {
+                let page = current_page.get();
+                let page_count = state.page_count().get();
+
+                match announcement {
+                    Some(announcement) => announcement.run((page, page_count)),
+                    None => {
+                        number_formatter.with_value(|f| page_of_page_count(f, page, page_count))
+                    }
+                }
+            };
+
+            view! {
+                <span role="status" aria-live="polite" style=VISUALLY_HIDDEN>
+                    {text}
+                </span>
+            }
+        })
+    };
+
+    let loading_view = move || {
+        if page_count_error.get().is_some() {
+            None
+        } else {
+            loading.clone().map(|l| (l.children)())
+        }
+    };
+
     view! {
         {move || {
-            page_count_error.get().map(|error| view! { <div class="error-message">{error}</div> })
+            page_count_error
+                .get()
+                .map(|error| view! { <div class="error-message" role="alert">{error}</div> })
         }}
-        <PaginationRange
-            state
-            current_page
-            range=start_range
-            ul_class
-            anchor_class
-            li_class
-            active_class
-        />
-        <Show when=move || show_separator_before.get()>
-            <div class=separator_class>{separator}</div>
+        {announcement_view}
+        <Show when=move || !has_loading || is_page_count_known.get() fallback=loading_view>
+            <PaginationRange
+                state
+                current_page
+                range=start_range
+                ul_class
+                anchor_class
+                cached_class
+                li_class
+                active_class
+                number_formatter=number_formatter.get_value()
+                hidden
+                prefetch
+                page_label
+                page_formatter
+            />
+            <Show when=move || show_separator_before.get()>
+                <div class=separator_class style:visibility=visibility aria-hidden="true">
+                    {separator}
+                </div>
+            </Show>
+            <PaginationRange
+                state
+                current_page
+                range=current_range
+                ul_class
+                anchor_class
+                cached_class
+                li_class
+                active_class
+                number_formatter=number_formatter.get_value()
+                hidden
+                prefetch
+                page_label
+                page_formatter
+            />
+            <Show when=move || show_separator_after.get()>
+                <div class=separator_class style:visibility=visibility aria-hidden="true">
+                    {separator}
+                </div>
+            </Show>
+            <PaginationRange
+                state
+                current_page
+                range=end_range
+                ul_class
+                anchor_class
+                cached_class
+                li_class
+                active_class
+                number_formatter=number_formatter.get_value()
+                hidden
+                prefetch
+                page_label
+                page_formatter
+            />
         </Show>
-        <PaginationRange
-            state
-            current_page
-            range=current_range
-            ul_class
-            anchor_class
-            li_class
-            active_class
-        />
-        <Show when=move || show_separator_after.get()>
-            <div class=separator_class>{separator}</div>
-        </Show>
-        <PaginationRange
-            state
-            current_page
-            range=end_range
-            ul_class
-            anchor_class
-            li_class
-            active_class
-        />
+    }
+}
+
+/// A placeholder with the same structure as [`PaginationPages`] in the common case of many pages
+/// with the current page in the middle.
+///
+/// Render it while the page count is loading, for example in the `Loading` slot of
+/// `PaginationPages`, to avoid layout shifts. Pass the same options and classes as to
+/// `PaginationPages`. The anchors are empty, so style them to have the size of a page button.
+///
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos_pagination::{Loading, PaginationPages, PaginationSkeleton, PaginationState};
+/// #
+/// # #[component]
+/// # pub fn App() -> impl IntoView {
+/// let state = PaginationState::new_store();
+///
+/// view! {
+///     <PaginationPages state anchor_class="page">
+///         <Loading slot>
+///             <PaginationSkeleton anchor_class="page skeleton" />
+///         </Loading>
+///     </PaginationPages>
+/// }
+/// # }
+/// ```
+#[component]
+pub fn PaginationSkeleton(
+    /// See `PaginationPages`. Default is 5.
+    #[prop(default = 5)]
+    display_page_count: usize,
+
+    /// See `PaginationPages`. Default is 1.
+    #[prop(default = 1)]
+    margin_page_count: usize,
+
+    /// See `PaginationPages`. Default is "⋯"
+    #[prop(into, default = "⋯".into())]
+    separator: Signal<String>,
+
+    /// The class of the `<a>` elements that represent a page.
+    #[prop(into, optional)]
+    anchor_class: Signal<String>,
+
+    /// The class of the `<li>` elements that wrap the `<a>` elements.
+    #[prop(into, optional)]
+    li_class: Signal<String>,
+
+    /// The class of the `<ul>` elements that contain the pages.
+    #[prop(into, optional)]
+    ul_class: Signal<String>,
+
+    /// The class of the `<div>` elements that contain the separators.
+    #[prop(into, optional)]
+    separator_class: Signal<String>,
+) -> impl IntoView {
+    let ranges = common_page_ranges(display_page_count, margin_page_count);
+
+    let range_view = move |count: usize| {
+        (count > 0).then(|| {
+            view! {
+                <ul class=ul_class aria-hidden="true">
+                    {(0..count)
+                        .map(|_| {
+                            view! {
+                                <li class=li_class>
+                                    <a class=anchor_class>"\u{00a0}"</a>
+                                </li>
+                            }
+                        })
+                        .collect_view()}
+                </ul>
+            }
+        })
+    };
+
+    let separator_view = move |show: bool| {
+        show.then(|| {
+            view! {
+                <div class=separator_class aria-hidden="true">
+                    {separator}
+                </div>
+            }
+        })
+    };
+
+    view! {
+        {range_view(ranges.start.len())}
+        {separator_view(ranges.show_separator_before)}
+        {range_view(ranges.current.len())}
+        {separator_view(ranges.show_separator_after)}
+        {range_view(ranges.end.len())}
     }
 }
 
@@ -119,20 +366,53 @@ pub fn PaginationPages(
 #[component]
 pub fn PaginationRange(
     state: Store<PaginationState>,
-    current_page: Signal<usize>,
-    range: Signal<Vec<usize>>,
+    current_page: Signal<PageIndex>,
+    range: Signal<Vec<PageIndex>>,
     ul_class: Signal<String>,
     li_class: Signal<String>,
     anchor_class: Signal<String>,
     active_class: Signal<String>,
+    number_formatter: NumberFormatter,
+    /// Render the range invisible but still take up its space.
+    #[prop(into, optional)]
+    hidden: Signal<bool>,
+    /// Load the page of a button in the background when it's hovered or focused.
+    #[prop(optional)]
+    prefetch: bool,
+    /// The accessible label of a page button. Default is "Page 3".
+    #[prop(optional_no_strip)]
+    page_label: Option<Callback<PageIndex, String>>,
+    /// Formats the visible label of a page button instead of `number_formatter`.
+    #[prop(optional_no_strip)]
+    page_formatter: Option<Callback<PageIndex, String>>,
+    /// The class that is added to the anchors of pages that are already loaded.
+    #[prop(into, optional)]
+    cached_class: Signal<String>,
 ) -> impl IntoView {
+    let number_formatter = StoredValue::new(number_formatter);
+    let list_id = StoredValue::new(state.list_id().get_untracked());
+
     view! {
         <Show when=move || !range.get().is_empty()>
-            <ul class=ul_class>
+            <ul
+                class=ul_class
+                style:visibility=move || hidden.get().then_some("hidden")
+            >
                 <For
                     each=move || range.get()
                     key=|i| *i
                     children=move |index| {
+                        let label = match page_formatter {
+                            Some(page_formatter) => page_formatter.run(index),
+                            None => number_formatter.with_value(|f| f.format(index.number())),
+                        };
+                        let aria_label = match page_label {
+                            Some(page_label) => page_label.run(index),
+                            None => {
+                                number_formatter
+                                    .with_value(|f| format!("Page {}", f.format(index.number())))
+                            }
+                        };
                         let class = Signal::derive(move || {
                             if current_page.get() == index {
                                 active_class.get()
@@ -140,17 +420,59 @@ pub fn PaginationRange(
                                 li_class.get()
                             }
                         });
+                        let anchor_class = Signal::derive(move || {
+                            let is_cached = state
+                                .pages_cached()
+                                .with(|pages| pages.contains(&index));
+
+                            match cached_class.get() {
+                                cached_class if is_cached && !cached_class.is_empty() => {
+                                    format!("{} {cached_class}", anchor_class.get())
+                                }
+                                _ => anchor_class.get(),
+                            }
+                        });
 
                         view! {
                             <li class=class>
                                 <a
                                     class=anchor_class
+                                    aria-controls=list_id.get_value()
+                                    data-pagination-page=""
+                                    role="button"
+                                    aria-label=aria_label
+                                    aria-current=move || (current_page.get() == index).then_some("page")
+                                    tabindex=move || if current_page.get() == index { "0" } else { "-1" }
                                     on:click=move |evt| {
                                         evt.prevent_default();
                                         state.current_page().set(index);
                                     }
+                                    on:keydown=move |evt| {
+                                        if matches!(evt.key().as_str(), "Enter" | " ") {
+                                            evt.prevent_default();
+                                            state.current_page().set(index);
+                                        } else if let Some(anchor) = evt
+                                            .current_target()
+                                            .and_then(|target| target.dyn_into().ok())
+                                        {
+                                            list_id
+                                                .with_value(|list_id| {
+                                                    focus_page_button(&anchor, list_id, &evt.key())
+                                                });
+                                        }
+                                    }
+                                    on:mouseenter=move |_| {
+                                        if prefetch {
+                                            PaginationState::prefetch(state, index);
+                                        }
+                                    }
+                                    on:focus=move |_| {
+                                        if prefetch {
+                                            PaginationState::prefetch(state, index);
+                                        }
+                                    }
                                 >
-                                    {index + 1}
+                                    {label}
                                 </a>
                             </li>
                         }
@@ -161,16 +483,54 @@ pub fn PaginationRange(
     }
 }
 
+/// Moves the focus from `anchor` to another page button of the same list according to `key`.
+fn focus_page_button(anchor: &web_sys::HtmlElement, list_id: &str, key: &str) {
+    let Ok(buttons) = document().query_selector_all(&format!(
+        "[data-pagination-page][aria-controls=\"{list_id}\"]"
+    )) else {
+        return;
+    };
+
+    let buttons = (0..buttons.length())
+        .filter_map(|i| buttons.item(i)?.dyn_into::<web_sys::HtmlElement>().ok())
+        .collect::<Vec<_>>();
+
+    let Some(position) = buttons.iter().position(|button| button == anchor) else {
+        return;
+    };
+
+    if let Some(target) = page_button_focus_target(key, position, buttons.len()) {
+        let _ = buttons[target].focus();
+    }
+}
+
 #[component]
 /// Button to navigate to the next page.
 pub fn PaginationNext(
     /// The current state of the pagination. This is used to communicate with the PaginatedFor component.
     state: Store<PaginationState>,
+    /// Load the next page in the background when the button is hovered or focused so it's
+    /// displayed instantly when clicked.
+    #[prop(optional)]
+    prefetch: bool,
+    /// The accessible label of the button. Default is "Next page".
+    #[prop(into, default = "Next page".into())]
+    label: Signal<String>,
     children: Children,
 ) -> impl IntoView {
+    let prefetch_next = move || {
+        if prefetch {
+            PaginationState::prefetch(state, state.current_page().get_untracked() + 1);
+        }
+    };
+
     view! {
         <button
             on:click=move |_| PaginationState::next(state)
+            on:mouseenter=move |_| prefetch_next()
+            on:focus=move |_| prefetch_next()
+            aria-label=label
+            aria-controls=state.list_id().get_untracked()
             prop:disabled=move || PaginationState::is_last_page(state)
         >
             {children()}
@@ -178,19 +538,279 @@ pub fn PaginationNext(
     }
 }
 
+/// A `<select>` that lets the user choose the number of items per page.
+///
+/// The first displayed item stays on the page when the size changes. See
+/// [`PaginationState::set_page_size`].
+///
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos_pagination::{PaginationPageSize, PaginationState};
+/// #
+/// # #[component]
+/// # pub fn App() -> impl IntoView {
+/// let state = PaginationState::new_store();
+///
+/// view! {
+///     <label>
+///         "Items per page" <PaginationPageSize state sizes=vec![20, 50, 100] />
+///     </label>
+/// }
+/// # }
+/// ```
+#[component]
+pub fn PaginationPageSize(
+    /// The current state of the pagination. This is used to communicate with the PaginatedFor component.
+    state: Store<PaginationState>,
+
+    /// The page sizes to choose from.
+    ///
+    /// Default is `[10, 25, 50, 100]`.
+    #[prop(into, default = vec![10, 25, 50, 100].into())]
+    sizes: Signal<Vec<usize>>,
+
+    /// Formats the page sizes. Defaults to [`format_number`](crate::format_number).
+    #[prop(into, optional)]
+    number_formatter: NumberFormatter,
+) -> impl IntoView {
+    let number_formatter = StoredValue::new(number_formatter);
+
+    view! {
+        <select
+            aria-controls=state.list_id().get_untracked()
+            on:change=move |evt| {
+                if let Ok(page_size) = event_target_value(&evt).parse() {
+                    PaginationState::set_page_size(state, page_size);
+                }
+            }
+        >
+            <For
+                each=move || sizes.get()
+                key=|size| *size
+                children=move |size| {
+                    view! {
+                        <option
+                            value=size.to_string()
+                            prop:selected=move || state.page_size().get() == Some(size)
+                        >
+                            {number_formatter.with_value(|f| f.format(size))}
+                        </option>
+                    }
+                }
+            />
+        </select>
+    }
+}
+
+/// Displays the current page and the page count, e.g. "Page 3 of 1,234".
+///
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos_pagination::{PageIndex, PaginationState, PaginationSummary};
+/// #
+/// # #[component]
+/// # pub fn App() -> impl IntoView {
+/// let state = PaginationState::new_store();
+///
+/// view! {
+///     <PaginationSummary
+///         state
+///         summary=|(page, page_count): (PageIndex, Option<usize>)| match page_count {
+///             Some(page_count) => format!("Seite {} von {page_count}", page.number()),
+///             None => format!("Seite {}", page.number()),
+///         }
+///     />
+/// }
+/// # }
+/// ```
+#[component]
+pub fn PaginationSummary(
+    /// The current state of the pagination. This is used to communicate with the PaginatedFor component.
+    state: Store<PaginationState>,
+
+    /// Formats the page number and the page count. Defaults to [`format_number`](crate::format_number).
+    #[prop(into, optional)]
+    number_formatter: NumberFormatter,
+
+    /// The text to display. It's called with the current page and the page count if it's known.
+    /// This is used instead of `number_formatter`. Default is "Page 3 of 10".
+    #[prop(into, optional)]
+    summary: Option<Callback<(PageIndex, Option<usize>), String>>,
+) -> impl IntoView {
+    let text = move || {
+        let page = state.current_page().get();
+        let page_count = state.page_count().get();
+
+        match summary {
+            Some(summary) => summary.run((page, page_count)),
+            None => page_of_page_count(&number_formatter, page, page_count),
+        }
+    };
+
+    view! { <span>{text}</span> }
+}
+
+/// The default text of [`PaginationSummary`] and of the announcements of [`PaginationPages`].
+fn page_of_page_count(
+    number_formatter: &NumberFormatter,
+    page: PageIndex,
+    page_count: Option<usize>,
+) -> String {
+    let page_number = number_formatter.format(page.number());
+
+    match page_count {
+        Some(page_count) => {
+            let page_count = number_formatter.format(page_count);
+            format!("Page {page_number} of {page_count}")
+        }
+        None => format!("Page {page_number}"),
+    }
+}
+
+#[component]
+/// Button to navigate to the first page.
+pub fn PaginationFirst(
+    /// The current state of the pagination. This is used to communicate with the PaginatedFor component.
+    state: Store<PaginationState>,
+    /// The accessible label of the button. Default is "First page".
+    #[prop(into, default = "First page".into())]
+    label: Signal<String>,
+    children: Children,
+) -> impl IntoView {
+    view! {
+        <button
+            on:click=move |_| PaginationState::first(state)
+            aria-label=label
+            aria-controls=state.list_id().get_untracked()
+            prop:disabled=move || PaginationState::is_first_page(state)
+        >
+            {children()}
+        </button>
+    }
+}
+
+#[component]
+/// Button to navigate to the last page. It's disabled while the page count is unknown.
+pub fn PaginationLast(
+    /// The current state of the pagination. This is used to communicate with the PaginatedFor component.
+    state: Store<PaginationState>,
+    /// The accessible label of the button. Default is "Last page".
+    #[prop(into, default = "Last page".into())]
+    label: Signal<String>,
+    children: Children,
+) -> impl IntoView {
+    view! {
+        <button
+            on:click=move |_| PaginationState::last(state)
+            aria-label=label
+            aria-controls=state.list_id().get_untracked()
+            prop:disabled=move || {
+                state.page_count().get().is_none() || PaginationState::is_last_page(state)
+            }
+        >
+            {children()}
+        </button>
+    }
+}
+
 #[component]
 /// Button to navigate to the previous page.
 pub fn PaginationPrev(
     /// The current state of the pagination. This is used to communicate with the PaginatedFor component.
     state: Store<PaginationState>,
+    /// The accessible label of the button. Default is "Previous page".
+    #[prop(into, default = "Previous page".into())]
+    label: Signal<String>,
     children: Children,
 ) -> impl IntoView {
     view! {
         <button
             on:click=move |_| PaginationState::prev(state)
+            aria-label=label
+            aria-controls=state.list_id().get_untracked()
             prop:disabled=move || PaginationState::is_first_page(state)
         >
             {children()}
         </button>
     }
 }
+
+#[component]
+/// Button to append more items to an [`InfiniteFor`](crate::InfiniteFor) with `load_on_scroll=false`.
+///
+/// The button is disabled while the appended items are loading and hidden once all items are displayed.
+///
+/// ```
+/// # use std::ops::Range;
+/// #
+/// # use leptos::prelude::*;
+/// # use leptos_pagination::{InfiniteFor, LoadMore, LoadMoreState, LoadMoreStateStoreFields, Loading, MemoryLoader};
+/// #
+/// # pub struct BookLoader;
+/// #
+/// # impl MemoryLoader for BookLoader {
+/// #     type Item = String;
+/// #     type Query = ();
+/// #
+/// #     fn load_items(&self, range: Range<usize>, _query: &Self::Query) -> Vec<Self::Item> {
+/// #         range.map(|i| format!("Book {i}")).collect()
+/// #     }
+/// #
+/// #     fn item_count(&self, _query: &Self::Query) -> usize {
+/// #         1000
+/// #     }
+/// # }
+/// #
+/// # #[component]
+/// # pub fn App() -> impl IntoView {
+/// let state = LoadMoreState::new_store();
+///
+/// view! {
+///     <ul id=state.list_id().get_untracked()>
+///         <InfiniteFor loader=BookLoader query=() item_count_per_load=20 state load_on_scroll=false let:book>
+///             <li>{book.data.to_string()}</li>
+///         </InfiniteFor>
+///     </ul>
+///
+///     <LoadMore state>
+///         "Load more"
+///         <Loading slot>"Loading..."</Loading>
+///     </LoadMore>
+/// }
+/// # }
+/// ```
+pub fn LoadMore(
+    /// The load more state. This is used to communicate with the InfiniteFor component.
+    state: Store<LoadMoreState>,
+
+    /// Slot that is rendered instead of `children` while the appended items are being loaded.
+    #[prop(optional)]
+    loading: Option<Loading>,
+
+    children: ChildrenFn,
+) -> impl IntoView {
+    let is_loading_more = move || state.is_loading_more().get();
+
+    view! {
+        <Show when=move || !LoadMoreState::is_end_reached(state)>
+            <button
+                on:click=move |_| LoadMoreState::load_more(state)
+                aria-controls=state.list_id().get_untracked()
+                prop:disabled=is_loading_more
+                aria-busy=move || is_loading_more().to_string()
+            >
+                {
+                    let children = children.clone();
+                    let loading = loading.clone();
+                    move || match &loading {
+                        Some(loading) if is_loading_more() => (loading.children)().into_any(),
+                        _ => children().into_any(),
+                    }
+                }
+            </button>
+        </Show>
+    }
+}
+
+/// Hides an element visually while keeping it accessible to screen readers.
+const VISUALLY_HIDDEN: &str = "position: absolute; width: 1px; height: 1px; padding: 0; margin: -1px; overflow: hidden; clip: rect(0, 0, 0, 0); white-space: nowrap; border: 0;";
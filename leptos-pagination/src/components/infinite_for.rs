@@ -0,0 +1,288 @@
+use std::{marker::PhantomData, sync::Arc, time::Duration};
+
+use leptos::{ev::MouseEvent, prelude::*};
+use leptos_windowing::{
+    InternalLoader, ItemInteractions, ItemLoadError, ItemWindow, WindowItem,
+    cache::CacheController, item_state::ItemState,
+};
+use reactive_stores::{Store, StoreFieldIterator};
+
+use crate::{
+    Empty, LoadError, LoadMoreState, Loading, UseInfiniteScrollOptions, UseLoadMoreOptions,
+    use_infinite_scroll, use_load_more,
+};
+
+/// Slot that is rendered after the last item once all items are displayed.
+///
+/// This is the counterpart of the [`EndOfList`](crate::EndOfList) slot of
+/// [`PaginatedFor`](crate::PaginatedFor). There is no `StartOfList` because an infinite list always
+/// starts with the first item, so content before it can simply be put before the list.
+#[derive(Clone)]
+#[slot]
+pub struct EndReached {
+    children: ChildrenFn,
+}
+
+/// Like [`PaginatedFor`](crate::PaginatedFor) but instead of pages this appends more items as the user
+/// scrolls near the end of the list (infinite scrolling).
+///
+/// Items are loaded and cached on-demand using the provided `loader`.
+///
+/// After the items an invisible sentinel `<div>` is rendered. As soon as it comes into view
+/// (see `load_margin_px`) the next `item_count_per_load` items are appended.
+///
+/// For a mobile-friendly "Load more" button instead, set `load_on_scroll` to `false` and put a
+/// [`LoadMore`](crate::LoadMore) button with the same `state` after the list.
+///
+/// ## Example
+///
+/// ```
+/// # use std::ops::Range;
+/// #
+/// # use leptos::prelude::*;
+/// # use leptos_pagination::{EndReached, InfiniteFor, LoadMoreState, LoadMoreStateStoreFields, Loading, MemoryLoader};
+/// #
+/// pub struct Book {
+///     title: String,
+/// }
+///
+/// # #[component]
+/// # pub fn App() -> impl IntoView {
+/// let state = LoadMoreState::new_store();
+///
+/// view! {
+///     <div class="feed" aria-busy=move || state.is_loading_more().get().to_string()>
+///         <InfiniteFor
+///             loader=BookLoader
+///             query=()
+///             item_count_per_load=20
+///             state
+///             let:idx_book
+///         >
+///             <article>{idx_book.data.title.clone()}</article>
+///
+///             <Loading slot>
+///                 <article class="loading">Loading...</article>
+///             </Loading>
+///
+///             <EndReached slot>
+///                 <p>"You've seen all books."</p>
+///             </EndReached>
+///         </InfiniteFor>
+///     </div>
+/// }
+/// # }
+///
+/// pub struct BookLoader;
+///
+/// impl MemoryLoader for BookLoader {
+///     type Item = Book;
+///     type Query = ();
+///
+///     fn load_items(&self, range: Range<usize>, _query: &Self::Query) -> Vec<Self::Item> {
+///         range.map(|i| Book { title: format!("Book {i}") }).collect()
+///     }
+///
+///     fn item_count(&self, _query: &Self::Query) -> usize {
+///         1000
+///     }
+/// }
+/// ```
+#[component]
+pub fn InfiniteFor<T, L, Q, CF, V, M>(
+    /// The loader to get the data on-demand.
+    loader: L,
+
+    /// The query to get the data on-demand. Changing it starts over from the first items.
+    #[prop(into)]
+    query: Signal<Q>,
+
+    /// How many items are appended every time the user scrolls near the end.
+    #[prop(into)]
+    item_count_per_load: Signal<usize>,
+
+    /// The load more state.
+    ///
+    /// Used to communicate between this component and the [`LoadMore`](crate::LoadMore) button.
+    /// Its `is_loading_more` tells whether the appended items are still being loaded.
+    #[prop(default = LoadMoreState::new_store())]
+    state: Store<LoadMoreState>,
+
+    /// Whether to append more items automatically when the user scrolls near the end.
+    ///
+    /// Set this to `false` if you want to use a [`LoadMore`](crate::LoadMore) button instead.
+    /// Defaults to `true`.
+    #[prop(default = true)]
+    load_on_scroll: bool,
+
+    /// How many loads worth of items to load ahead of the displayed items.
+    ///
+    /// Defaults to 1.
+    #[prop(default = 1)]
+    overscan_load_count: usize,
+
+    /// How many pixels before the sentinel becomes visible more items are appended.
+    ///
+    /// Defaults to 200.
+    #[prop(default = 200)]
+    load_margin_px: u32,
+
+    /// How long items that failed to load keep their error. Afterwards they are loaded again the
+    /// next time more items are loaded. Defaults to never.
+    #[prop(optional)]
+    error_ttl: Option<Duration>,
+
+    /// Invalidation tags of the data of this list, e.g. `tags=vec!["posts".to_string()]`.
+    /// Calling [`invalidate_tag`](crate::invalidate_tag) with one of them reloads the list while
+    /// the loaded items stay displayed.
+    #[prop(optional)]
+    tags: Vec<String>,
+
+    /// Slot that is rendered instead of `children` while an item is being loaded.
+    #[prop(optional)]
+    loading: Option<Loading>,
+
+    /// Slot that is rendered instead of `children` when the data has been loaded but is empty.
+    #[prop(optional)]
+    empty: Option<Empty>,
+
+    /// Slot that is rendered instead of `children` when an error occurs.
+    #[prop(optional)]
+    load_error: Option<LoadError>,
+
+    /// Slot that is rendered after the last item once all items are displayed.
+    #[prop(optional)]
+    end_reached: Option<EndReached>,
+
+    /// You can provide this to implement mutable access to the cache for editing/inserting elements.
+    #[prop(optional)]
+    cache_controller: CacheController<T>,
+
+    /// Called when the user opens the context menu of a loaded item, e.g. with a right click.
+    ///
+    /// The listener is put on the root element rendered by `children`. Call
+    /// `prevent_default()` on the event to replace the browser's menu with your own.
+    #[prop(into, optional)]
+    on_item_context_menu: Option<Callback<(WindowItem<T>, MouseEvent)>>,
+
+    /// Called when the user opens a loaded item by double-clicking it or by pressing Enter while
+    /// it's focused.
+    ///
+    /// The listeners are put on the root element rendered by `children`. The items get a roving
+    /// `tabindex` so the list is a single tab stop and the arrow keys, Home and End move the
+    /// focus between the displayed items. See [`ItemInteractions`].
+    #[prop(into, optional)]
+    on_item_activate: Option<Callback<WindowItem<T>>>,
+
+    /// The normal children are rendered when an item is loaded.
+    children: CF,
+
+    #[prop(optional)] _marker: PhantomData<(M, L)>,
+) -> impl IntoView
+where
+    T: Send + Sync + 'static,
+    L: InternalLoader<M, Item = T, Query = Q> + 'static,
+    L::Error: Send + Sync,
+    Q: Send + Sync + 'static,
+    CF: Fn(WindowItem<T>) -> V + Send + Clone + 'static,
+    V: IntoView,
+{
+    let sentinel = NodeRef::<leptos::html::Div>::new();
+
+    let window: ItemWindow<T> = if load_on_scroll {
+        use_infinite_scroll(
+            state,
+            sentinel,
+            loader,
+            query,
+            item_count_per_load,
+            UseInfiniteScrollOptions::default()
+                .overscan_load_count(overscan_load_count)
+                .load_margin_px(load_margin_px)
+                .error_ttl(error_ttl)
+                .tags(tags),
+        )
+    } else {
+        use_load_more(
+            state,
+            loader,
+            query,
+            item_count_per_load,
+            UseLoadMoreOptions::default()
+                .overscan_load_count(overscan_load_count)
+                .error_ttl(error_ttl)
+                .tags(tags),
+        )
+    };
+
+    cache_controller.init_with_item_window(window);
+
+    let interactions = ItemInteractions::new(None, on_item_context_menu, on_item_activate);
+
+    let empty_view = move || {
+        if window.cache.item_count().get() == Some(0) {
+            empty.clone().map(|e| (e.children)())
+        } else {
+            None
+        }
+    };
+
+    let end_reached_view = move || {
+        if LoadMoreState::is_end_reached(state) && window.cache.item_count().get() != Some(0) {
+            end_reached.clone().map(|e| (e.children)())
+        } else {
+            None
+        }
+    };
+
+    view! {
+        {empty_view}
+
+        <For each=move || window.displayed_keys() key=|key| *key let:key>
+            {
+                let children = children.clone();
+                let loading = loading.clone();
+                let load_error = load_error.clone();
+                let index = Memo::new(move |_| window.index_of(key));
+
+                move || {
+                    let Some(index) = index.get() else {
+                        return ().into_any();
+                    };
+
+                    match &*window.cache.items().at_unkeyed(index).read() {
+                        ItemState::Loaded(item) => {
+                            let item = WindowItem::new(index, Arc::clone(item), &window);
+                            interactions.apply(children.clone()(item.clone()).into_any(), &item, window)
+                        }
+                        ItemState::Error(error) => {
+                            load_error
+                                .clone()
+                                .map(|e| {
+                                    let error = ItemLoadError::new(index, error.clone(), &window);
+                                    (e.children)(error).into_any()
+                                })
+                                .unwrap_or_else(|| {
+
+                                    view! { <div style="color: red;">Error: {error.to_string()}</div> }
+                                        .into_any()
+                                })
+                        }
+                        _ => {
+                            loading
+                                .clone()
+                                .map(|l| (l.children)().into_any())
+                                .unwrap_or_else(|| ().into_any())
+                        }
+                    }
+                }
+            }
+        </For>
+
+        {end_reached_view}
+
+        <Show when=move || load_on_scroll>
+            <div node_ref=sentinel aria-hidden="true" style="height: 1px;"></div>
+        </Show>
+    }
+}
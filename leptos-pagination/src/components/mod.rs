@@ -1,5 +1,9 @@
 mod controls;
+mod infinite_for;
 mod paginated_for;
+mod paginated_vec;
 
 pub use controls::*;
+pub use infinite_for::*;
 pub use paginated_for::*;
+pub use paginated_vec::*;
@@ -1,18 +1,28 @@
-use std::{marker::PhantomData, sync::Arc};
+use std::{marker::PhantomData, sync::Arc, time::Duration};
 
+use leptos::ev::MouseEvent;
 use leptos::prelude::*;
+use leptos::tachys::html::directive::DirectiveAttribute;
 use leptos_windowing::{
-    InternalLoader, ItemWindow, WindowItem, cache::CacheController, item_state::ItemState,
+    ImpressionTracker, InternalLoader, ItemInteractions, ItemLoadError, RetryPolicy,
+    WindowController, WindowItem, WindowLink,
+    cache::{CacheController, ItemKey},
+    item_state::ItemState,
 };
 use reactive_stores::{Store, StoreFieldIterator};
 
-use crate::{PaginationState, PaginationStateStoreFields, UsePaginationOptions, use_pagination};
+use crate::{
+    ItemIndex, PageIndex, PaginationState, PaginationStateStoreFields, UsePaginationOptions,
+    UsePaginationResult, use_pagination,
+};
 
-/// Slot that is rendered when an error occurs.
+/// Slot that is rendered for every item that failed to load.
+///
+/// It receives an [`ItemLoadError`] with the error and a `retry` callback to load the item again.
 #[derive(Clone)]
 #[slot]
 pub struct LoadError {
-    children: Arc<dyn Fn(String) -> AnyView + Send + Sync>,
+    children: Arc<dyn Fn(ItemLoadError) -> AnyView + Send + Sync>,
 }
 
 /// Slot that is rendered when the data is being loaded.
@@ -29,6 +39,22 @@ pub struct Empty {
     children: ChildrenFn,
 }
 
+/// Slot that is rendered before the items when the first page is displayed.
+#[derive(Clone)]
+#[slot]
+pub struct StartOfList {
+    children: ChildrenFn,
+}
+
+/// Slot that is rendered after the items when the last page is displayed, e.g. "You're all caught up".
+///
+/// It's only rendered once the total number of items is known.
+#[derive(Clone)]
+#[slot]
+pub struct EndOfList {
+    children: ChildrenFn,
+}
+
 /// Quite similar to Leptos' `<For>` this displays a list of items.
 ///
 /// But these items are loaded and cached on-demand using the provided `loader`.
@@ -39,7 +65,7 @@ pub struct Empty {
 /// # use std::ops::Range;
 /// #
 /// # use leptos::prelude::*;
-/// # use leptos_pagination::{Loading, PaginatedFor, PaginationState, PaginationPrev, PaginationNext, ExactLoader};
+/// # use leptos_pagination::{EndOfList, Loading, PaginatedFor, PaginationState, PaginationStateStoreFields, PaginationPrev, PaginationNext, ExactLoader};
 /// #
 /// pub struct Book {
 ///     title: String,
@@ -51,7 +77,7 @@ pub struct Empty {
 /// let state = PaginationState::new_store();
 ///
 /// view! {
-///     <ul>
+///     <ul id=state.list_id().get_untracked()>
 ///         <PaginatedFor
 ///             loader=BookLoader
 ///             query=()
@@ -60,15 +86,20 @@ pub struct Empty {
 ///             let:idx_book
 ///         >
 ///             // Shown when the data has finished loading.
-///             <li class={if idx_book.0 % 2 == 0 { "even" } else { "odd" }}>
-///                 <h3>{idx_book.1.title.clone()}</h3>
-///                 <p>{idx_book.1.author.clone()}</p>
+///             <li class={if idx_book.index % 2 == 0 { "even" } else { "odd" }}>
+///                 <h3>{idx_book.data.title.clone()}</h3>
+///                 <p>{idx_book.data.author.clone()}</p>
 ///             </li>
 ///
 ///             // Shown while the data is loading.
 ///             <Loading slot>
 ///                 <li class="loading">Loading...</li>
 ///             </Loading>
+///
+///             // Shown after the last item.
+///             <EndOfList slot>
+///                 <li class="end">"No more books"</li>
+///             </EndOfList>
 ///         </PaginatedFor>
 ///     </ul>
 ///
@@ -117,6 +148,9 @@ pub fn PaginatedFor<T, L, Q, CF, V, M>(
     state: Store<PaginationState>,
 
     /// How many items to display per page.
+    ///
+    /// Use [`use_auto_item_count_per_page`](crate::use_auto_item_count_per_page) to compute this
+    /// from the height of a container.
     #[prop(into)]
     item_count_per_page: Signal<usize>,
 
@@ -127,6 +161,75 @@ pub fn PaginatedFor<T, L, Q, CF, V, M>(
     #[prop(default = 1)]
     overscan_page_count: usize,
 
+    /// Only load the current page until the user changes the page for the first time.
+    #[prop(optional)]
+    defer_overscan: bool,
+
+    /// How many pages after the current page to prefetch while the browser is idle. Defaults to 0.
+    #[prop(optional)]
+    idle_warm_page_count: usize,
+
+    /// The page to start on. Counting starts from 0.
+    ///
+    /// This is applied before the first load so deep links land directly on the right page.
+    #[prop(into, optional)]
+    initial_page: Option<PageIndex>,
+
+    /// How failed loads are retried. Defaults to no retries.
+    #[prop(default = RetryPolicy::none())]
+    retry: RetryPolicy,
+
+    /// The maximum number of loaded items to keep in the cache. The items of the pages farthest
+    /// away from the current page are evicted first. Defaults to keeping all items.
+    #[prop(optional)]
+    max_cached_items: Option<usize>,
+
+    /// How long loaded items stay fresh. Stale items are loaded again when their page or one of
+    /// the overscan pages is displayed. They stay displayed meanwhile and
+    /// [`Cache::is_stale`](leptos_windowing::cache::Cache::is_stale) is `true` for them.
+    /// Defaults to never.
+    #[prop(optional)]
+    ttl: Option<Duration>,
+
+    /// How long items that failed to load keep their error. Afterwards they are loaded again when
+    /// their page or one of the overscan pages is displayed. Defaults to never.
+    #[prop(optional)]
+    error_ttl: Option<Duration>,
+
+    /// Determines the identity of the items, e.g. `item_key=|book: &Book| book.id`. Items that
+    /// shifted in the data source between loads then aren't displayed twice.
+    #[prop(optional, into)]
+    item_key: Option<ItemKey<T>>,
+
+    /// Keep displaying the current page when the list is reloaded, e.g. with
+    /// [`WindowController::reload`], and replace its items once they're loaded again.
+    #[prop(optional)]
+    stale_while_revalidate: bool,
+
+    /// Wait for the items of the current page in a surrounding `<Suspense/>` or `<Transition/>`.
+    ///
+    /// With `<Transition/>` the previous page stays visible until the next one is loaded.
+    #[prop(optional)]
+    suspense: bool,
+
+    /// Keep displaying the current page when the user navigates until the items of the new page
+    /// are loaded instead of showing the `loading` slot.
+    ///
+    /// Use [`WindowController::is_pending`] of the `controller` to dim the previous page meanwhile.
+    #[prop(optional)]
+    keep_previous_data: bool,
+
+    /// Invalidation tags of the data of this list, e.g. `tags=vec!["customers".to_string()]`.
+    /// Calling [`invalidate_tag`](crate::invalidate_tag) with one of them reloads the list while
+    /// the current page stays displayed.
+    #[prop(optional)]
+    tags: Vec<String>,
+
+    /// The number of columns of a grid that displays the items. The number of items per page is
+    /// rounded down to a multiple of it so the last row of every page is full.
+    #[prop(optional, into)]
+    align_page_size_to: Option<Signal<usize>>,
+
     /// Slot that is rendered instead of `children` when the data is being loaded.
     /// This is recommended to be used to show a loading skeleton.
     #[prop(optional)]
@@ -140,10 +243,53 @@ pub fn PaginatedFor<T, L, Q, CF, V, M>(
     #[prop(optional)]
     load_error: Option<LoadError>,
 
+    /// Slot that is rendered before the items when the first page is displayed.
+    #[prop(optional)]
+    start_of_list: Option<StartOfList>,
+
+    /// Slot that is rendered after the items when the last page is displayed.
+    #[prop(optional)]
+    end_of_list: Option<EndOfList>,
+
     /// You can provide this to implement mutable access to the cache for editing/inserting elements.
     #[prop(optional)]
     cache_controller: CacheController<T>,
 
+    /// You can provide this to control the list imperatively like reloading or prefetching data.
+    #[prop(optional)]
+    controller: WindowController<T>,
+
+    /// Link this list to other lists over the same data (e.g. in a split view).
+    ///
+    /// Linked lists share their cache, their selection and navigate together. Clicking an item
+    /// selects it. See [`WindowLink`].
+    #[prop(optional, into)]
+    link: Option<WindowLink<T>>,
+
+    /// Called once for every item when it becomes visible in the viewport for the first time.
+    ///
+    /// This is useful for analytics like impression tracking. The visibility is observed on the
+    /// root element rendered by `children`. Changing the query resets the tracking.
+    #[prop(into, optional)]
+    on_item_visible: Option<Callback<WindowItem<T>>>,
+
+    /// Called when the user opens the context menu of a loaded item, e.g. with a right click.
+    ///
+    /// The listener is put on the root element rendered by `children`. Call
+    /// `prevent_default()` on the event to replace the browser's menu with your own.
+    #[prop(into, optional)]
+    on_item_context_menu: Option<Callback<(WindowItem<T>, MouseEvent)>>,
+
+    /// Called when the user opens a loaded item by double-clicking it or by pressing Enter while
+    /// it's focused.
+    ///
+    /// The listeners are put on the root element rendered by `children`. The items get a roving
+    /// `tabindex` so the list is a single tab stop and the arrow keys, Home and End move the
+    /// focus between the items of the page. Enter presses inside of nested elements like buttons
+    /// are ignored. See [`ItemInteractions`].
+    #[prop(into, optional)]
+    on_item_activate: Option<Callback<WindowItem<T>>>,
+
     /// The normal children are rendered when an item is loaded.
     /// This would be a normal `<li>` or `<tr>` element for example.
     children: CF,
@@ -158,15 +304,56 @@ where
     CF: Fn(WindowItem<T>) -> V + Send + Clone + 'static,
     V: IntoView,
 {
-    let window: ItemWindow<T> = use_pagination(
+    let UsePaginationResult {
+        item_window: window,
+        item_count_per_page,
+        ..
+    } = use_pagination(
         state,
         loader,
         query,
         item_count_per_page,
-        UsePaginationOptions::default().overscan_page_count(overscan_page_count),
+        UsePaginationOptions::default()
+            .overscan_page_count(overscan_page_count)
+            .defer_overscan(defer_overscan)
+            .idle_warm_page_count(idle_warm_page_count)
+            .initial_page(initial_page)
+            .link(link)
+            .retry(retry)
+            .max_cached_items(max_cached_items)
+            .ttl(ttl)
+            .error_ttl(error_ttl)
+            .item_key(item_key)
+            .stale_while_revalidate(stale_while_revalidate)
+            .suspense(suspense)
+            .keep_previous_data(keep_previous_data)
+            .tags(tags)
+            .align_page_size_to(align_page_size_to),
     );
 
     cache_controller.init_with_item_window(window);
+    controller.init(
+        window,
+        Callback::new(move |index| {
+            state
+                .current_page()
+                .set(ItemIndex(index).page(item_count_per_page.get_untracked()));
+        }),
+    );
+    controller.init_pages(Callback::new(move |page| {
+        PaginationState::go_to_page(state, PageIndex(page));
+    }));
+
+    let interactions = ItemInteractions::new(link, on_item_context_menu, on_item_activate);
+
+    let impression_tracker = on_item_visible.map(ImpressionTracker::new);
+
+    if let Some(impression_tracker) = impression_tracker {
+        Effect::new(move || {
+            query.track();
+            impression_tracker.reset();
+        });
+    }
 
     let empty_view = move || {
         if let Some(count) = state.page_count().get()
@@ -178,38 +365,82 @@ where
         }
     };
 
+    let start_of_list_view = move || {
+        if window.is_at_start() {
+            start_of_list.clone().map(|s| (s.children)())
+        } else {
+            None
+        }
+    };
+
+    let end_of_list_view = move || {
+        if window.is_at_end() {
+            end_of_list.clone().map(|e| (e.children)())
+        } else {
+            None
+        }
+    };
+
+    // Reading the resource registers it with the surrounding `<Suspense/>`
+    let suspense_view = move || window.suspense.map(|resource| resource.get());
+
     view! {
+        {suspense_view}
         {empty_view}
+        {start_of_list_view}
 
-        <For each=move || window.range.get() key=|idx| *idx let:index>
+        <For each=move || window.displayed_keys() key=|key| *key let:key>
             {
                 let children = children.clone();
                 let loading = loading.clone();
                 let load_error = load_error.clone();
-                move || match &*window.cache.items().at_unkeyed(index).read() {
-                    ItemState::Loaded(item) => {
-                        children
-                            .clone()(WindowItem::new(index, Arc::clone(item), &window))
-                            .into_any()
-                    }
-                    ItemState::Error(error) => {
-                        load_error
-                            .clone()
-                            .map(|e| (e.children)(error.clone()).into_any())
-                            .unwrap_or_else(|| {
+                let index = Memo::new(move |_| window.index_of(key));
+
+                move || {
+                    let Some(index) = index.get() else {
+                        return ().into_any();
+                    };
 
-                                view! { <div style="color: red;">Error: {error.clone()}</div> }
+                    match &*window.cache.items().at_unkeyed(index).read() {
+                        ItemState::Loaded(item) => {
+                            let item = WindowItem::new(index, Arc::clone(item), &window);
+                            let view = interactions
+                                .apply(children.clone()(item.clone()).into_any(), &item, window);
+
+                            if let Some(impression_tracker) = impression_tracker {
+                                view.directive(
+                                        move |el| impression_tracker.observe(el, item.clone()),
+                                        (),
+                                    )
                                     .into_any()
-                            })
-                    }
-                    _ => {
-                        loading
-                            .clone()
-                            .map(|l| (l.children)().into_any())
-                            .unwrap_or_else(|| ().into_any())
+                            } else {
+                                view
+                            }
+                        }
+                        ItemState::Error(error) => {
+                            load_error
+                                .clone()
+                                .map(|e| {
+                                    let error = ItemLoadError::new(index, error.clone(), &window);
+                                    (e.children)(error).into_any()
+                                })
+                                .unwrap_or_else(|| {
+
+                                    view! { <div style="color: red;">Error: {error.to_string()}</div> }
+                                        .into_any()
+                                })
+                        }
+                        _ => {
+                            loading
+                                .clone()
+                                .map(|l| (l.children)().into_any())
+                                .unwrap_or_else(|| ().into_any())
+                        }
                     }
                 }
             }
         </For>
+
+        {end_of_list_view}
     }
 }
@@ -0,0 +1,75 @@
+use leptos::prelude::*;
+use leptos_windowing::WindowItem;
+use reactive_stores::Store;
+
+use crate::{
+    PaginatedFor, PaginationNext, PaginationPages, PaginationPrev, PaginationState,
+    PaginationStateStoreFields,
+};
+
+/// The quickest way to paginate a `Vec` that is already in memory.
+///
+/// It bundles the state, the loader and the default controls of [`PaginatedFor`] into one
+/// component. This is meant for prototypes and small lists. Once you need a different loader or
+/// your own markup, switch to [`PaginatedFor`] and the controls.
+///
+/// The items are displayed in a `<div>` and followed by a `<nav>` with the previous, page and next
+/// buttons. Changing `items` displays the new items.
+///
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos_pagination::PaginatedVec;
+/// #
+/// # #[component]
+/// # pub fn App() -> impl IntoView {
+/// let books = vec!["Dune".to_string(), "Emma".to_string(), "Ulysses".to_string()];
+///
+/// view! {
+///     <PaginatedVec items=books item_count_per_page=2 let:book>
+///         <p>{book.data.to_string()}</p>
+///     </PaginatedVec>
+/// }
+/// # }
+/// ```
+#[component]
+pub fn PaginatedVec<T, CF, V>(
+    /// The items to display.
+    #[prop(into)]
+    items: Signal<Vec<T>>,
+
+    /// How many items to display per page. Defaults to 10.
+    #[prop(into, default = 10.into())]
+    item_count_per_page: Signal<usize>,
+
+    /// The pagination state. Provide this if you want to add more controls or read the current
+    /// page. Defaults to a new state.
+    #[prop(optional)]
+    state: Option<Store<PaginationState>>,
+
+    /// Rendered for every item of the current page.
+    children: CF,
+) -> impl IntoView
+where
+    T: Clone + Send + Sync + 'static,
+    CF: Fn(WindowItem<T>) -> V + Send + Clone + 'static,
+    V: IntoView + 'static,
+{
+    let state = state.unwrap_or_else(PaginationState::new_store);
+
+    // The loader doesn't track the items so they're reloaded through the query
+    let query = Signal::derive(move || items.track());
+
+    view! {
+        <div id=state.list_id().get_untracked()>
+            <PaginatedFor loader=items query state item_count_per_page let:item>
+                {children(item)}
+            </PaginatedFor>
+        </div>
+
+        <nav>
+            <PaginationPrev state>"Previous"</PaginationPrev>
+            <PaginationPages state />
+            <PaginationNext state>"Next"</PaginationNext>
+        </nav>
+    }
+}
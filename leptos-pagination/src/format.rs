@@ -0,0 +1,85 @@
+use std::{fmt::Debug, sync::Arc};
+
+/// Formats numbers like page numbers and item counts for display.
+///
+/// By default this uses [`format_number`] which, with the feature `intl` enabled, formats
+/// numbers according to the user's locale with `Intl.NumberFormat` (e.g. "1,234" or "1.234").
+/// Without the feature numbers are displayed without any grouping.
+///
+/// You can provide your own formatter from any closure:
+///
+/// ```
+/// # use leptos_pagination::NumberFormatter;
+/// #
+/// let formatter = NumberFormatter::from(|n: usize| format!("#{n}"));
+///
+/// assert_eq!(formatter.format(3), "#3");
+/// ```
+#[derive(Clone)]
+pub struct NumberFormatter(Arc<dyn Fn(usize) -> String + Send + Sync>);
+
+impl NumberFormatter {
+    /// Formats the given number.
+    #[inline]
+    pub fn format(&self, number: usize) -> String {
+        (self.0)(number)
+    }
+}
+
+impl Default for NumberFormatter {
+    fn default() -> Self {
+        Self(Arc::new(format_number))
+    }
+}
+
+impl Debug for NumberFormatter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "NumberFormatter")
+    }
+}
+
+impl<F> From<F> for NumberFormatter
+where
+    F: Fn(usize) -> String + Send + Sync + 'static,
+{
+    fn from(f: F) -> Self {
+        Self(Arc::new(f))
+    }
+}
+
+/// Formats a number for display.
+///
+/// With the feature `intl` enabled and running in the browser this uses `Intl.NumberFormat`
+/// with the user's default locale. Otherwise (also during SSR) it simply returns the number as is.
+pub fn format_number(number: usize) -> String {
+    #[cfg(all(feature = "intl", target_arch = "wasm32"))]
+    {
+        intl::format_number(number)
+    }
+
+    #[cfg(not(all(feature = "intl", target_arch = "wasm32")))]
+    {
+        number.to_string()
+    }
+}
+
+#[cfg(all(feature = "intl", target_arch = "wasm32"))]
+mod intl {
+    use js_sys::{Array, Intl::NumberFormat, Object, wasm_bindgen::JsValue};
+
+    thread_local! {
+        static NUMBER_FORMAT: NumberFormat = NumberFormat::new(&Array::new(), &Object::new());
+    }
+
+    pub fn format_number(number: usize) -> String {
+        NUMBER_FORMAT
+            .with(|number_format| {
+                number_format
+                    .format()
+                    .call1(&JsValue::NULL, &JsValue::from_f64(number as f64))
+                    .ok()
+                    .and_then(|formatted| formatted.as_string())
+            })
+            .unwrap_or_else(|| number.to_string())
+    }
+}
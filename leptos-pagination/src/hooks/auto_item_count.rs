@@ -0,0 +1,85 @@
+use leptos::prelude::*;
+use leptos_use::{UseElementSizeReturn, core::IntoElementMaybeSignal, use_element_size};
+
+/// Computes how many items fit into a container so it can be used as `item_count_per_page`.
+///
+/// The container is measured and divided by the estimated height of a single item.
+/// This updates whenever the container is resized so for example dashboards fill the
+/// available space without hardcoded page sizes.
+///
+/// This always returns at least 1.
+///
+/// ## Usage
+///
+/// ```
+/// # use std::ops::Range;
+/// #
+/// # use leptos::{html::Div, prelude::*};
+/// # use leptos_pagination::{PaginatedFor, PaginationState, MemoryLoader, use_auto_item_count_per_page};
+/// #
+/// # pub struct BookLoader;
+/// #
+/// # impl MemoryLoader for BookLoader {
+/// #     type Item = String;
+/// #     type Query = ();
+/// #     fn load_items(&self, range: Range<usize>, _query: &()) -> Vec<Self::Item> {
+/// #         vec![]
+/// #     }
+/// #     fn item_count(&self, _query: &()) -> usize {
+/// #         0
+/// #     }
+/// # }
+/// #
+/// # #[component]
+/// # pub fn App() -> impl IntoView {
+/// let state = PaginationState::new_store();
+/// let container = NodeRef::<Div>::new();
+///
+/// // every item is 40px high
+/// let item_count_per_page = use_auto_item_count_per_page(container, 40.0);
+///
+/// view! {
+///     <div node_ref=container style="height: 100%">
+///         <PaginatedFor loader=BookLoader query=() state item_count_per_page let:book>
+///             <div style="height: 40px">{book.data.to_string()}</div>
+///         </PaginatedFor>
+///     </div>
+/// }
+/// # }
+/// ```
+#[must_use]
+pub fn use_auto_item_count_per_page<El, M>(
+    container: El,
+    estimated_item_height: impl Into<Signal<f64>>,
+) -> Signal<usize>
+where
+    El: IntoElementMaybeSignal<web_sys::Element, M>,
+{
+    let estimated_item_height = estimated_item_height.into();
+
+    let UseElementSizeReturn { height, .. } = use_element_size(container);
+
+    Memo::new(move |_| item_count_fitting(height.get(), estimated_item_height.get())).into()
+}
+
+/// How many items of height `item_height` fit into `container_height`. At least 1.
+fn item_count_fitting(container_height: f64, item_height: f64) -> usize {
+    if item_height <= 0.0 || !container_height.is_finite() {
+        return 1;
+    }
+
+    ((container_height / item_height).floor() as usize).max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_item_count_fitting() {
+        assert_eq!(item_count_fitting(400.0, 40.0), 10);
+        assert_eq!(item_count_fitting(419.0, 40.0), 10);
+        assert_eq!(item_count_fitting(0.0, 40.0), 1);
+        assert_eq!(item_count_fitting(400.0, 0.0), 1);
+    }
+}
@@ -1,9 +1,8 @@
 use default_struct_builder::DefaultBuilder;
-use leptos::prelude::*;
-use leptos_use::math::{use_not, use_or};
+use leptos::{ev, prelude::*, wasm_bindgen::JsCast};
 use reactive_stores::Store;
 
-use crate::{PaginationState, PaginationStateStoreFields};
+use crate::{PageIndex, PaginationState, PaginationStateStoreFields};
 
 /// Hook for pagination page controls.
 ///
@@ -19,77 +18,178 @@ pub fn use_pagination_controls(
     let UsePaginationControlsOptions {
         display_page_count,
         margin_page_count,
+        placeholder_page_count,
     } = options;
 
-    let page_count = Signal::derive(move || state.page_count().get().unwrap_or_default());
+    let is_page_count_known = Signal::derive(move || state.page_count().get().is_some());
 
-    let additional_page_count = display_page_count / 2;
-
-    let current_page: Signal<usize> = state.current_page().into();
-    let current_range_start =
-        Signal::derive(move || current_page.get().saturating_sub(additional_page_count));
-    let current_range_end =
-        Signal::derive(move || current_page.get().saturating_add(additional_page_count));
-
-    let merge_current_with_start =
-        Memo::new(move |_| current_range_start.get() <= margin_page_count);
-    let merge_current_with_end = Memo::new(move |_| {
-        current_range_end.get() + 1 >= page_count.get().saturating_sub(margin_page_count)
+    let page_count = Signal::derive(move || {
+        state
+            .page_count()
+            .get()
+            .or(placeholder_page_count)
+            .unwrap_or_default()
     });
 
-    let start_range_end = Signal::derive(move || {
-        if merge_current_with_start.get() {
-            current_range_end.get()
-        } else {
-            margin_page_count + 1
-        }
-    });
+    let current_page: Signal<PageIndex> = state.current_page().into();
 
-    let end_range_start = Signal::derive(move || {
-        if merge_current_with_end.get() {
-            current_range_start.get() + 1
-        } else {
-            page_count.get().saturating_sub(margin_page_count + 1)
-        }
+    let ranges = Memo::new(move |_| {
+        page_ranges(
+            page_count.get(),
+            current_page.get().0,
+            display_page_count,
+            margin_page_count,
+        )
     });
 
-    let merge_all = Signal::derive(move || start_range_end.get() + 1 >= end_range_start.get());
-
     PaginationControls {
         current_page,
-        start_range: Memo::new(move |_| {
-            let end = if merge_all.get() {
-                page_count.get()
-            } else {
-                start_range_end.get()
-            };
-
-            (0..end).collect()
-        })
-        .into(),
-        end_range: Memo::new(move |_| {
-            if merge_all.get() {
-                vec![]
-            } else {
-                let start = end_range_start.get();
-                let end = page_count.get();
-                (start..end).collect()
+        start_range: Memo::new(move |_| ranges.read().start.clone()).into(),
+        end_range: Memo::new(move |_| ranges.read().end.clone()).into(),
+        current_range: Memo::new(move |_| ranges.read().current.clone()).into(),
+        show_separator_before: Memo::new(move |_| ranges.read().show_separator_before).into(),
+        show_separator_after: Memo::new(move |_| ranges.read().show_separator_after).into(),
+        page_count_error: state.page_count_error().into(),
+        is_page_count_known,
+    }
+}
+
+/// The number of page buttons that [`PaginationPages`](crate::PaginationPages) renders with these
+/// options in the common case of many pages with the current page in the middle.
+///
+/// Use this to reserve space for the controls while the page count is loading.
+/// See also [`PaginationSkeleton`](crate::PaginationSkeleton).
+pub fn expected_page_button_count(display_page_count: usize, margin_page_count: usize) -> usize {
+    let ranges = common_page_ranges(display_page_count, margin_page_count);
+
+    ranges.start.len() + ranges.current.len() + ranges.end.len()
+}
+
+/// The ranges in the common case of many pages with the current page in the middle.
+pub(crate) fn common_page_ranges(
+    display_page_count: usize,
+    margin_page_count: usize,
+) -> PageRanges {
+    let page_count = 2 * (display_page_count + 2 * margin_page_count + 2) + 1;
+
+    page_ranges(
+        page_count,
+        page_count / 2,
+        display_page_count,
+        margin_page_count,
+    )
+}
+
+/// Navigates to the next and previous page with the PageDown and PageUp keys while `state` is
+/// alive. Key presses in text fields are ignored.
+pub fn use_page_keys(state: Store<PaginationState>) {
+    // Effects only run in the browser
+    Effect::new(move || {
+        let handle = window_event_listener(ev::keydown, move |evt| {
+            let is_editing = evt
+                .target()
+                .and_then(|target| target.dyn_into::<web_sys::HtmlElement>().ok())
+                .is_some_and(|target| {
+                    target.is_content_editable()
+                        || matches!(target.tag_name().as_str(), "INPUT" | "TEXTAREA" | "SELECT")
+                });
+
+            if evt.default_prevented() || is_editing {
+                return;
             }
-        })
-        .into(),
-        current_range: Memo::new(move |_| {
-            if merge_current_with_start.get() || merge_current_with_end.get() || merge_all.get() {
-                vec![]
-            } else {
-                let start = current_page.get() - margin_page_count;
-                let end = current_page.get() + margin_page_count;
-                (start..=end).collect()
+
+            match evt.key().as_str() {
+                "PageDown" => {
+                    evt.prevent_default();
+                    PaginationState::next(state);
+                }
+                "PageUp" => {
+                    evt.prevent_default();
+                    PaginationState::prev(state);
+                }
+                _ => {}
             }
-        })
-        .into(),
-        show_separator_before: use_not(use_or(merge_current_with_start, merge_all)),
-        show_separator_after: use_not(use_or(merge_current_with_end, merge_all)),
-        page_count_error: state.page_count_error().into(),
+        });
+
+        on_cleanup(move || handle.remove());
+    });
+}
+
+/// The position of the page button to focus when `key` is pressed on the button at `position`
+/// of `count` buttons. `None` if the key doesn't move the focus.
+pub(crate) fn page_button_focus_target(key: &str, position: usize, count: usize) -> Option<usize> {
+    let last = count.checked_sub(1)?;
+
+    match key {
+        "ArrowLeft" | "ArrowUp" => Some(position.saturating_sub(1)),
+        "ArrowRight" | "ArrowDown" => Some((position + 1).min(last)),
+        "Home" => Some(0),
+        "End" => Some(last),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PageRanges {
+    pub start: Vec<PageIndex>,
+    pub current: Vec<PageIndex>,
+    pub end: Vec<PageIndex>,
+    pub show_separator_before: bool,
+    pub show_separator_after: bool,
+}
+
+/// Computes the page ranges that are returned by [`use_pagination_controls`].
+fn page_ranges(
+    page_count: usize,
+    current_page: usize,
+    display_page_count: usize,
+    margin_page_count: usize,
+) -> PageRanges {
+    let additional_page_count = display_page_count / 2;
+
+    let current_range_start = current_page.saturating_sub(additional_page_count);
+    let current_range_end = current_page.saturating_add(additional_page_count);
+
+    let merge_current_with_start = current_range_start <= margin_page_count;
+    let merge_current_with_end =
+        current_range_end + 1 >= page_count.saturating_sub(margin_page_count);
+
+    let start_range_end = if merge_current_with_start {
+        current_range_end
+    } else {
+        margin_page_count + 1
+    };
+
+    let end_range_start = if merge_current_with_end {
+        current_range_start + 1
+    } else {
+        page_count.saturating_sub(margin_page_count + 1)
+    };
+
+    let merge_all = start_range_end + 1 >= end_range_start;
+
+    let start_end = if merge_all {
+        page_count
+    } else {
+        start_range_end
+    };
+
+    PageRanges {
+        start: (0..start_end).map(PageIndex).collect(),
+        end: if merge_all {
+            vec![]
+        } else {
+            (end_range_start..page_count).map(PageIndex).collect()
+        },
+        current: if merge_current_with_start || merge_current_with_end || merge_all {
+            vec![]
+        } else {
+            (current_page - margin_page_count..=current_page + margin_page_count)
+                .map(PageIndex)
+                .collect()
+        },
+        show_separator_before: !(merge_current_with_start || merge_all),
+        show_separator_after: !(merge_current_with_end || merge_all),
     }
 }
 
@@ -102,7 +202,7 @@ pub struct PaginationControls {
     /// If the page count couldn't be determined, this signal will contain an error message.
     pub page_count_error: Signal<Option<String>>,
 
-    pub current_page: Signal<usize>,
+    pub current_page: Signal<PageIndex>,
 
     /// The range of pages at the start of the pagination.
     ///
@@ -113,7 +213,7 @@ pub struct PaginationControls {
     ///
     /// If there are so few pages that all the ranges (start, end, current) need to be merged into one range then they will all
     /// be merged into this start range and they will be returned emtpy.
-    pub start_range: Signal<Vec<usize>>,
+    pub start_range: Signal<Vec<PageIndex>>,
 
     /// The range of pages at the end of the pagination.
     ///
@@ -124,17 +224,21 @@ pub struct PaginationControls {
     ///
     /// If there are so few pages that all the ranges (start, end, current) need to be merged into one range then they will all
     /// be merged into the start range and this will be empty.
-    pub end_range: Signal<Vec<usize>>,
+    pub end_range: Signal<Vec<PageIndex>>,
 
     /// The current range of pages. This will be empty if the current range is too close to the start or the end. In this case
     /// the range will be merged with the start or end range.
-    pub current_range: Signal<Vec<usize>>,
+    pub current_range: Signal<Vec<PageIndex>>,
 
     /// Whether to show a separator (usually an ellipsis "...") before the current range.
     pub show_separator_before: Signal<bool>,
 
     /// Whether to show a separator (usually an ellipsis "...") after the current range.
     pub show_separator_after: Signal<bool>,
+
+    /// Whether the page count has been determined. Until then the ranges are computed with the
+    /// `placeholder_page_count`.
+    pub is_page_count_known: Signal<bool>,
 }
 
 /// Options for [`use_pagination`].
@@ -152,6 +256,13 @@ pub struct UsePaginationControlsOptions {
     ///
     /// Default is 1.
     margin_page_count: usize,
+
+    /// The number of pages that is assumed while the page count isn't known yet.
+    ///
+    /// This way the controls don't shift the layout when the page count arrives.
+    ///
+    /// Default is `None` which means no pages are shown until the page count is known.
+    placeholder_page_count: Option<usize>,
 }
 
 impl Default for UsePaginationControlsOptions {
@@ -159,6 +270,44 @@ impl Default for UsePaginationControlsOptions {
         Self {
             display_page_count: 5,
             margin_page_count: 1,
+            placeholder_page_count: None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_ranges() {
+        let ranges = page_ranges(100, 50, 5, 1);
+        assert_eq!(ranges.start, [PageIndex(0), PageIndex(1)]);
+        assert_eq!(
+            ranges.current,
+            [PageIndex(49), PageIndex(50), PageIndex(51)]
+        );
+        assert_eq!(ranges.end, [PageIndex(98), PageIndex(99)]);
+        assert!(ranges.show_separator_before && ranges.show_separator_after);
+
+        let ranges = page_ranges(4, 0, 5, 1);
+        assert_eq!(ranges.start.len(), 4);
+        assert!(ranges.current.is_empty() && ranges.end.is_empty());
+        assert!(!ranges.show_separator_before && !ranges.show_separator_after);
+
+        assert_eq!(expected_page_button_count(5, 1), 7);
+        assert_eq!(expected_page_button_count(3, 0), 3);
+    }
+
+    #[test]
+    fn test_page_button_focus_target() {
+        assert_eq!(page_button_focus_target("ArrowRight", 2, 7), Some(3));
+        assert_eq!(page_button_focus_target("ArrowRight", 6, 7), Some(6));
+        assert_eq!(page_button_focus_target("ArrowLeft", 0, 7), Some(0));
+        assert_eq!(page_button_focus_target("ArrowUp", 3, 7), Some(2));
+        assert_eq!(page_button_focus_target("Home", 3, 7), Some(0));
+        assert_eq!(page_button_focus_target("End", 3, 7), Some(6));
+        assert_eq!(page_button_focus_target("Enter", 3, 7), None);
+        assert_eq!(page_button_focus_target("End", 0, 0), None);
+    }
+}
@@ -0,0 +1,124 @@
+use std::time::Duration;
+
+use default_struct_builder::DefaultBuilder;
+use leptos::prelude::*;
+use leptos_use::{
+    UseIntersectionObserverOptions,
+    core::{ElementMaybeSignal, IntoElementMaybeSignal},
+    use_intersection_observer_with_options,
+};
+use leptos_windowing::{InternalLoader, ItemWindow};
+use reactive_stores::Store;
+
+use crate::{LoadMoreState, LoadMoreStateStoreFields, UseLoadMoreOptions, use_load_more};
+
+/// Hook for the infinite scroll logic.
+///
+/// This is [`use_load_more`] that appends more items automatically as soon as the `sentinel`
+/// element, usually an empty element after the last item, comes into view.
+///
+/// This is the logic of [`InfiniteFor`](crate::InfiniteFor).
+///
+/// ## Parameters
+///
+/// - `state`: The load more state. Used to communicate between the controls and the list.
+/// - `sentinel`: The element that triggers loading more items when it becomes visible.
+/// - `loader`: The loader used to load items from the data source.
+/// - `query`: The query to load the items with. Changing it starts over from the first items.
+/// - `item_count_per_load`: How many items are appended every time more items are loaded.
+/// - `options`: Additional options. See [`UseInfiniteScrollOptions`].
+#[must_use]
+pub fn use_infinite_scroll<T, L, Q, M, El, ElM>(
+    state: Store<LoadMoreState>,
+    sentinel: El,
+    loader: L,
+    query: impl Into<Signal<Q>>,
+    item_count_per_load: impl Into<Signal<usize>>,
+    options: UseInfiniteScrollOptions,
+) -> ItemWindow<T>
+where
+    T: Send + Sync + 'static,
+    L: InternalLoader<M, Item = T, Query = Q> + 'static,
+    L::Error: Send + Sync,
+    Q: Send + Sync + 'static,
+    El: IntoElementMaybeSignal<web_sys::Element, ElM>,
+{
+    let UseInfiniteScrollOptions {
+        overscan_load_count,
+        load_margin_px,
+        error_ttl,
+        tags,
+    } = options;
+
+    let item_window = use_load_more(
+        state,
+        loader,
+        query,
+        item_count_per_load,
+        UseLoadMoreOptions::default()
+            .overscan_load_count(overscan_load_count)
+            .error_ttl(error_ttl)
+            .tags(tags),
+    );
+
+    let sentinel: ElementMaybeSignal<web_sys::Element> = sentinel.into_element_maybe_signal();
+    let sentinel_visible = RwSignal::new(false);
+
+    let _ = use_intersection_observer_with_options(
+        sentinel,
+        move |entries, _| {
+            sentinel_visible.set(entries.iter().any(|entry| entry.is_intersecting()));
+        },
+        UseIntersectionObserverOptions::default()
+            .root_margin(format!("0px 0px {load_margin_px}px 0px")),
+    );
+
+    // Keep appending while the sentinel stays visible, e.g. if the appended items are short.
+    Effect::new(move || {
+        if sentinel_visible.get()
+            && !state.is_loading_more().get()
+            && !LoadMoreState::is_end_reached(state)
+        {
+            untrack(|| LoadMoreState::load_more(state));
+        }
+    });
+
+    item_window
+}
+
+/// Options for [`use_infinite_scroll`].
+#[derive(Debug, Clone, DefaultBuilder)]
+pub struct UseInfiniteScrollOptions {
+    /// How many loads worth of items to load ahead of the displayed items.
+    ///
+    /// A value of 1 means that the items for the next load are already loaded in the background.
+    /// Defaults to 1.
+    overscan_load_count: usize,
+
+    /// How many pixels before the sentinel becomes visible more items are appended.
+    ///
+    /// Defaults to 200.
+    load_margin_px: u32,
+
+    /// How long items that failed to load keep their error. Afterwards they are loaded again the
+    /// next time more items are loaded.
+    /// Defaults to `None` which keeps the errors until the list is reloaded.
+    error_ttl: Option<Duration>,
+
+    /// Invalidation tags of the data of this list like `"customers"`. Calling
+    /// [`invalidate_tag`](crate::invalidate_tag) with one of them reloads the list while the
+    /// loaded items stay displayed.
+    /// Defaults to no tags.
+    tags: Vec<String>,
+}
+
+impl Default for UseInfiniteScrollOptions {
+    fn default() -> Self {
+        Self {
+            overscan_load_count: 1,
+            load_margin_px: 200,
+            error_ttl: None,
+            tags: Vec::new(),
+        }
+    }
+}
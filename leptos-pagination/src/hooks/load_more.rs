@@ -0,0 +1,180 @@
+use std::time::Duration;
+
+use default_struct_builder::DefaultBuilder;
+use leptos::prelude::*;
+use leptos_windowing::{
+    InternalLoader, ItemWindow,
+    hook::{UseLoadOnDemandOptions, UseLoadOnDemandResult, use_load_on_demand},
+    item_state::ItemState,
+};
+use reactive_stores::Store;
+
+use crate::{LoadMoreState, LoadMoreStateStoreFields};
+
+/// Hook for the "load more" logic.
+///
+/// Instead of displaying pages this displays all items from the start and appends
+/// `item_count_per_load` more items whenever [`LoadMoreState::load_more`] is called, e.g. by the
+/// [`LoadMore`](crate::LoadMore) button. Loading and caching is handled the same way as in
+/// [`use_pagination`](crate::use_pagination).
+///
+/// It returns an [`ItemWindow`] that is in effect a signal of the items to display.
+///
+/// ## Usage
+///
+/// ```
+/// # use std::ops::Range;
+/// #
+/// # use leptos_pagination::{use_load_more, UseLoadMoreOptions, LoadMoreState, MemoryLoader};
+/// #
+/// # pub struct ExampleLoader;
+/// #
+/// # impl MemoryLoader for ExampleLoader {
+/// #     type Item = usize;
+/// #     type Query = ();
+/// #
+/// #     fn load_items(&self, range: Range<usize>, _query: &Self::Query) -> Vec<Self::Item> {
+/// #         range.collect()
+/// #     }
+/// #
+/// #     fn item_count(&self, _query: &Self::Query) -> usize {
+/// #         1000
+/// #     }
+/// # }
+/// #
+/// # // Effects need an executor when they're enabled by the feature `test-utils`
+/// # #[cfg(feature = "test-utils")]
+/// # let _runtime = leptos_pagination::testing::TestRuntime::new();
+/// let state = LoadMoreState::new_store();
+///
+/// let window = use_load_more(
+///     state,
+///     ExampleLoader,
+///     (),
+///     20, // items per load
+///     UseLoadMoreOptions::default(),
+/// );
+///
+/// // Call this to append the next 20 items
+/// LoadMoreState::load_more(state);
+/// ```
+///
+/// ## Parameters
+///
+/// - `state`: The load more state. Used to communicate between the controls and the list.
+/// - `loader`: The loader used to load items from the data source.
+/// - `query`: The query to load the items with. Changing it starts over from the first items.
+/// - `item_count_per_load`: How many items are appended every time more items are loaded.
+/// - `options`: Additional options. See [`UseLoadMoreOptions`].
+#[must_use]
+pub fn use_load_more<T, L, Q, M>(
+    state: Store<LoadMoreState>,
+    loader: L,
+    query: impl Into<Signal<Q>>,
+    item_count_per_load: impl Into<Signal<usize>>,
+    options: UseLoadMoreOptions,
+) -> ItemWindow<T>
+where
+    T: Send + Sync + 'static,
+    L: InternalLoader<M, Item = T, Query = Q> + 'static,
+    L::Error: Send + Sync,
+    Q: Send + Sync + 'static,
+{
+    let UseLoadMoreOptions {
+        overscan_load_count,
+        error_ttl,
+        tags,
+    } = options;
+
+    let query = query.into();
+    let item_count_per_load = item_count_per_load.into();
+
+    state
+        .displayed_count()
+        .set(item_count_per_load.get_untracked());
+
+    Effect::new(move || {
+        state.item_count_per_load().set(item_count_per_load.get());
+    });
+
+    // Start over when the query changes
+    Effect::new(move |prev: Option<()>| {
+        query.track();
+
+        if prev.is_some() {
+            state
+                .displayed_count()
+                .set(item_count_per_load.get_untracked());
+        }
+    });
+
+    let range_to_load = Memo::new(move |_| {
+        0..state.displayed_count().get() + overscan_load_count * item_count_per_load.get()
+    });
+
+    let range_to_display = Memo::new(move |_| 0..state.displayed_count().get());
+
+    let UseLoadOnDemandResult { item_window, .. } = use_load_on_demand(
+        range_to_load,
+        range_to_display,
+        loader,
+        query,
+        UseLoadOnDemandOptions::default()
+            .error_ttl(error_ttl)
+            .tags(tags),
+    );
+
+    // Loads complete in whole ranges so checking the last displayed item is enough.
+    let is_loading_more = Memo::new(move |_| {
+        let end = item_window.range.get().end;
+
+        end > 0
+            && item_window.cache.items().with(|items| {
+                matches!(
+                    items.get(end - 1),
+                    None | Some(ItemState::Loading | ItemState::Placeholder)
+                )
+            })
+    });
+
+    Effect::new(move || {
+        state.is_loading_more().set(is_loading_more.get());
+    });
+
+    Effect::new(move || {
+        state.item_count().set(item_window.cache.item_count().get());
+    });
+
+    item_window
+}
+
+/// Options for [`use_load_more`].
+#[derive(Debug, Clone, DefaultBuilder)]
+pub struct UseLoadMoreOptions {
+    /// How many loads worth of items to load ahead of the displayed items.
+    ///
+    /// A value of 1 means that the items for the next load are already loaded in the background.
+    /// Defaults to 1.
+    overscan_load_count: usize,
+
+    /// How long items that failed to load keep their error. Afterwards they are loaded again the
+    /// next time more items are loaded.
+    /// Defaults to `None` which keeps the errors until the list is reloaded.
+    error_ttl: Option<Duration>,
+
+    /// Invalidation tags of the data of this list like `"customers"`. Calling
+    /// [`invalidate_tag`](crate::invalidate_tag) with one of them reloads the list while the
+    /// loaded items stay displayed.
+    /// Defaults to no tags.
+    tags: Vec<String>,
+}
+
+impl Default for UseLoadMoreOptions {
+    fn default() -> Self {
+        Self {
+            overscan_load_count: 1,
+            error_ttl: None,
+            tags: Vec::new(),
+        }
+    }
+}
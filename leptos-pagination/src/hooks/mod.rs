@@ -1,5 +1,15 @@
+mod auto_item_count;
 mod controls;
+mod infinite_scroll;
+mod load_more;
+mod page_history;
 mod pagination;
+mod state_history;
 
+pub use auto_item_count::*;
 pub use controls::*;
+pub use infinite_scroll::*;
+pub use load_more::*;
+pub use page_history::*;
 pub use pagination::*;
+pub use state_history::*;
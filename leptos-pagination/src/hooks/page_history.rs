@@ -0,0 +1,182 @@
+use default_struct_builder::DefaultBuilder;
+use leptos::{ev, prelude::*, wasm_bindgen::JsValue};
+use reactive_stores::Store;
+use web_sys::js_sys::{Object, Reflect};
+
+use crate::{PageIndex, PaginationState, PaginationStateStoreFields};
+
+/// Hook that makes page changes part of the browser history.
+///
+/// Every page change creates a history entry (or replaces the current one, see [`HistoryMode`])
+/// and the back and forward buttons of the browser restore the page of the entry.
+///
+/// With `query_param` the page number is also written into the URL, e.g. `?page=3`, and restored
+/// from there when the page is opened. This happens after hydration so the server renders the
+/// first page.
+///
+/// The page is stored as a property of the history state so the state of other code, e.g. a
+/// router, is kept. Entries without it, e.g. the ones created by a router, are left alone when
+/// navigating back and forward.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos_pagination::{PaginationState, UsePageHistoryOptions, use_page_history};
+/// #
+/// # #[component]
+/// # pub fn App() -> impl IntoView {
+/// let state = PaginationState::new_store();
+///
+/// use_page_history(state, UsePageHistoryOptions::default().query_param(Some("page".to_string())));
+///
+/// // Use `state` for `PaginatedFor` and the controls as usual
+/// # }
+/// ```
+pub fn use_page_history(state: Store<PaginationState>, options: UsePageHistoryOptions) {
+    let UsePageHistoryOptions { mode, query_param } = options;
+
+    // Effects only run in the browser
+    Effect::new(move |prev: Option<()>| {
+        let current_page = state.current_page().get();
+
+        let Some(history) = window().history().ok() else {
+            return;
+        };
+
+        if prev.is_none() {
+            let page = query_param
+                .as_deref()
+                .and_then(page_from_url)
+                .unwrap_or(current_page);
+
+            // The initial entry has to know its page as well to be restored.
+            let _ = history.replace_state(&page_state(history.state().ok(), page), "");
+            state.current_page().set(page);
+            return;
+        }
+
+        // Navigating with back and forward already restored the page of the entry.
+        if history.state().ok().and_then(page_from_js) == Some(current_page) {
+            return;
+        }
+
+        let url = query_param
+            .as_deref()
+            .and_then(|query_param| page_url(query_param, current_page));
+
+        let history_state = page_state(history.state().ok(), current_page);
+
+        let _ = match mode {
+            HistoryMode::Push => history.push_state_with_url(&history_state, "", url.as_deref()),
+            HistoryMode::Replace => {
+                history.replace_state_with_url(&history_state, "", url.as_deref())
+            }
+        };
+    });
+
+    Effect::new(move || {
+        let handle = window_event_listener(ev::popstate, move |event| {
+            if let Some(page) = page_from_js(event.state()) {
+                state.current_page().set(page);
+            }
+        });
+
+        on_cleanup(move || handle.remove());
+    });
+}
+
+/// How [`use_page_history`] records page changes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HistoryMode {
+    /// Every page change adds a history entry so back goes to the previous page.
+    #[default]
+    Push,
+    /// Page changes replace the current history entry. Back leaves the list but the page is
+    /// restored when coming back.
+    Replace,
+}
+
+/// Options for [`use_page_history`].
+#[derive(DefaultBuilder, Debug, Clone, Default)]
+pub struct UsePageHistoryOptions {
+    /// Whether to add a history entry for every page change or to replace the current one.
+    /// Defaults to [`HistoryMode::Push`].
+    mode: HistoryMode,
+
+    /// The name of the URL query parameter that contains the 1-based page number.
+    /// Defaults to `None` which leaves the URL unchanged.
+    query_param: Option<String>,
+}
+
+/// The property of the history state that contains the page index.
+const PAGE_STATE_KEY: &str = "leptosPaginationPage";
+
+/// Copies the history state `previous` and sets the page in the copy.
+fn page_state(previous: Option<JsValue>, page: PageIndex) -> JsValue {
+    let state = Object::new();
+
+    if let Some(previous) = previous.filter(JsValue::is_object) {
+        Object::assign(&state, &Object::from(previous));
+    }
+
+    let _ = Reflect::set(
+        &state,
+        &PAGE_STATE_KEY.into(),
+        &JsValue::from_f64(page.0 as f64),
+    );
+
+    state.into()
+}
+
+fn page_from_js(state: JsValue) -> Option<PageIndex> {
+    if !state.is_object() {
+        return None;
+    }
+
+    Reflect::get(&state, &PAGE_STATE_KEY.into())
+        .ok()?
+        .as_f64()
+        .filter(|page| *page >= 0.0)
+        .map(|page| PageIndex(page as usize))
+}
+
+fn page_from_url(query_param: &str) -> Option<PageIndex> {
+    let search = window().location().search().ok()?;
+    let number = web_sys::UrlSearchParams::new_with_str(&search)
+        .ok()?
+        .get(query_param)?;
+
+    parse_page_number(&number)
+}
+
+fn page_url(query_param: &str, page: PageIndex) -> Option<String> {
+    let url = web_sys::Url::new(&window().location().href().ok()?).ok()?;
+    url.search_params()
+        .set(query_param, &page.number().to_string());
+
+    Some(url.href())
+}
+
+/// Parses a 1-based page number.
+fn parse_page_number(number: &str) -> Option<PageIndex> {
+    number
+        .trim()
+        .parse::<usize>()
+        .ok()
+        .and_then(|number| number.checked_sub(1))
+        .map(PageIndex)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_page_number() {
+        assert_eq!(parse_page_number("3"), Some(PageIndex(2)));
+        assert_eq!(parse_page_number(" 1 "), Some(PageIndex::FIRST));
+        assert_eq!(parse_page_number("0"), None);
+        assert_eq!(parse_page_number("abc"), None);
+    }
+}
@@ -1,20 +1,34 @@
-use std::fmt::Debug;
+use std::{
+    fmt::Debug,
+    ops::Range,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
 
 use default_struct_builder::DefaultBuilder;
-use leptos::prelude::*;
+use leptos::{
+    prelude::*,
+    wasm_bindgen::{JsCast, closure::Closure},
+};
 use leptos_windowing::{
-    InternalLoader, ItemWindow,
-    hook::{UseLoadOnDemandResult, use_load_on_demand},
+    AdaptiveChunkSize, InternalLoader, ItemWindow, RetryPolicy, WindowLink,
+    cache::ItemKey,
+    hook::{UseLoadOnDemandOptions, UseLoadOnDemandResult, use_load_on_demand},
+    item_state::ItemState,
 };
 use reactive_stores::Store;
 
-use crate::{PaginationState, PaginationStateStoreFields};
+use crate::{ItemIndex, PageIndex, PaginationState, PaginationStateStoreFields, math};
 
 /// Hook for the pagination logic.
 ///
 /// This handles loading items on-demand from the data source and caching them.
 ///
-/// It returns an [`ItemWindow`] that is in effect a signal of the items to display.
+/// It returns an [`ItemWindow`] that is in effect a signal of the items to display as part of
+/// [`UsePaginationResult`].
 ///
 /// ## Usage
 ///
@@ -23,6 +37,9 @@ use crate::{PaginationState, PaginationStateStoreFields};
 /// #
 /// # use leptos_pagination::{use_pagination, use_pagination_controls, UsePaginationOptions, UsePaginationControlsOptions, PaginationState, MemoryLoader};
 /// #
+/// # // Effects need an executor when they're enabled by the feature `test-utils`
+/// # #[cfg(feature = "test-utils")]
+/// # let _runtime = leptos_pagination::testing::TestRuntime::new();
 /// let state = PaginationState::new_store();
 ///
 /// pub struct ExampleItem {
@@ -54,7 +71,8 @@ use crate::{PaginationState, PaginationStateStoreFields};
 ///     (),
 ///     20, // items per page
 ///     UsePaginationOptions::default(),
-/// );
+/// )
+/// .item_window;
 ///
 /// // Use this to control the pagination
 /// let pagination_controls = use_pagination_controls(state, UsePaginationControlsOptions::default());
@@ -64,7 +82,8 @@ use crate::{PaginationState, PaginationStateStoreFields};
 ///
 /// - `state`: The pagination state. Used to communicate between the pagination controls and this component.
 /// - `loader`: The loader used to load items from the data source.
-/// - `item_count_per_page`: The number of items to display per page.
+/// - `item_count_per_page`: The number of items to display per page. It can be changed by the user with
+///   [`PaginationPageSize`](crate::PaginationPageSize) until it changes itself.
 /// - `options`: Additional options for the pagination logic.
 #[must_use]
 pub fn use_pagination<T, L, Q, M>(
@@ -72,8 +91,8 @@ pub fn use_pagination<T, L, Q, M>(
     loader: L,
     query: impl Into<Signal<Q>>,
     item_count_per_page: impl Into<Signal<usize>>,
-    options: UsePaginationOptions,
-) -> ItemWindow<T>
+    options: UsePaginationOptions<T>,
+) -> UsePaginationResult<T>
 where
     T: Send + Sync + 'static,
     L: InternalLoader<M, Item = T, Query = Q> + 'static,
@@ -82,49 +101,204 @@ where
 {
     let UsePaginationOptions {
         overscan_page_count,
+        initial_page,
+        link,
+        retry,
+        max_cached_items,
+        ttl,
+        error_ttl,
+        item_key,
+        stale_while_revalidate,
+        defer_overscan,
+        idle_warm_page_count,
+        suspense,
+        keep_previous_data,
+        tags,
+        align_page_size_to,
+        adaptive_chunk_size,
     } = options;
 
-    let item_count_per_page = item_count_per_page.into();
+    if let Some(initial_page) = initial_page {
+        state.current_page().set(initial_page);
+    }
 
-    let item_count = RwSignal::new(None::<usize>);
+    if error_ttl.is_some() {
+        state.error_ttl().set(error_ttl);
+    }
 
-    Effect::new(move || {
-        if let Some(item_count) = item_count.get() {
-            state
-                .page_count()
-                .set(Some(item_count.div_ceil(item_count_per_page.get())));
+    let initial_item_count_per_page = item_count_per_page.into();
+
+    // The page size chosen by the user takes precedence until `item_count_per_page` changes.
+    Effect::new(move |prev: Option<()>| {
+        let item_count_per_page = initial_item_count_per_page.get();
+
+        if prev.is_some() || state.page_size().get_untracked().is_none() {
+            state.page_size().set(Some(item_count_per_page));
         }
     });
 
-    let start_index_to_load = Signal::derive(move || {
-        let current_page = state.current_page().get();
-        current_page.saturating_sub(overscan_page_count) * item_count_per_page.get()
+    let page_size = Signal::derive(move || {
+        state
+            .page_size()
+            .get()
+            .unwrap_or_else(|| initial_item_count_per_page.get())
+            .max(1)
     });
 
-    let end_index_to_load = Signal::derive(move || {
-        let current_page = state.current_page().get();
-        (current_page + overscan_page_count) * item_count_per_page.get()
+    let item_count_per_page = Memo::new(move |_| match align_page_size_to {
+        Some(columns) => math::align_page_size(page_size.get(), columns.get()),
+        None => page_size.get(),
     });
 
-    let range_to_load = Memo::new(move |_| {
-        let start_index = start_index_to_load.get();
-        let end_index = end_index_to_load.get();
+    // Keep the first displayed item on the current page when the number of items per page
+    // changes, be it the page size or the number of columns.
+    Effect::new(move |prev: Option<usize>| {
+        let item_count_per_page = item_count_per_page.get();
 
-        start_index..end_index
+        if let Some(prev) = prev
+            && prev != item_count_per_page
+        {
+            let first_item = state.current_page().get_untracked().first_item(prev);
+            state
+                .current_page()
+                .set(first_item.page(item_count_per_page));
+        }
+
+        item_count_per_page
+    });
+
+    let item_count = RwSignal::new(None::<usize>);
+
+    Effect::new(move || {
+        if let Some(item_count) = item_count.get() {
+            state.page_count().set(Some(math::page_count(
+                item_count,
+                item_count_per_page.get(),
+            )));
+        }
     });
 
     let range_to_display = Memo::new(move |_| {
-        let item_count_per_page = item_count_per_page.get();
-        let start_index = state.current_page().get() * item_count_per_page;
-        let end_index = start_index + item_count_per_page;
+        state
+            .current_page()
+            .get()
+            .item_range(item_count_per_page.get())
+    });
+
+    let is_overscan_deferred = RwSignal::new(defer_overscan);
+    if defer_overscan {
+        Effect::new(move |prev: Option<()>| {
+            state.current_page().track();
+
+            if prev.is_some() && is_overscan_deferred.get_untracked() {
+                is_overscan_deferred.set(false);
+            }
+        });
+    }
+
+    let range_to_load = Memo::new(move |_| {
+        if is_overscan_deferred.get() {
+            return range_to_display.get();
+        }
 
-        start_index..end_index
+        math::overscan_range(
+            state.current_page().get(),
+            item_count_per_page.get(),
+            overscan_page_count,
+        )
     });
 
     let UseLoadOnDemandResult {
         item_count_result,
         item_window,
-    } = use_load_on_demand(range_to_load, range_to_display, loader, query);
+        chunk_size,
+    } = use_load_on_demand(
+        range_to_load,
+        range_to_display,
+        loader,
+        query,
+        UseLoadOnDemandOptions::default()
+            .cache(link.map(|link| link.cache()))
+            .retry(retry)
+            .max_cached_items(max_cached_items)
+            .ttl(ttl)
+            .error_ttl(Signal::derive(move || state.error_ttl().get()))
+            .item_key(item_key)
+            .stale_while_revalidate(stale_while_revalidate)
+            .suspense(suspense)
+            .keep_previous_data(keep_previous_data)
+            .tags(tags)
+            .adaptive_chunk_size(adaptive_chunk_size),
+    );
+
+    if let Some(link) = link {
+        link.connect(
+            item_window,
+            Callback::new(move |index| {
+                state
+                    .current_page()
+                    .set(ItemIndex(index).page(item_count_per_page.get_untracked()));
+            }),
+        );
+    }
+
+    Effect::new(move || {
+        if let Some(page) = state.prefetch_page().get() {
+            item_window.prefetch(math::page_range(
+                page,
+                item_count_per_page.get_untracked(),
+                item_count.get_untracked(),
+            ));
+        }
+    });
+
+    if idle_warm_page_count > 0 {
+        let loaded_fraction = item_window.loaded_fraction;
+
+        Effect::new(move || {
+            let current_page = state.current_page().get();
+
+            // Don't compete with the loads of the displayed page
+            if loaded_fraction.get() < 1.0 {
+                return;
+            }
+
+            let item_count_per_page = item_count_per_page.get_untracked();
+            let item_count = item_count.get_untracked();
+
+            let ranges = (1..=idle_warm_page_count)
+                .map(|offset| {
+                    math::page_range(current_page + offset, item_count_per_page, item_count)
+                })
+                .filter(|range| !range.is_empty())
+                .rev()
+                .collect();
+
+            let is_cancelled = Arc::new(AtomicBool::new(false));
+            warm_when_idle(item_window, ranges, Arc::clone(&is_cancelled));
+
+            // Navigating starts over from the new page
+            on_cleanup(move || is_cancelled.store(true, Ordering::Relaxed));
+        });
+    }
+
+    Effect::new(move || {
+        // Tracking the items would rerun this for every single loaded item.
+        item_window.cache.track_load_state();
+
+        let (item_count_per_page, item_count) = (item_count_per_page.get(), item_count.get());
+        let pages_cached = item_window
+            .cache
+            .items()
+            .with_untracked(|items| fully_loaded_pages(items, item_count_per_page, item_count));
+
+        if state
+            .pages_cached()
+            .with_untracked(|pages| *pages != pages_cached)
+        {
+            state.pages_cached().set(pages_cached);
+        }
+    });
 
     Effect::new(move || {
         match &*item_count_result.read() {
@@ -144,22 +318,289 @@ where
         }
     });
 
-    item_window
+    UsePaginationResult {
+        item_window,
+        chunk_size,
+        is_loading: item_window.is_loading,
+        item_count_per_page: item_count_per_page.into(),
+    }
 }
 
-#[derive(Debug, Clone, DefaultBuilder)]
-pub struct UsePaginationOptions {
+/// Return type of [`use_pagination`].
+pub struct UsePaginationResult<T: Send + Sync + 'static> {
+    /// The items of the current page.
+    pub item_window: ItemWindow<T>,
+
+    /// The current number of items per request if [`UsePaginationOptions::adaptive_chunk_size`]
+    /// is set.
+    pub chunk_size: Signal<Option<usize>>,
+
+    /// Whether any item of the current page is being loaded, e.g. to disable the controls
+    /// meanwhile. The same as [`ItemWindow::is_loading`] of `item_window`.
+    pub is_loading: Signal<bool>,
+
+    /// The number of items per page that is actually used. This is the page size of the state or
+    /// `item_count_per_page` aligned to [`UsePaginationOptions::align_page_size_to`].
+    pub item_count_per_page: Signal<usize>,
+}
+
+/// Options for [`use_pagination`].
+#[derive(DefaultBuilder)]
+pub struct UsePaginationOptions<T>
+where
+    T: Send + Sync + 'static,
+{
     /// How many pages to load before and after the current page.
     ///
     /// A value of 1 means that the current page as well as the one before and after will be loaded.
     /// Defaults to 1.
     overscan_page_count: usize,
+
+    /// The page to start on. Counting starts from 0.
+    ///
+    /// This is applied before the first load so deep links land directly on the right page
+    /// instead of loading the first page and then jumping.
+    /// Defaults to `None` which keeps the current page of the state.
+    initial_page: Option<PageIndex>,
+
+    /// Link this window to other windows over the same data.
+    ///
+    /// Linked windows share their cache and navigate together. See [`WindowLink`].
+    /// Defaults to `None`.
+    #[builder(keep_type)]
+    link: Option<WindowLink<T>>,
+
+    /// How failed loads are retried.
+    /// Defaults to [`RetryPolicy::none()`].
+    retry: RetryPolicy,
+
+    /// The maximum number of loaded items to keep in the cache. The items of the pages farthest
+    /// away from the current page are evicted first.
+    /// Defaults to `None` which keeps all items.
+    max_cached_items: Option<usize>,
+
+    /// How long loaded items stay fresh. Stale items are loaded again when their page or one of
+    /// the overscan pages is displayed. They stay displayed meanwhile and are
+    /// [stale](leptos_windowing::cache::Cache::is_stale).
+    /// Defaults to `None` which means items never become stale.
+    ttl: Option<Duration>,
+
+    /// How long items that failed to load keep their error. Afterwards they are loaded again when
+    /// their page or one of the overscan pages is displayed. It's written into
+    /// [`PaginationState::error_ttl`] which can be changed later on.
+    /// Defaults to `None` which keeps the errors until the cache is cleared.
+    error_ttl: Option<Duration>,
+
+    /// Determines the identity of the items so that items that shifted in the data source
+    /// between loads aren't displayed twice. See [`ItemKey`].
+    /// Defaults to `None` which identifies items only by their index.
+    #[builder(keep_type)]
+    item_key: Option<ItemKey<T>>,
+
+    /// Keep displaying the current page when the list is reloaded and replace its items once
+    /// they're loaded again in the background.
+    /// Defaults to `false` which shows the items as loading until they arrive.
+    stale_while_revalidate: bool,
+
+    /// How many pages after the current page to prefetch while the browser is idle.
+    ///
+    /// Once the current page is loaded, one page is prefetched per idle period using
+    /// `requestIdleCallback` so it doesn't compete with interactive loads. Changing the page
+    /// cancels the pages that haven't been requested yet. Browsers without `requestIdleCallback`
+    /// don't prefetch.
+    /// Defaults to `0`.
+    idle_warm_page_count: usize,
+
+    /// Only load the current page until the user changes the page for the first time.
+    ///
+    /// This saves the requests for the overscan pages on landing pages where most users never
+    /// paginate.
+    /// Defaults to `false`.
+    defer_overscan: bool,
+
+    /// Track the loading of the current page with a resource so a surrounding `<Suspense/>` shows
+    /// its fallback and a `<Transition/>` keeps the previous page until the new one is loaded.
+    /// `PaginatedFor` reads the resource for you. If you render the items yourself, read
+    /// [`ItemWindow::suspense`] inside of the `<Suspense/>`.
+    /// Defaults to `false`.
+    suspense: bool,
+
+    /// Keep displaying the current page when the user navigates until the items of the new page
+    /// are loaded instead of showing them as loading. Meanwhile [`ItemWindow::is_pending`] is `true`.
+    /// Defaults to `false`.
+    keep_previous_data: bool,
+
+    /// Invalidation tags of the data of this list like `"customers"`. Calling
+    /// [`invalidate_tag`](crate::invalidate_tag) with one of them reloads the list while the
+    /// current page stays displayed.
+    /// Defaults to no tags.
+    tags: Vec<String>,
+
+    /// The number of columns of a grid that displays the items. The number of items per page is
+    /// rounded down to a multiple of it so the last row of a page is full. When it changes, the
+    /// current page is changed so that the first displayed item is still displayed.
+    /// Defaults to `None` which uses the number of items per page as is.
+    #[builder(keep_type)]
+    align_page_size_to: Option<Signal<usize>>,
+
+    /// Request missing items in chunks whose size adapts to how long the loads take instead of
+    /// one request per missing range. See [`AdaptiveChunkSize`]. The current size is returned as
+    /// [`UsePaginationResult::chunk_size`].
+    /// Defaults to `None`.
+    adaptive_chunk_size: Option<AdaptiveChunkSize>,
 }
 
-impl Default for UsePaginationOptions {
+impl<T> Default for UsePaginationOptions<T>
+where
+    T: Send + Sync + 'static,
+{
     fn default() -> Self {
         Self {
             overscan_page_count: 1,
+            initial_page: None,
+            link: None,
+            retry: RetryPolicy::none(),
+            max_cached_items: None,
+            ttl: None,
+            error_ttl: None,
+            item_key: None,
+            stale_while_revalidate: false,
+            defer_overscan: false,
+            idle_warm_page_count: 0,
+            suspense: false,
+            keep_previous_data: false,
+            tags: Vec::new(),
+            align_page_size_to: None,
+            adaptive_chunk_size: None,
+        }
+    }
+}
+
+impl<T> Clone for UsePaginationOptions<T>
+where
+    T: Send + Sync + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            overscan_page_count: self.overscan_page_count,
+            initial_page: self.initial_page,
+            link: self.link,
+            retry: self.retry,
+            max_cached_items: self.max_cached_items,
+            ttl: self.ttl,
+            error_ttl: self.error_ttl,
+            item_key: self.item_key.clone(),
+            stale_while_revalidate: self.stale_while_revalidate,
+            defer_overscan: self.defer_overscan,
+            idle_warm_page_count: self.idle_warm_page_count,
+            suspense: self.suspense,
+            keep_previous_data: self.keep_previous_data,
+            tags: self.tags.clone(),
+            align_page_size_to: self.align_page_size_to,
+            adaptive_chunk_size: self.adaptive_chunk_size,
         }
     }
 }
+
+impl<T> Debug for UsePaginationOptions<T>
+where
+    T: Send + Sync + 'static,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UsePaginationOptions")
+            .field("overscan_page_count", &self.overscan_page_count)
+            .field("initial_page", &self.initial_page)
+            .field("link", &self.link.is_some())
+            .field("retry", &self.retry)
+            .field("max_cached_items", &self.max_cached_items)
+            .field("ttl", &self.ttl)
+            .field("error_ttl", &self.error_ttl)
+            .field("item_key", &self.item_key.is_some())
+            .field("stale_while_revalidate", &self.stale_while_revalidate)
+            .field("defer_overscan", &self.defer_overscan)
+            .field("idle_warm_page_count", &self.idle_warm_page_count)
+            .field("suspense", &self.suspense)
+            .field("keep_previous_data", &self.keep_previous_data)
+            .field("tags", &self.tags)
+            .field("align_page_size_to", &self.align_page_size_to)
+            .field("adaptive_chunk_size", &self.adaptive_chunk_size)
+            .finish()
+    }
+}
+
+/// Prefetches the last of `ranges` in the next idle period and then continues with the others.
+fn warm_when_idle<T>(
+    item_window: ItemWindow<T>,
+    mut ranges: Vec<Range<usize>>,
+    is_cancelled: Arc<AtomicBool>,
+) where
+    T: Send + Sync + 'static,
+{
+    if ranges.is_empty() {
+        return;
+    }
+
+    let callback = Closure::once_into_js(move || {
+        if is_cancelled.load(Ordering::Relaxed) {
+            return;
+        }
+
+        if let Some(range) = ranges.pop() {
+            item_window.prefetch(range);
+        }
+
+        warm_when_idle(item_window, ranges, is_cancelled);
+    });
+
+    let _ = window().request_idle_callback(callback.unchecked_ref());
+}
+
+/// The pages whose items are all loaded. A page at the end is complete once the items up to
+/// `item_count` are loaded.
+fn fully_loaded_pages<T>(
+    items: &[ItemState<T>],
+    item_count_per_page: usize,
+    item_count: Option<usize>,
+) -> Vec<PageIndex>
+where
+    T: Send + Sync + 'static,
+{
+    let item_count_per_page = item_count_per_page.max(1);
+    let item_count = item_count.unwrap_or(usize::MAX);
+
+    items
+        .chunks(item_count_per_page)
+        .enumerate()
+        .filter(|(page, chunk)| {
+            let expected = item_count
+                .saturating_sub(page * item_count_per_page)
+                .min(item_count_per_page);
+
+            expected > 0
+                && chunk.len() >= expected
+                && chunk[..expected]
+                    .iter()
+                    .all(|item| matches!(item, ItemState::Loaded(_)))
+        })
+        .map(|(page, _)| PageIndex(page))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fully_loaded_pages() {
+        let loaded = || ItemState::Loaded(Arc::new(0));
+        let items = [loaded(), loaded(), loaded(), ItemState::Loading, loaded()];
+
+        assert_eq!(
+            fully_loaded_pages(&items, 2, Some(5)),
+            [PageIndex(0), PageIndex(2)]
+        );
+        // The last page might be longer
+        assert_eq!(fully_loaded_pages(&items, 2, None), [PageIndex(0)]);
+        assert!(fully_loaded_pages::<i32>(&[], 2, Some(0)).is_empty());
+    }
+}
@@ -0,0 +1,189 @@
+use std::collections::VecDeque;
+
+use default_struct_builder::DefaultBuilder;
+use leptos::prelude::*;
+use leptos_windowing::cache::{Cache, CacheEvent, now_millis};
+use reactive_stores::Store;
+
+use crate::{PageIndex, PaginationState, PaginationStateStoreFields, SortMode};
+
+/// Hook that records how the pagination state changed over time.
+///
+/// Every page change, page size change, sorting change and reload is recorded with a timestamp
+/// in a bounded history. This helps to find out how a list ended up in a certain state, e.g. by
+/// logging [`StateHistory::state_history`] when a bug is reported.
+///
+/// Reloads are only recorded for the caches passed to [`StateHistory::watch_cache`].
+///
+/// There is no devtools overlay for it because this crate doesn't ship any UI outside of the
+/// list components. Render or log [`StateHistory::state_history`] in your own debug panel.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos_pagination::{ListState, UseStateHistoryOptions, use_state_history};
+/// #
+/// # #[component]
+/// # pub fn App() -> impl IntoView {
+/// let list = ListState::new(300.0);
+///
+/// let history = use_state_history(
+///     list.pagination,
+///     UseStateHistoryOptions::default().sorting(Some(list.sorting)),
+/// );
+///
+/// let log_history = move |_| {
+///     for entry in history.state_history().get() {
+///         leptos::logging::log!("{entry:?}");
+///     }
+/// };
+///
+/// view! { <button on:click=log_history>"Log history"</button> }
+/// # }
+/// ```
+pub fn use_state_history(
+    state: Store<PaginationState>,
+    options: UseStateHistoryOptions,
+) -> StateHistory {
+    let UseStateHistoryOptions { capacity, sorting } = options;
+
+    let history = StateHistory {
+        entries: RwSignal::new(VecDeque::new()),
+        capacity,
+    };
+
+    Effect::new(move |prev: Option<PageIndex>| {
+        let page = state.current_page().get();
+
+        if let Some(prev) = prev
+            && prev != page
+        {
+            history.record(StateTransition::Page {
+                from: prev,
+                to: page,
+            });
+        }
+
+        page
+    });
+
+    Effect::new(move |prev: Option<Option<usize>>| {
+        let page_size = state.page_size().get();
+
+        if prev.is_some_and(|prev| prev != page_size) {
+            history.record(StateTransition::PageSize(page_size));
+        }
+
+        page_size
+    });
+
+    if let Some(sorting) = sorting {
+        Effect::new(move |prev: Option<()>| {
+            let sorting = sorting.get();
+
+            if prev.is_some() {
+                history.record(StateTransition::Sorting(sorting));
+            }
+        });
+    }
+
+    history
+}
+
+/// Options for [`use_state_history`].
+#[derive(DefaultBuilder, Debug, Clone)]
+pub struct UseStateHistoryOptions {
+    /// How many transitions are kept. The oldest ones are dropped first. Defaults to 100.
+    capacity: usize,
+
+    /// The sorting of the list to record its changes as well, e.g. the one of a
+    /// [`ListState`](crate::ListState). Defaults to `None`.
+    #[builder(keep_type)]
+    sorting: Option<Signal<Vec<(String, SortMode)>>>,
+}
+
+impl Default for UseStateHistoryOptions {
+    fn default() -> Self {
+        Self {
+            capacity: 100,
+            sorting: None,
+        }
+    }
+}
+
+/// Return type of [`use_state_history`].
+#[derive(Debug, Clone, Copy)]
+pub struct StateHistory {
+    entries: RwSignal<VecDeque<StateHistoryEntry>>,
+    capacity: usize,
+}
+
+impl StateHistory {
+    /// The recorded transitions from oldest to newest.
+    pub fn state_history(&self) -> Signal<Vec<StateHistoryEntry>> {
+        let entries = self.entries;
+        Signal::derive(move || entries.read().iter().cloned().collect())
+    }
+
+    /// Records a [`StateTransition::Reload`] whenever `cache` is cleared, which happens when it's
+    /// reloaded or the query changes.
+    ///
+    /// Call this in the same reactive owner as the list, e.g. with the `cache` of the
+    /// [`ItemWindow`](crate::ItemWindow) returned by [`use_pagination`](crate::use_pagination).
+    pub fn watch_cache<T>(&self, cache: Cache<T>)
+    where
+        T: Send + Sync + 'static,
+    {
+        let history = *self;
+
+        cache.on_change(move |event| {
+            if matches!(event, CacheEvent::Cleared) {
+                history.record(StateTransition::Reload);
+            }
+        });
+    }
+
+    /// Adds `transition` to the history, e.g. to record changes of your own state in between.
+    pub fn record(&self, transition: StateTransition) {
+        let capacity = self.capacity;
+        let entry = StateHistoryEntry {
+            timestamp: now_millis(),
+            transition,
+        };
+
+        self.entries.try_update(|entries| {
+            entries.push_back(entry);
+            while entries.len() > capacity {
+                entries.pop_front();
+            }
+        });
+    }
+
+    /// Removes all recorded transitions.
+    pub fn clear(&self) {
+        self.entries.update(VecDeque::clear);
+    }
+}
+
+/// A transition recorded by [`use_state_history`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateHistoryEntry {
+    /// When the transition happened in milliseconds since the Unix epoch.
+    pub timestamp: f64,
+    /// What changed.
+    pub transition: StateTransition,
+}
+
+/// A change of the pagination state. See [`use_state_history`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StateTransition {
+    /// The current page changed.
+    Page { from: PageIndex, to: PageIndex },
+    /// The number of items per page changed.
+    PageSize(Option<usize>),
+    /// The sorting changed to the contained columns.
+    Sorting(Vec<(String, SortMode)>),
+    /// A watched cache has been cleared to load everything again.
+    Reload,
+}
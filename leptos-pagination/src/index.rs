@@ -0,0 +1,167 @@
+use std::{
+    fmt::{Display, Formatter},
+    ops::{Add, AddAssign, Range, Sub, SubAssign},
+};
+
+use serde::{Deserialize, Serialize};
+
+/// The index of a page. Counting starts from 0.
+///
+/// It's displayed 1-based so it can be put directly into the UI. Keeping page indices apart from
+/// item indices ([`ItemIndex`]) prevents mixing them up when wiring custom controls.
+///
+/// ```
+/// # use leptos_pagination::{ItemIndex, PageIndex};
+/// #
+/// let page = PageIndex(2);
+///
+/// assert_eq!(page.to_string(), "3");
+/// assert_eq!(page.first_item(10), ItemIndex(20));
+/// assert_eq!(ItemIndex(25).page(10), page);
+/// ```
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct PageIndex(pub usize);
+
+impl PageIndex {
+    /// The first page.
+    pub const FIRST: Self = Self(0);
+
+    /// The last page if there are `page_count` pages. `None` if there are no pages.
+    #[inline]
+    pub fn last(page_count: usize) -> Option<Self> {
+        page_count.checked_sub(1).map(Self)
+    }
+
+    /// The 1-based page number for display.
+    #[inline]
+    pub fn number(self) -> usize {
+        self.0 + 1
+    }
+
+    /// The index of the first item on this page.
+    #[inline]
+    pub fn first_item(self, item_count_per_page: usize) -> ItemIndex {
+        ItemIndex(self.0 * item_count_per_page)
+    }
+
+    /// The indices of the items on this page.
+    #[inline]
+    pub fn item_range(self, item_count_per_page: usize) -> Range<usize> {
+        let start = self.0 * item_count_per_page;
+        start..start + item_count_per_page
+    }
+}
+
+impl From<usize> for PageIndex {
+    #[inline]
+    fn from(index: usize) -> Self {
+        Self(index)
+    }
+}
+
+impl Display for PageIndex {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+/// The index of an item. Counting starts from 0.
+///
+/// It's displayed 1-based so it can be put directly into the UI. See [`PageIndex`].
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct ItemIndex(pub usize);
+
+impl ItemIndex {
+    /// The 1-based item number for display.
+    #[inline]
+    pub fn number(self) -> usize {
+        self.0 + 1
+    }
+
+    /// The page this item is on.
+    ///
+    /// `item_count_per_page` has to be greater than 0.
+    #[inline]
+    pub fn page(self, item_count_per_page: usize) -> PageIndex {
+        PageIndex(self.0 / item_count_per_page)
+    }
+}
+
+impl From<usize> for ItemIndex {
+    #[inline]
+    fn from(index: usize) -> Self {
+        Self(index)
+    }
+}
+
+impl Display for ItemIndex {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+macro_rules! impl_index_arithmetic {
+    ($index:ident) => {
+        impl Add<usize> for $index {
+            type Output = Self;
+
+            #[inline]
+            fn add(self, rhs: usize) -> Self {
+                Self(self.0 + rhs)
+            }
+        }
+
+        /// Saturates at 0.
+        impl Sub<usize> for $index {
+            type Output = Self;
+
+            #[inline]
+            fn sub(self, rhs: usize) -> Self {
+                Self(self.0.saturating_sub(rhs))
+            }
+        }
+
+        impl AddAssign<usize> for $index {
+            #[inline]
+            fn add_assign(&mut self, rhs: usize) {
+                *self = *self + rhs;
+            }
+        }
+
+        /// Saturates at 0.
+        impl SubAssign<usize> for $index {
+            #[inline]
+            fn sub_assign(&mut self, rhs: usize) {
+                *self = *self - rhs;
+            }
+        }
+    };
+}
+
+impl_index_arithmetic!(PageIndex);
+impl_index_arithmetic!(ItemIndex);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_and_item_index() {
+        assert_eq!(PageIndex::last(0), None);
+        assert_eq!(PageIndex::last(5), Some(PageIndex(4)));
+
+        assert_eq!(PageIndex(1) - 3, PageIndex::FIRST);
+        assert_eq!(PageIndex(1) + 3, PageIndex(4));
+        assert_eq!(PageIndex(3).item_range(10), 30..40);
+
+        assert_eq!(ItemIndex(9).page(10), PageIndex(0));
+        assert_eq!(ItemIndex(10).page(10), PageIndex(1));
+        assert_eq!(ItemIndex(0).to_string(), "1");
+    }
+}
@@ -35,10 +35,10 @@
 //! let state = PaginationState::new_store();
 //!
 //! view! {
-//!     <ul>
+//!     <ul id=state.list_id().get_untracked()>
 //!         <PaginatedFor loader=BookLoader query=() state item_count_per_page=10 let:idx_book>
-//!             // idx_book is a tuple containing the index and the book data
-//!             <li>{idx_book.1.title.clone()}</li>
+//!             // idx_book is a `WindowItem` containing the index and the book data
+//!             <li>{idx_book.data.title.clone()}</li>
 //!         </PaginatedFor>
 //!     </ul>
 //!
@@ -60,6 +60,12 @@
 //! - [`MemoryLoader`]: If your dataset is already in memory like in a `Vec`, `HashSet`, array, ...
 //! - [`PaginatedLoader`]: If your data source provides data in pages (independent of if you use UI pagination or virtualization).
 //! - [`ExactLoader`]: If your data source can provide an exact range of items (start index to end index).
+//! - [`CursorLoader`]: If your data source paginates by opaque cursor instead of by offset.
+//! - [`KeysetLoader`]: If your data source loads the items after the sort key of the last item (seek pagination).
+//! - [`RelayLoader`]: If your data source is a GraphQL API with Relay connections.
+//! - [`StreamLoader`]: If your data source delivers the items one by one as a stream. They're displayed as they arrive.
+//! - `ODataLoader`: If your data source is an OData service. Requires the feature `odata`.
+//! - `StartAfterLoader`: If your data source is a document database with `startAfter` cursors like Firestore. Requires the feature `start-after`.
 //! - [`Loader`]: If none of the above fit your needs, you can implement this trait to provide your own loading logic.
 //!
 //! Please refer to the documentation and the examples to see how to implement these traits.
@@ -70,9 +76,15 @@
 //! These components are:
 //!
 //! - [`PaginatedFor`]: A component that displays a list of items in a paginated manner.
+//! - [`PaginatedVec`]: A shortcut that paginates a `Vec` with the default controls. Good for prototypes.
+//! - [`InfiniteFor`]: A component that appends more items as the user scrolls near the end (infinite scrolling).
 //! - [`PaginationPages`]: A component that displays the buttons to jump to a certain page.
 //! - [`PaginationNext`]: A component that displays a button to navigate to the next page.
 //! - [`PaginationPrev`]: A component that displays a button to navigate to the previous page.
+//! - [`PaginationFirst`] and [`PaginationLast`]: Components that display buttons to jump to the first and last page.
+//! - [`PaginationPageSize`]: A component that lets the user choose the number of items per page.
+//! - [`PaginationSummary`]: A component that displays the current page and the page count.
+//! - [`LoadMore`]: A component that displays a button to append more items to an [`InfiniteFor`].
 //!
 //! Please refer to the examples to see how to use these components.
 //!
@@ -84,17 +96,42 @@
 //! These are the hooks:
 //!
 //! - [`use_pagination`]: Logic for [`PaginatedFor`]. Handles loading items on-demand from the data source and caching them.
+//! - [`use_load_more`]: Logic for [`InfiniteFor`] with a [`LoadMore`] button. Appends more items on demand.
+//! - [`use_infinite_scroll`]: Logic for [`InfiniteFor`]. Appends more items when the user scrolls near the end.
 //! - [`use_pagination_controls`]: Logic for [`PaginationPages`]. Returns page ranges that can be used to display pagination controls.
+//! - [`use_auto_item_count_per_page`]: Computes how many items fit into a container. Use it as `item_count_per_page`.
+//! - [`use_page_keys`]: Navigates between pages with the PageUp and PageDown keys.
+//! - [`use_page_history`]: Adds the page changes to the browser history so back and forward navigate between pages.
+//! - [`use_state_history`]: Records the changes of the pagination state with timestamps for debugging.
 //!
 //! If you want to implement your own custom components using these hooks, please have a look at the pre-made components in this crate.
 //! You'll see that there is really nothing special about them.
+//!
+//! To combine a search field and sortable columns with the pagination use [`ListState`]. It goes back to
+//! the first page whenever the search term or the sorting changes.
+//!
+//! To compute the same page ranges as the components in your own hooks or on the server use the
+//! [`math`] module.
 
 mod components;
+mod format;
 mod hooks;
+mod index;
+pub mod math;
 mod state;
 
 pub use components::*;
+pub use format::*;
 pub use hooks::*;
+pub use index::*;
 pub use state::*;
 
 pub use leptos_windowing::*;
+
+/// The [`COMPAT_VERSION`] of leptos-windowing that this crate is written against.
+///
+/// Pass it to [`assert_compatible`] of the leptos-windowing you depend on directly to make sure
+/// both are the same version.
+pub const WINDOWING_COMPAT_VERSION: u32 = 1;
+
+const _: () = leptos_windowing::assert_compatible(WINDOWING_COMPAT_VERSION);
@@ -0,0 +1,132 @@
+//! The page arithmetic of the hooks and components of this crate as pure functions.
+//!
+//! Use these in custom hooks or on the server to compute the same ranges as the components.
+//! `item_count_per_page` has to be greater than 0 everywhere.
+//!
+//! ```
+//! # use leptos_pagination::{PageIndex, math};
+//! #
+//! // 95 items with 10 per page
+//! assert_eq!(math::page_count(95, 10), 10);
+//! assert_eq!(math::page_range(PageIndex(9), 10, Some(95)), 90..95);
+//! assert_eq!(math::clamp_page(PageIndex(12), Some(10)), PageIndex(9));
+//! ```
+
+use std::ops::Range;
+
+use crate::{ItemIndex, PageIndex};
+
+/// The number of pages that are needed for `item_count` items.
+#[inline]
+pub fn page_count(item_count: usize, item_count_per_page: usize) -> usize {
+    item_count.div_ceil(item_count_per_page)
+}
+
+/// The indices of the items on `page`. If the item count is known, the range ends with the last
+/// item so the last page can be shorter.
+pub fn page_range(
+    page: PageIndex,
+    item_count_per_page: usize,
+    item_count: Option<usize>,
+) -> Range<usize> {
+    let range = page.item_range(item_count_per_page);
+
+    match item_count {
+        Some(item_count) => range.start.min(item_count)..range.end.min(item_count),
+        None => range,
+    }
+}
+
+/// The pages that contain at least one of the items in `range`. Empty for an empty range.
+pub fn pages_of_range(range: Range<usize>, item_count_per_page: usize) -> Range<PageIndex> {
+    if range.is_empty() {
+        let page = ItemIndex(range.start).page(item_count_per_page);
+        return page..page;
+    }
+
+    ItemIndex(range.start).page(item_count_per_page)
+        ..ItemIndex(range.end - 1).page(item_count_per_page) + 1
+}
+
+/// `page` clamped to the last page if the page count is known. Without pages this is the first page.
+pub fn clamp_page(page: PageIndex, page_count: Option<usize>) -> PageIndex {
+    match page_count {
+        Some(page_count) => page.min(PageIndex::last(page_count).unwrap_or(PageIndex::FIRST)),
+        None => page,
+    }
+}
+
+/// `item_count_per_page` rounded down to a multiple of `columns` so the last row of a grid is full.
+/// A page always has at least one row.
+pub fn align_page_size(item_count_per_page: usize, columns: usize) -> usize {
+    let columns = columns.max(1);
+
+    (item_count_per_page / columns).max(1) * columns
+}
+
+/// The indices of the items on `page` and on `overscan_page_count` pages before and after it.
+///
+/// This is the range that [`use_pagination`](crate::use_pagination) loads.
+pub fn overscan_range(
+    page: PageIndex,
+    item_count_per_page: usize,
+    overscan_page_count: usize,
+) -> Range<usize> {
+    let start = (page - overscan_page_count)
+        .first_item(item_count_per_page)
+        .0;
+    let end = (page + overscan_page_count + 1)
+        .first_item(item_count_per_page)
+        .0;
+
+    start..end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_count() {
+        assert_eq!(page_count(0, 10), 0);
+        assert_eq!(page_count(10, 10), 1);
+        assert_eq!(page_count(11, 10), 2);
+    }
+
+    #[test]
+    fn test_page_range() {
+        assert_eq!(page_range(PageIndex(2), 10, None), 20..30);
+        assert_eq!(page_range(PageIndex(2), 10, Some(25)), 20..25);
+        assert_eq!(page_range(PageIndex(3), 10, Some(25)), 25..25);
+    }
+
+    #[test]
+    fn test_pages_of_range() {
+        assert_eq!(pages_of_range(5..25, 10), PageIndex(0)..PageIndex(3));
+        assert_eq!(pages_of_range(10..20, 10), PageIndex(1)..PageIndex(2));
+        assert!(pages_of_range(10..10, 10).is_empty());
+    }
+
+    #[test]
+    fn test_clamp_page() {
+        assert_eq!(clamp_page(PageIndex(5), Some(3)), PageIndex(2));
+        assert_eq!(clamp_page(PageIndex(1), Some(3)), PageIndex(1));
+        assert_eq!(clamp_page(PageIndex(5), Some(0)), PageIndex::FIRST);
+        assert_eq!(clamp_page(PageIndex(5), None), PageIndex(5));
+    }
+
+    #[test]
+    fn test_align_page_size() {
+        assert_eq!(align_page_size(20, 3), 18);
+        assert_eq!(align_page_size(20, 4), 20);
+        assert_eq!(align_page_size(2, 3), 3);
+        assert_eq!(align_page_size(20, 0), 20);
+    }
+
+    #[test]
+    fn test_overscan_range() {
+        assert_eq!(overscan_range(PageIndex(0), 10, 1), 0..20);
+        assert_eq!(overscan_range(PageIndex(3), 10, 1), 20..50);
+        assert_eq!(overscan_range(PageIndex(3), 10, 0), 30..40);
+    }
+}
@@ -1,25 +1,63 @@
+use std::time::Duration;
+
 use leptos::prelude::*;
+use leptos_use::use_debounce_fn_with_arg;
 use reactive_stores::Store;
 
+use crate::{ListQuery, PageIndex, SortMode, math, use_unique_id};
+
 /// The state of pagination.
 ///
 /// Used as a reactive store to communicate between control and display components.
 #[derive(Store, Clone, Debug, PartialEq, Eq)]
 pub struct PaginationState {
-    /// The current page number. Counting starts from 0.
-    pub current_page: usize,
+    /// The current page. Counting starts from 0.
+    pub current_page: PageIndex,
     /// The total number of pages or None initially or if the count could not be determined.
     pub page_count: Option<usize>,
     /// The error message if the page count could not be determined.
     pub page_count_error: Option<String>,
+    /// The id of the element that contains the list. The controls reference it with `aria-controls`.
+    ///
+    /// Put it on the element that wraps your `PaginatedFor`.
+    pub list_id: String,
+    /// A page that the user is likely to navigate to next, e.g. because a control is hovered.
+    ///
+    /// [`use_pagination`](crate::use_pagination) loads it into the cache in the background.
+    pub prefetch_page: Option<PageIndex>,
+    /// The number of items per page chosen by the user, e.g. with
+    /// [`PaginationPageSize`](crate::PaginationPageSize).
+    ///
+    /// [`use_pagination`](crate::use_pagination) initializes it with its `item_count_per_page`
+    /// and uses it instead from then on.
+    pub page_size: Option<usize>,
+    /// The pages whose items are all loaded into the cache. Kept up to date by
+    /// [`use_pagination`](crate::use_pagination).
+    ///
+    /// Use this to show which pages are displayed instantly, e.g. with the `cached_class` of
+    /// [`PaginationPages`](crate::PaginationPages).
+    pub pages_cached: Vec<PageIndex>,
+    /// How long items that failed to load keep their error before they're loaded again.
+    ///
+    /// [`use_pagination`](crate::use_pagination) initializes it with its `error_ttl` option if
+    /// that is set and uses it from then on, so it can be changed at runtime, e.g. in a settings
+    /// dialog. `None` keeps the errors until the items are retried.
+    pub error_ttl: Option<Duration>,
 }
 
 impl PaginationState {
+    /// Creates a new store. Call this in the body of a component so the generated `list_id` is
+    /// the same on the server and in the browser. See [`use_unique_id`].
     pub fn new_store() -> Store<Self> {
         Store::new(Self {
-            current_page: 0,
+            current_page: PageIndex::FIRST,
             page_count: None,
             page_count_error: None,
+            list_id: use_unique_id("pagination-list"),
+            prefetch_page: None,
+            page_size: None,
+            pages_cached: Vec::new(),
+            error_ttl: None,
         })
     }
 
@@ -32,20 +70,256 @@ impl PaginationState {
 
     /// If possible, move to the previous page.
     pub fn prev(this_store: Store<Self>) {
-        if this_store.current_page().get() > 0 {
+        if this_store.current_page().get() > PageIndex::FIRST {
             this_store.current_page().update(|cp| *cp -= 1);
         }
     }
 
+    /// Move to the given page. It's clamped to the last page if the page count is known.
+    pub fn go_to_page(this_store: Store<Self>, page: PageIndex) {
+        let page = math::clamp_page(page, this_store.page_count().get_untracked());
+
+        this_store.current_page().set(page);
+    }
+
+    /// Move to the first page.
+    pub fn first(this_store: Store<Self>) {
+        this_store.current_page().set(PageIndex::FIRST);
+    }
+
+    /// If the page count is known, move to the last page.
+    pub fn last(this_store: Store<Self>) {
+        if let Some(last_page) = this_store
+            .page_count()
+            .get_untracked()
+            .and_then(PageIndex::last)
+        {
+            this_store.current_page().set(last_page);
+        }
+    }
+
+    /// Changes the number of items per page. [`use_pagination`](crate::use_pagination) changes the
+    /// current page so that the first item of the old page is still displayed.
+    pub fn set_page_size(this_store: Store<Self>, page_size: usize) {
+        this_store.page_size().set(Some(page_size.max(1)));
+    }
+
+    /// Requests to load the given page in the background. Pages that don't exist are ignored.
+    pub fn prefetch(this_store: Store<Self>, page: PageIndex) {
+        if this_store
+            .page_count()
+            .get_untracked()
+            .is_none_or(|page_count| page.0 < page_count)
+        {
+            this_store.prefetch_page().set(Some(page));
+        }
+    }
+
     pub fn is_first_page(this_store: Store<Self>) -> bool {
-        this_store.current_page().get() == 0
+        this_store.current_page().get() == PageIndex::FIRST
     }
 
     pub fn is_last_page(this_store: Store<Self>) -> bool {
         if let Some(page_count) = this_store.page_count().get() {
-            this_store.current_page().get() >= page_count.saturating_sub(1)
+            this_store.current_page().get() >= PageIndex(page_count.saturating_sub(1))
         } else {
             false
         }
     }
 }
+
+/// The state of a list that appends more items on demand.
+///
+/// Used as a reactive store to communicate between [`InfiniteFor`](crate::InfiniteFor) and
+/// [`LoadMore`](crate::LoadMore).
+#[derive(Store, Clone, Debug, PartialEq, Eq)]
+pub struct LoadMoreState {
+    /// How many items are displayed.
+    pub displayed_count: usize,
+    /// How many items are appended every time more items are loaded.
+    pub item_count_per_load: usize,
+    /// The total number of items or None initially or if the count could not be determined.
+    pub item_count: Option<usize>,
+    /// Whether the last appended items are still being loaded.
+    pub is_loading_more: bool,
+    /// The id of the element that contains the list. [`LoadMore`](crate::LoadMore) references it
+    /// with `aria-controls`.
+    ///
+    /// Put it on the element that wraps your `InfiniteFor`.
+    pub list_id: String,
+}
+
+impl LoadMoreState {
+    /// Creates a new store. Call this in the body of a component so the generated `list_id` is
+    /// the same on the server and in the browser. See [`use_unique_id`].
+    pub fn new_store() -> Store<Self> {
+        Store::new(Self {
+            displayed_count: 0,
+            item_count_per_load: 0,
+            item_count: None,
+            is_loading_more: false,
+            list_id: use_unique_id("load-more-list"),
+        })
+    }
+
+    /// If possible, append the next items.
+    ///
+    /// Does nothing while the last appended items are still being loaded or after all items are displayed.
+    pub fn load_more(this_store: Store<Self>) {
+        if !Self::is_end_reached(this_store) && !this_store.is_loading_more().get() {
+            let item_count_per_load = this_store.item_count_per_load().get();
+            this_store
+                .displayed_count()
+                .update(|count| *count += item_count_per_load);
+        }
+    }
+
+    /// Whether all items are displayed.
+    pub fn is_end_reached(this_store: Store<Self>) -> bool {
+        if let Some(item_count) = this_store.item_count().get() {
+            this_store.displayed_count().get() >= item_count
+        } else {
+            false
+        }
+    }
+}
+
+/// The state of a list with a search field, sortable columns and pagination.
+///
+/// Bind the toolbar and the list to this instead of coordinating the search, the sorting and the
+/// page yourself. Whenever the search term (after the user stopped typing) or the sorting changes,
+/// the list goes back to the first page.
+///
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos_pagination::*;
+/// # use std::ops::Range;
+/// #
+/// # pub struct BookLoader;
+/// #
+/// # impl MemoryLoader for BookLoader {
+/// #     type Item = String;
+/// #     type Query = ListQuery;
+/// #     fn load_items(&self, range: Range<usize>, _query: &ListQuery) -> Vec<Self::Item> {
+/// #         vec![]
+/// #     }
+/// #     fn item_count(&self, _query: &ListQuery) -> usize {
+/// #         0
+/// #     }
+/// # }
+/// #
+/// # #[component]
+/// # pub fn App() -> impl IntoView {
+/// let list = ListState::new(300.0);
+/// let state = list.pagination;
+///
+/// view! {
+///     <input bind:value=list.search />
+///     <button on:click=move |_| list.toggle_sort("title")>"Title"</button>
+///
+///     <ul id=state.list_id().get_untracked()>
+///         <PaginatedFor loader=BookLoader query=list.query() state item_count_per_page=10 let:book>
+///             <li>{book.data.to_string()}</li>
+///         </PaginatedFor>
+///     </ul>
+///
+///     <PaginationPages state />
+/// }
+/// # }
+/// ```
+#[derive(Clone, Copy)]
+pub struct ListState {
+    /// The pagination of the list. Pass it to `PaginatedFor` and the controls.
+    pub pagination: Store<PaginationState>,
+    /// The search term as typed by the user. Bind the search input to it.
+    pub search: RwSignal<String>,
+    /// The search term once the user stopped typing. This is what's used for the query.
+    pub debounced_search: Signal<String>,
+    /// The columns to sort by in order of priority. Change it with [`ListState::set_sorting`] or
+    /// [`ListState::toggle_sort`].
+    pub sorting: Signal<Vec<(String, SortMode)>>,
+    set_sorting: WriteSignal<Vec<(String, SortMode)>>,
+}
+
+impl ListState {
+    /// Creates a new list state. Changes to the search term are applied after the user stopped
+    /// typing for `search_debounce_ms` milliseconds.
+    ///
+    /// Call this in the body of a component like [`PaginationState::new_store`].
+    pub fn new(search_debounce_ms: f64) -> Self {
+        let pagination = PaginationState::new_store();
+        let search = RwSignal::new(String::new());
+        let (debounced_search, set_debounced_search) = signal(String::new());
+        let (sorting, set_sorting) = signal(Vec::new());
+
+        // The page is reset together with the search term so the list never loads the new query
+        // on the old page.
+        let apply_search = use_debounce_fn_with_arg(
+            move |search: String| {
+                set_debounced_search.set(search);
+                PaginationState::first(pagination);
+            },
+            search_debounce_ms,
+        );
+
+        Effect::new(move |prev: Option<()>| {
+            let search = search.get();
+
+            if prev.is_some() {
+                apply_search(search);
+            }
+        });
+
+        Self {
+            pagination,
+            search,
+            debounced_search: debounced_search.into(),
+            sorting: sorting.into(),
+            set_sorting,
+        }
+    }
+
+    /// The query for the loader consisting of the debounced search term and the sorting.
+    pub fn query(self) -> Signal<ListQuery> {
+        Signal::derive(move || ListQuery {
+            search: Some(self.debounced_search.get()).filter(|search| !search.is_empty()),
+            sorting: self.sorting.get(),
+            ..Default::default()
+        })
+    }
+
+    /// Sorts by `column` only. Useful for clickable table headers.
+    ///
+    /// If the list is already sorted by `column`, its sort mode is cycled with [`SortMode::next`].
+    /// Otherwise it's sorted ascending.
+    pub fn toggle_sort(self, column: impl Into<String>) {
+        let column = column.into();
+
+        let mut mode = self
+            .sorting
+            .read_untracked()
+            .iter()
+            .find(|(sorted_column, _)| *sorted_column == column)
+            .map(|(_, mode)| *mode)
+            .unwrap_or_default();
+        mode.next();
+
+        self.set_sorting(vec![(column, mode)]);
+    }
+
+    /// Sorts by `sorting`, the columns in order of priority, and goes back to the first page.
+    pub fn set_sorting(self, sorting: Vec<(String, SortMode)>) {
+        self.set_sorting.set(sorting);
+        PaginationState::first(self.pagination);
+    }
+
+    /// The sort mode of `column`. [`SortMode::None`] if the list isn't sorted by it.
+    pub fn sort_mode(self, column: &str) -> SortMode {
+        self.sorting
+            .read()
+            .iter()
+            .find(|(sorted_column, _)| sorted_column == column)
+            .map(|(_, mode)| *mode)
+            .unwrap_or_default()
+    }
+}
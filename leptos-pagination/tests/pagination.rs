@@ -0,0 +1,205 @@
+use std::time::Duration;
+
+use leptos::prelude::*;
+use leptos_pagination::{
+    MockLoader, PageIndex, PaginationState, PaginationStateStoreFields, SortMode, StateTransition,
+    UsePaginationOptions, UseStateHistoryOptions,
+    testing::{TestRuntime, cached_items},
+    use_pagination, use_state_history,
+};
+
+#[test]
+fn test_pages() {
+    let runtime = TestRuntime::new();
+    let loader = MockLoader::new((0..95).collect::<Vec<usize>>());
+    let state = PaginationState::new_store();
+
+    let window = use_pagination(
+        state,
+        loader.clone(),
+        (),
+        10,
+        UsePaginationOptions::default(),
+    )
+    .item_window;
+    runtime.settle();
+
+    assert_eq!(state.page_count().get_untracked(), Some(10));
+    assert_eq!(window.range.get_untracked(), 0..10);
+
+    PaginationState::go_to_page(state, PageIndex(9));
+    runtime.settle();
+
+    // The last page isn't full
+    assert_eq!(window.range.get_untracked(), 90..95);
+    assert_eq!(
+        cached_items(&window.cache)[90..95],
+        (90..95).map(Some).collect::<Vec<_>>()
+    );
+    assert!(state.pages_cached().get_untracked().contains(&PageIndex(9)));
+
+    // Going past the last page is clamped
+    PaginationState::next(state);
+    runtime.settle();
+    assert_eq!(state.current_page().get_untracked(), PageIndex(9));
+}
+
+#[test]
+fn test_loaded_pages_are_not_loaded_again() {
+    let runtime = TestRuntime::new();
+    let loader = MockLoader::new((0..100).collect::<Vec<usize>>());
+    let state = PaginationState::new_store();
+
+    let _window = use_pagination(
+        state,
+        loader.clone(),
+        (),
+        10,
+        UsePaginationOptions::default(),
+    )
+    .item_window;
+    runtime.settle();
+
+    PaginationState::next(state);
+    runtime.settle();
+    let loaded_ranges = loader.loaded_ranges();
+
+    PaginationState::prev(state);
+    runtime.settle();
+
+    assert_eq!(loader.loaded_ranges(), loaded_ranges);
+}
+
+#[test]
+fn test_align_page_size_to_columns() {
+    let runtime = TestRuntime::new();
+    let loader = MockLoader::new((0..100).collect::<Vec<usize>>());
+    let state = PaginationState::new_store();
+    let columns = RwSignal::new(4);
+
+    let window = use_pagination(
+        state,
+        loader.clone(),
+        (),
+        10,
+        UsePaginationOptions::default().align_page_size_to(Some(columns.into())),
+    )
+    .item_window;
+    runtime.settle();
+
+    // 10 items per page are rounded down to 2 rows of 4
+    assert_eq!(state.page_count().get_untracked(), Some(13));
+
+    PaginationState::go_to_page(state, PageIndex(5));
+    runtime.settle();
+    assert_eq!(window.range.get_untracked(), 40..48);
+
+    // 3 rows of 3 and the first item is still displayed
+    columns.set(3);
+    runtime.settle();
+    assert_eq!(state.current_page().get_untracked(), PageIndex(4));
+    assert_eq!(window.range.get_untracked(), 36..45);
+}
+
+#[test]
+fn test_page_size_change_keeps_the_first_item() {
+    let runtime = TestRuntime::new();
+    let loader = MockLoader::new((0..100).collect::<Vec<usize>>());
+    let state = PaginationState::new_store();
+    let columns = RwSignal::new(3);
+
+    let result = use_pagination(
+        state,
+        loader,
+        (),
+        10,
+        UsePaginationOptions::default().align_page_size_to(Some(columns.into())),
+    );
+    runtime.settle();
+
+    // 3 rows of 3
+    assert_eq!(result.item_count_per_page.get_untracked(), 9);
+    PaginationState::go_to_page(state, PageIndex(5));
+    runtime.settle();
+    assert_eq!(result.item_window.range.get_untracked(), 45..54);
+
+    // 6 rows of 3 and the first item is still displayed
+    PaginationState::set_page_size(state, 20);
+    runtime.settle();
+    assert_eq!(result.item_count_per_page.get_untracked(), 18);
+    assert_eq!(state.current_page().get_untracked(), PageIndex(2));
+    assert_eq!(result.item_window.range.get_untracked(), 36..54);
+}
+
+#[test]
+fn test_error_ttl_is_kept_in_the_state() {
+    let _runtime = TestRuntime::new();
+    let loader = MockLoader::new((0..100).collect::<Vec<usize>>());
+    let state = PaginationState::new_store();
+
+    let _window = use_pagination(
+        state,
+        loader,
+        (),
+        10,
+        UsePaginationOptions::default().error_ttl(Some(Duration::from_secs(5))),
+    );
+
+    assert_eq!(
+        state.error_ttl().get_untracked(),
+        Some(Duration::from_secs(5))
+    );
+}
+
+#[test]
+fn test_state_history() {
+    let runtime = TestRuntime::new();
+    let loader = MockLoader::new((0..100).collect::<Vec<usize>>());
+    let state = PaginationState::new_store();
+    let sorting = RwSignal::new(Vec::new());
+
+    let window = use_pagination(
+        state,
+        loader.clone(),
+        (),
+        10,
+        UsePaginationOptions::default(),
+    )
+    .item_window;
+    let history = use_state_history(
+        state,
+        UseStateHistoryOptions::default()
+            .capacity(3)
+            .sorting(Some(sorting.into())),
+    );
+    history.watch_cache(window.cache);
+    runtime.settle();
+
+    PaginationState::go_to_page(state, PageIndex(3));
+    runtime.settle();
+    sorting.set(vec![("title".to_string(), SortMode::Ascending)]);
+    runtime.settle();
+    window.cache.reload();
+    runtime.settle();
+    PaginationState::first(state);
+    runtime.settle();
+
+    // The oldest transition has been dropped
+    let transitions = history
+        .state_history()
+        .get_untracked()
+        .into_iter()
+        .map(|entry| entry.transition)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        transitions,
+        [
+            StateTransition::Sorting(vec![("title".to_string(), SortMode::Ascending)]),
+            StateTransition::Reload,
+            StateTransition::Page {
+                from: PageIndex(3),
+                to: PageIndex::FIRST,
+            },
+        ]
+    );
+}
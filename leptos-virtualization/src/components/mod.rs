@@ -0,0 +1,3 @@
+mod virtual_for;
+
+pub use virtual_for::*;
@@ -0,0 +1,311 @@
+use std::{marker::PhantomData, sync::Arc, time::Duration};
+
+use leptos::{ev::MouseEvent, prelude::*};
+use leptos_windowing::{
+    InternalLoader, ItemInteractions, ItemLoadError, WindowController, WindowItem, WindowLink,
+    cache::CacheController, item_state::ItemState,
+};
+use reactive_stores::{Store, StoreFieldIterator};
+
+use crate::{
+    UseVirtualizationOptions, UseVirtualizationResult, VirtualizationState,
+    VirtualizationStateStoreFields, use_virtualization,
+};
+
+/// Slot that is rendered for every item that failed to load.
+///
+/// It receives an [`ItemLoadError`] with the error and a `retry` callback to load the item again.
+#[derive(Clone)]
+#[slot]
+pub struct LoadError {
+    children: Arc<dyn Fn(ItemLoadError) -> AnyView + Send + Sync>,
+}
+
+/// Slot that is rendered when the data is being loaded.
+#[derive(Clone)]
+#[slot]
+pub struct Loading {
+    children: ChildrenFn,
+}
+
+/// Slot that is rendered when the data has been loaded but is empty.
+#[derive(Clone)]
+#[slot]
+pub struct Empty {
+    children: ChildrenFn,
+}
+
+/// Slot that is rendered after the last item, e.g. "You're all caught up".
+///
+/// It's only rendered once the total number of items is known. It's placed after the space of the
+/// items so it comes into view when the user scrolls to the end. Unlike pagination there is no
+/// `StartOfList` slot because content before the items would shift the scroll position that the
+/// visible items are computed from. Put such content before the scroll container instead.
+#[derive(Clone)]
+#[slot]
+pub struct EndOfList {
+    children: ChildrenFn,
+}
+
+/// Quite similar to Leptos' `<For>` this displays a list of items.
+///
+/// But only the items that are visible in the scroll container are rendered and they are
+/// loaded and cached on-demand using the provided `loader`. This way lists with hundreds of
+/// thousands of items stay fast.
+///
+/// This renders a `<div>` that is the scroll container. Give it a fixed height, e.g. with
+/// `attr:class`. Every item has to be exactly `item_height` pixels high.
+///
+/// ## Example
+///
+/// ```
+/// # use std::ops::Range;
+/// #
+/// # use leptos::prelude::*;
+/// # use leptos_virtualization::{Loading, MemoryLoader, VirtualFor};
+/// #
+/// pub struct Book {
+///     title: String,
+/// }
+///
+/// # #[component]
+/// # pub fn App() -> impl IntoView {
+/// view! {
+///     <VirtualFor
+///         loader=BookLoader
+///         query=()
+///         item_height=32.0
+///         attr:class="book-list"
+///         let:idx_book
+///     >
+///         // .book-list has a fixed height like 400px
+///         <div style="height: 32px">{idx_book.data.title.clone()}</div>
+///
+///         <Loading slot>
+///             <div style="height: 32px" class="loading">Loading...</div>
+///         </Loading>
+///     </VirtualFor>
+/// }
+/// # }
+///
+/// pub struct BookLoader;
+///
+/// impl MemoryLoader for BookLoader {
+///     type Item = Book;
+///     type Query = ();
+///
+///     fn load_items(&self, range: Range<usize>, _query: &Self::Query) -> Vec<Self::Item> {
+///         range.map(|i| Book { title: format!("Book {i}") }).collect()
+///     }
+///
+///     fn item_count(&self, _query: &Self::Query) -> usize {
+///         100_000
+///     }
+/// }
+/// ```
+#[component]
+pub fn VirtualFor<T, L, Q, CF, V, M>(
+    /// The loader to get the data on-demand.
+    loader: L,
+
+    /// The query to get the data on-demand.
+    #[prop(into)]
+    query: Signal<Q>,
+
+    /// The height of every item in pixels.
+    #[prop(into)]
+    item_height: Signal<f64>,
+
+    /// The virtualization state. Kept up to date with the visible items.
+    #[prop(default = VirtualizationState::new_store())]
+    state: Store<VirtualizationState>,
+
+    /// How many items before and after the visible ones are loaded in the background.
+    ///
+    /// Defaults to 10.
+    #[prop(default = 10)]
+    overscan_item_count: usize,
+
+    /// How many items are rendered as long as the scroll container hasn't been measured, e.g.
+    /// when rendering on the server.
+    ///
+    /// Defaults to 20.
+    #[prop(default = 20)]
+    initial_item_count: usize,
+
+    /// How long items that failed to load keep their error. Afterwards they are loaded again when
+    /// they're scrolled into view again. Defaults to never.
+    #[prop(optional)]
+    error_ttl: Option<Duration>,
+
+    /// Invalidation tags of the data of this list, e.g. `tags=vec!["customers".to_string()]`.
+    /// Calling [`invalidate_tag`](crate::invalidate_tag) with one of them reloads the list while
+    /// the loaded items stay displayed.
+    #[prop(optional)]
+    tags: Vec<String>,
+
+    /// Slot that is rendered instead of `children` while an item is being loaded.
+    #[prop(optional)]
+    loading: Option<Loading>,
+
+    /// Slot that is rendered instead of `children` when the data has been loaded but is empty.
+    #[prop(optional)]
+    empty: Option<Empty>,
+
+    /// Slot that is rendered instead of `children` when an error occurs.
+    #[prop(optional)]
+    load_error: Option<LoadError>,
+
+    /// Slot that is rendered after the last item.
+    #[prop(optional)]
+    end_of_list: Option<EndOfList>,
+
+    /// You can provide this to implement mutable access to the cache for editing/inserting elements.
+    #[prop(optional)]
+    cache_controller: CacheController<T>,
+
+    /// You can provide this to control the list imperatively like reloading or scrolling to an item.
+    #[prop(optional)]
+    controller: WindowController<T>,
+
+    /// Link this list to other lists over the same data (e.g. in a split view).
+    ///
+    /// Linked lists share their cache, their selection and navigate together. Clicking an item
+    /// selects it. See [`WindowLink`].
+    #[prop(optional, into)]
+    link: Option<WindowLink<T>>,
+
+    /// Called when the user opens the context menu of a loaded item, e.g. with a right click.
+    ///
+    /// The listener is put on the root element rendered by `children`. Call
+    /// `prevent_default()` on the event to replace the browser's menu with your own.
+    #[prop(into, optional)]
+    on_item_context_menu: Option<Callback<(WindowItem<T>, MouseEvent)>>,
+
+    /// Called when the user opens a loaded item by double-clicking it or by pressing Enter while
+    /// it's focused.
+    ///
+    /// The listeners are put on the root element rendered by `children`. The items get a roving
+    /// `tabindex` so the list is a single tab stop and the arrow keys, Home and End move the
+    /// focus between the rendered items. See [`ItemInteractions`].
+    #[prop(into, optional)]
+    on_item_activate: Option<Callback<WindowItem<T>>>,
+
+    /// The normal children are rendered when an item is loaded.
+    children: CF,
+
+    #[prop(optional)] _marker: PhantomData<(M, L)>,
+) -> impl IntoView
+where
+    T: Send + Sync + 'static,
+    L: InternalLoader<M, Item = T, Query = Q> + 'static,
+    L::Error: Send + Sync,
+    Q: Send + Sync + 'static,
+    CF: Fn(WindowItem<T>) -> V + Send + Clone + 'static,
+    V: IntoView,
+{
+    let scroll_element = NodeRef::<leptos::html::Div>::new();
+
+    let UseVirtualizationResult {
+        item_window: window,
+        offset_before,
+        offset_after,
+        go_to_index,
+        ..
+    } = use_virtualization(
+        state,
+        scroll_element,
+        loader,
+        query,
+        item_height,
+        UseVirtualizationOptions::default()
+            .overscan_item_count(overscan_item_count)
+            .initial_item_count(initial_item_count)
+            .link(link)
+            .error_ttl(error_ttl)
+            .tags(tags),
+    );
+
+    cache_controller.init_with_item_window(window);
+    controller.init(window, go_to_index);
+
+    let interactions = ItemInteractions::new(link, on_item_context_menu, on_item_activate);
+
+    let empty_view = move || {
+        if window.cache.item_count().get() == Some(0) {
+            empty.clone().map(|e| (e.children)())
+        } else {
+            None
+        }
+    };
+
+    let end_of_list_view = move || {
+        if window
+            .cache
+            .item_count()
+            .get()
+            .is_some_and(|count| count > 0)
+        {
+            end_of_list.clone().map(|e| (e.children)())
+        } else {
+            None
+        }
+    };
+
+    view! {
+        <div
+            node_ref=scroll_element
+            id=state.list_id().get_untracked()
+            style:overflow-y="auto"
+        >
+            {empty_view}
+
+            <div aria-hidden="true" style:height=move || format!("{}px", offset_before.get())></div>
+
+            <For each=move || window.displayed_keys() key=|key| *key let:key>
+                {
+                    let children = children.clone();
+                    let loading = loading.clone();
+                    let load_error = load_error.clone();
+                    let index = Memo::new(move |_| window.index_of(key));
+
+                    move || {
+                        let Some(index) = index.get() else {
+                            return ().into_any();
+                        };
+
+                        match &*window.cache.items().at_unkeyed(index).read() {
+                            ItemState::Loaded(item) => {
+                                let item = WindowItem::new(index, Arc::clone(item), &window);
+                                interactions.apply(children.clone()(item.clone()).into_any(), &item, window)
+                            }
+                            ItemState::Error(error) => {
+                                load_error
+                                    .clone()
+                                    .map(|e| {
+                                        let error = ItemLoadError::new(index, error.clone(), &window);
+                                        (e.children)(error).into_any()
+                                    })
+                                    .unwrap_or_else(|| {
+
+                                        view! { <div style="color: red;">Error: {error.to_string()}</div> }
+                                            .into_any()
+                                    })
+                            }
+                            _ => {
+                                loading
+                                    .clone()
+                                    .map(|l| (l.children)().into_any())
+                                    .unwrap_or_else(|| ().into_any())
+                            }
+                        }
+                    }
+                }
+            </For>
+
+            <div aria-hidden="true" style:height=move || format!("{}px", offset_after.get())></div>
+
+            {end_of_list_view}
+        </div>
+    }
+}
@@ -0,0 +1,3 @@
+mod virtualization;
+
+pub use virtualization::*;
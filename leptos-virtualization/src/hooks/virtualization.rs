@@ -0,0 +1,308 @@
+use std::{fmt::Debug, ops::Range, time::Duration};
+
+use default_struct_builder::DefaultBuilder;
+use leptos::prelude::*;
+use leptos_use::{
+    UseElementSizeReturn, UseScrollReturn,
+    core::{ElementMaybeSignal, IntoElementMaybeSignal},
+    use_element_size, use_scroll,
+};
+use leptos_windowing::{
+    InternalLoader, ItemWindow, WindowLink,
+    hook::{UseLoadOnDemandOptions, UseLoadOnDemandResult, use_load_on_demand},
+};
+use reactive_stores::Store;
+
+use crate::{VirtualizationState, VirtualizationStateStoreFields};
+
+/// Hook for the virtualization logic.
+///
+/// Only the items that are visible in the scroll container are displayed. All items have to be
+/// `item_height` pixels high. The space of the items before and after them is filled with
+/// [`UseVirtualizationResult::offset_before`] and [`UseVirtualizationResult::offset_after`]
+/// so the scrollbar behaves as if all items were rendered.
+///
+/// Loading and caching is handled the same way as in the pagination crate.
+///
+/// This is the logic of [`VirtualFor`](crate::VirtualFor).
+///
+/// ## Usage
+///
+/// ```
+/// # use std::ops::Range;
+/// #
+/// # use leptos::{html::Div, prelude::*};
+/// # use leptos_virtualization::{use_virtualization, UseVirtualizationOptions, VirtualizationState, MemoryLoader};
+/// #
+/// # pub struct ExampleLoader;
+/// #
+/// # impl MemoryLoader for ExampleLoader {
+/// #     type Item = usize;
+/// #     type Query = ();
+/// #
+/// #     fn load_items(&self, range: Range<usize>, _query: &Self::Query) -> Vec<Self::Item> {
+/// #         range.collect()
+/// #     }
+/// #
+/// #     fn item_count(&self, _query: &Self::Query) -> usize {
+/// #         100_000
+/// #     }
+/// # }
+/// #
+/// # #[component]
+/// # pub fn App() -> impl IntoView {
+/// let state = VirtualizationState::new_store();
+/// let scroll_container = NodeRef::<Div>::new();
+///
+/// let result = use_virtualization(
+///     state,
+///     scroll_container,
+///     ExampleLoader,
+///     (),
+///     32.0, // every item is 32px high
+///     UseVirtualizationOptions::default(),
+/// );
+/// #
+/// # view! { <div node_ref=scroll_container></div> }
+/// # }
+/// ```
+///
+/// ## Parameters
+///
+/// - `state`: The virtualization state. Kept up to date with the visible items.
+/// - `scroll_element`: The element that scrolls and contains the items.
+/// - `loader`: The loader used to load items from the data source.
+/// - `query`: The query to load the items with.
+/// - `item_height`: The height of every item in pixels.
+/// - `options`: Additional options. See [`UseVirtualizationOptions`].
+#[must_use]
+pub fn use_virtualization<T, L, Q, M, El, ElM>(
+    state: Store<VirtualizationState>,
+    scroll_element: El,
+    loader: L,
+    query: impl Into<Signal<Q>>,
+    item_height: impl Into<Signal<f64>>,
+    options: UseVirtualizationOptions<T>,
+) -> UseVirtualizationResult<T>
+where
+    T: Send + Sync + 'static,
+    L: InternalLoader<M, Item = T, Query = Q> + 'static,
+    L::Error: Send + Sync,
+    Q: Send + Sync + 'static,
+    El: IntoElementMaybeSignal<web_sys::Element, ElM>,
+{
+    let UseVirtualizationOptions {
+        overscan_item_count,
+        initial_item_count,
+        link,
+        error_ttl,
+        tags,
+    } = options;
+
+    let item_height = item_height.into();
+
+    let scroll_element: ElementMaybeSignal<web_sys::Element> =
+        scroll_element.into_element_maybe_signal();
+
+    let UseScrollReturn { y, set_y, .. } = use_scroll(scroll_element);
+    let UseElementSizeReturn { height, .. } = use_element_size(scroll_element);
+
+    let range_to_display = Memo::new(move |_| {
+        visible_range(y.get(), height.get(), item_height.get(), initial_item_count)
+    });
+
+    let range_to_load = Memo::new(move |_| {
+        let Range { start, end } = range_to_display.get();
+
+        start.saturating_sub(overscan_item_count)..end + overscan_item_count
+    });
+
+    let UseLoadOnDemandResult { item_window, .. } = use_load_on_demand(
+        range_to_load,
+        range_to_display,
+        loader,
+        query,
+        UseLoadOnDemandOptions::default()
+            .cache(link.map(|link| link.cache()))
+            .error_ttl(error_ttl)
+            .tags(tags),
+    );
+
+    let go_to_index =
+        Callback::new(move |index: usize| set_y(index as f64 * item_height.get_untracked()));
+
+    if let Some(link) = link {
+        link.connect(item_window, go_to_index);
+    }
+
+    let offset_before =
+        Signal::derive(move || item_window.range.get().start as f64 * item_height.get());
+
+    let offset_after = Signal::derive(move || {
+        let end = item_window.range.get().end;
+        let item_count = item_window.cache.item_count().get().unwrap_or(end);
+
+        item_count.saturating_sub(end) as f64 * item_height.get()
+    });
+
+    Effect::new(move || {
+        let range = item_window.range.get();
+
+        state.first_visible_index().set(range.start);
+        state.visible_item_count().set(range.len());
+    });
+
+    Effect::new(move || {
+        state.item_count().set(item_window.cache.item_count().get());
+    });
+
+    UseVirtualizationResult {
+        item_window,
+        offset_before,
+        offset_after,
+        loaded_fraction: item_window.loaded_fraction,
+        go_to_index,
+    }
+}
+
+/// Return type of [`use_virtualization`].
+pub struct UseVirtualizationResult<T: Send + Sync + 'static> {
+    /// The visible items.
+    pub item_window: ItemWindow<T>,
+
+    /// The height in pixels of the items before the visible ones.
+    ///
+    /// Render an empty element of this height before the items.
+    pub offset_before: Signal<f64>,
+
+    /// The height in pixels of the items after the visible ones.
+    ///
+    /// Render an empty element of this height after the items.
+    pub offset_after: Signal<f64>,
+
+    /// The fraction of the visible items that are loaded from `0.0` to `1.0`.
+    ///
+    /// Use this to render a progress bar while the items stream in.
+    pub loaded_fraction: Signal<f32>,
+
+    /// Scrolls the item with the given index to the top of the scroll container.
+    pub go_to_index: Callback<usize>,
+}
+
+/// Options for [`use_virtualization`].
+#[derive(DefaultBuilder)]
+pub struct UseVirtualizationOptions<T>
+where
+    T: Send + Sync + 'static,
+{
+    /// How many items before and after the visible ones are loaded in the background.
+    ///
+    /// Defaults to 10.
+    overscan_item_count: usize,
+
+    /// How many items are displayed as long as the scroll container hasn't been measured, e.g.
+    /// when rendering on the server.
+    ///
+    /// Defaults to 20.
+    initial_item_count: usize,
+
+    /// Link this window to other windows over the same data.
+    ///
+    /// Linked windows share their cache and navigate together. See [`WindowLink`].
+    /// Defaults to `None`.
+    #[builder(keep_type)]
+    link: Option<WindowLink<T>>,
+
+    /// How long items that failed to load keep their error. Afterwards they are loaded again when
+    /// they're scrolled into view again.
+    /// Defaults to `None` which keeps the errors until the window is reloaded.
+    error_ttl: Option<Duration>,
+
+    /// Invalidation tags of the data of this window like `"customers"`. Calling
+    /// [`invalidate_tag`](crate::invalidate_tag) with one of them reloads the window while the
+    /// loaded items stay displayed.
+    /// Defaults to no tags.
+    tags: Vec<String>,
+}
+
+impl<T> Default for UseVirtualizationOptions<T>
+where
+    T: Send + Sync + 'static,
+{
+    fn default() -> Self {
+        Self {
+            overscan_item_count: 10,
+            initial_item_count: 20,
+            link: None,
+            error_ttl: None,
+            tags: Vec::new(),
+        }
+    }
+}
+
+impl<T> Clone for UseVirtualizationOptions<T>
+where
+    T: Send + Sync + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            overscan_item_count: self.overscan_item_count,
+            initial_item_count: self.initial_item_count,
+            link: self.link,
+            error_ttl: self.error_ttl,
+            tags: self.tags.clone(),
+        }
+    }
+}
+
+impl<T> Debug for UseVirtualizationOptions<T>
+where
+    T: Send + Sync + 'static,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UseVirtualizationOptions")
+            .field("overscan_item_count", &self.overscan_item_count)
+            .field("initial_item_count", &self.initial_item_count)
+            .field("link", &self.link.is_some())
+            .field("error_ttl", &self.error_ttl)
+            .field("tags", &self.tags)
+            .finish()
+    }
+}
+
+/// The range of the items of height `item_height` that are visible in a viewport of
+/// `viewport_height` scrolled down by `scroll_top`.
+///
+/// Falls back to the first `initial_item_count` items as long as nothing can be measured.
+fn visible_range(
+    scroll_top: f64,
+    viewport_height: f64,
+    item_height: f64,
+    initial_item_count: usize,
+) -> Range<usize> {
+    if item_height <= 0.0 || viewport_height <= 0.0 || !viewport_height.is_finite() {
+        return 0..initial_item_count;
+    }
+
+    let scroll_top = scroll_top.max(0.0);
+
+    let start = (scroll_top / item_height).floor() as usize;
+    let end = ((scroll_top + viewport_height) / item_height).ceil() as usize;
+
+    start..end.max(start + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_visible_range() {
+        assert_eq!(visible_range(0.0, 400.0, 40.0, 20), 0..10);
+        assert_eq!(visible_range(60.0, 400.0, 40.0, 20), 1..12);
+        assert_eq!(visible_range(-10.0, 400.0, 40.0, 20), 0..10);
+        assert_eq!(visible_range(0.0, 10.0, 40.0, 20), 0..1);
+        assert_eq!(visible_range(0.0, 0.0, 40.0, 20), 0..20);
+        assert_eq!(visible_range(0.0, 400.0, 0.0, 20), 0..20);
+    }
+}
@@ -0,0 +1,95 @@
+//! Virtualization for Leptos.
+//!
+//! This crate contains hooks and components for virtualized lists. Only the items that are
+//! visible in the scroll container are rendered while the scrollbar behaves as if all of them were.
+//! Like leptos-pagination it provides efficient loading and caching of large data.
+//!
+//! ## Usage
+//!
+//! ```
+//! # use leptos::prelude::*;
+//! # use leptos_virtualization::*;
+//! # use std::ops::Range;
+//! #
+//! pub struct Book {
+//!     title: String,
+//! }
+//!
+//! // Implement one of the loader traits for this struct (not shown here, see below).
+//! pub struct BookLoader;
+//!
+//! # // Dummy implementation for demonstration purposes
+//! # impl MemoryLoader for BookLoader {
+//! #     type Item = Book;
+//! #     type Query = ();
+//! #     fn load_items(&self, range: Range<usize>, _query: &()) -> Vec<Self::Item> {
+//! #         vec![]
+//! #     }
+//! #     fn item_count(&self, _query: &()) -> usize {
+//! #         0
+//! #     }
+//! # }
+//! #
+//! # #[component]
+//! # pub fn App() -> impl IntoView {
+//! view! {
+//!     // Every item is 32px high. Give the list a fixed height with CSS.
+//!     <VirtualFor loader=BookLoader query=() item_height=32.0 attr:class="book-list" let:idx_book>
+//!         // idx_book is a `WindowItem` containing the index and the book data
+//!         <div class="book">{idx_book.data.title.clone()}</div>
+//!     </VirtualFor>
+//! }
+//! # }
+//! ```
+//!
+//! ## Loading data
+//!
+//! Loading data is done through implementing one of the various `Loader` traits. Depending on your use case
+//! you should implement the trait that best fits your needs:
+//!
+//! - [`MemoryLoader`]: If your dataset is already in memory like in a `Vec`, `HashSet`, array, ...
+//! - [`PaginatedLoader`]: If your data source provides data in pages (independent of if you use UI pagination or virtualization).
+//! - [`ExactLoader`]: If your data source can provide an exact range of items (start index to end index).
+//! - [`CursorLoader`]: If your data source paginates by opaque cursor instead of by offset.
+//! - [`KeysetLoader`]: If your data source loads the items after the sort key of the last item (seek pagination).
+//! - [`RelayLoader`]: If your data source is a GraphQL API with Relay connections.
+//! - [`StreamLoader`]: If your data source delivers the items one by one as a stream. They're displayed as they arrive.
+//! - `ODataLoader`: If your data source is an OData service. Requires the feature `odata`.
+//! - `StartAfterLoader`: If your data source is a document database with `startAfter` cursors like Firestore. Requires the feature `start-after`.
+//! - [`Loader`]: If none of the above fit your needs, you can implement this trait to provide your own loading logic.
+//!
+//! Please refer to the documentation of leptos-windowing to see how to implement these traits.
+//!
+//! ## Components
+//!
+//! - [`VirtualFor`]: A component that renders only the visible items of a list inside a scroll container.
+//!
+//! ## Hooks
+//!
+//! The components are just thin wrappers that add commonly used html to hook functions that implement the actual logic.
+//! So if you want to customize your markup more than what the pre-made components allow you can use these hooks directly.
+//!
+//! - [`use_virtualization`]: Logic for [`VirtualFor`]. Computes the visible items from the scroll position and loads them on-demand.
+//!
+//! ## State
+//!
+//! [`VirtualizationState`] is a reactive store that is kept up to date with the visible items,
+//! e.g. to display "Showing items 20 - 35 of 1000" outside of the list.
+
+mod components;
+mod hooks;
+mod state;
+
+pub use components::*;
+pub use hooks::*;
+pub use state::*;
+
+pub use leptos_windowing::*;
+
+/// The [`COMPAT_VERSION`] of leptos-windowing that this crate is written against.
+///
+/// Pass it to [`assert_compatible`] of the leptos-windowing you depend on directly to make sure
+/// both are the same version.
+pub const WINDOWING_COMPAT_VERSION: u32 = 1;
+
+const _: () = leptos_windowing::assert_compatible(WINDOWING_COMPAT_VERSION);
@@ -0,0 +1,33 @@
+use reactive_stores::Store;
+
+use crate::use_unique_id;
+
+/// The state of virtualization.
+///
+/// Used as a reactive store to communicate between the list and other components, e.g. to
+/// display "Showing items 20 - 35 of 1000".
+#[derive(Store, Clone, Debug, PartialEq, Eq)]
+pub struct VirtualizationState {
+    /// The index of the first item that is visible in the scroll container. Counting starts from 0.
+    pub first_visible_index: usize,
+    /// How many items are visible in the scroll container.
+    pub visible_item_count: usize,
+    /// The total number of items or None initially or if the count could not be determined.
+    pub item_count: Option<usize>,
+    /// The id of the scroll container. [`VirtualFor`](crate::VirtualFor) puts it on the element
+    /// it renders.
+    pub list_id: String,
+}
+
+impl VirtualizationState {
+    /// Creates a new store. Call this in the body of a component so the generated `list_id` is
+    /// the same on the server and in the browser. See [`use_unique_id`].
+    pub fn new_store() -> Store<Self> {
+        Store::new(Self {
+            first_visible_index: 0,
+            visible_item_count: 0,
+            item_count: None,
+            list_id: use_unique_id("virtualization-list"),
+        })
+    }
+}
@@ -0,0 +1,146 @@
+//! Derive macros for [`leptos-windowing`](https://docs.rs/leptos-windowing) and
+//! [`leptos-pagination`](https://docs.rs/leptos-pagination).
+//!
+//! Use them through the re-exports of these crates.
+
+use proc_macro::TokenStream;
+use proc_macro_crate::{FoundCrate, crate_name};
+use proc_macro2::Span;
+use quote::quote;
+use syn::{
+    Data, DeriveInput, Error, Fields, Ident, LitInt, LitStr, Member, parse_macro_input,
+    spanned::Spanned,
+};
+
+/// Implements `Sortable` by comparing the fields marked with `#[sortable]`.
+///
+/// The columns are numbered in the order of the marked fields starting from 0. You can give a field
+/// an explicit column with `#[sortable(column = 3)]`. Fields are compared with `PartialOrd`;
+/// incomparable values like `NaN` are considered equal.
+///
+/// The path to the trait is detected from your dependencies. If that fails (e.g. when the crate is
+/// renamed), specify it with `#[sortable(crate = "my_leptos_windowing")]` on the struct.
+#[proc_macro_derive(Sortable, attributes(sortable))]
+pub fn derive_sortable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand_sortable(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand_sortable(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let mut crate_path = None;
+
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("sortable"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("crate") {
+                let path: LitStr = meta.value()?.parse()?;
+                crate_path = Some(path.parse::<syn::Path>()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `crate = \"...\"`"))
+            }
+        })?;
+    }
+
+    let crate_path = match crate_path {
+        Some(path) => quote! { #path },
+        None => windowing_crate_path(),
+    };
+
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new(
+            input.span(),
+            "`Sortable` can only be derived for structs",
+        ));
+    };
+
+    let mut arms = Vec::new();
+    let mut columns = Vec::new();
+    let mut next_column = 0_usize;
+
+    let members: Vec<(Member, &syn::Field)> = match &data.fields {
+        Fields::Named(fields) => fields
+            .named
+            .iter()
+            .map(|field| (Member::Named(field.ident.clone().unwrap()), field))
+            .collect(),
+        Fields::Unnamed(fields) => fields
+            .unnamed
+            .iter()
+            .enumerate()
+            .map(|(i, field)| (Member::Unnamed(i.into()), field))
+            .collect(),
+        Fields::Unit => vec![],
+    };
+
+    for (member, field) in members {
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("sortable"))
+        {
+            let mut column = next_column;
+
+            if !matches!(attr.meta, syn::Meta::Path(_)) {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("column") {
+                        column = meta.value()?.parse::<LitInt>()?.base10_parse()?;
+                        Ok(())
+                    } else {
+                        Err(meta.error("expected `column = ...`"))
+                    }
+                })?;
+            }
+
+            if columns.contains(&column) {
+                return Err(Error::new(
+                    attr.span(),
+                    format!("duplicate column {column}"),
+                ));
+            }
+            columns.push(column);
+            next_column = column + 1;
+
+            arms.push(quote! {
+                #column => ::std::cmp::PartialOrd::partial_cmp(&a.#member, &b.#member)
+                    .unwrap_or(::std::cmp::Ordering::Equal),
+            });
+        }
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #crate_path::Sortable for #name #ty_generics #where_clause {
+            fn compare(a: &Self, b: &Self, column: usize) -> ::std::cmp::Ordering {
+                match column {
+                    #(#arms)*
+                    _ => ::std::cmp::Ordering::Equal,
+                }
+            }
+        }
+    })
+}
+
+/// Finds `leptos-windowing` or `leptos-pagination` (which re-exports it) in the dependencies.
+fn windowing_crate_path() -> proc_macro2::TokenStream {
+    for name in ["leptos-windowing", "leptos-pagination"] {
+        let ident = match crate_name(name) {
+            // The crate declares `extern crate self as ...` so doc tests and the crate itself can use the same path.
+            Ok(FoundCrate::Itself) => Ident::new(&name.replace('-', "_"), Span::call_site()),
+            Ok(FoundCrate::Name(name)) => Ident::new(&name, Span::call_site()),
+            Err(_) => continue,
+        };
+
+        return quote! { ::#ident };
+    }
+
+    quote! { ::leptos_windowing }
+}
@@ -0,0 +1,58 @@
+//! Measures how long it takes to write loaded items into a cache that is watched by an effect
+//! like the load effect of `use_load_on_demand`.
+//!
+//! Run with `cargo bench -p leptos-windowing --bench cache`.
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use leptos::{prelude::*, reactive::effect::ImmediateEffect};
+use leptos_windowing::{LoadedItems, WindowLink, cache::Cache};
+
+const ITEM_COUNT: usize = 10_000;
+const ITERATIONS: u32 = 20;
+
+fn main() {
+    let owner = Owner::new();
+    owner.set();
+
+    // Before: the effect tracked the whole store and reran for every loaded item.
+    let whole_store = measure(|cache| cache.track());
+    // After: the effect only tracks the changes that can make items go missing.
+    let missing_only = measure(|cache| cache.track_missing());
+
+    println!("write {ITEM_COUNT} loaded items while tracking the whole cache: {whole_store:?}");
+    println!("write {ITEM_COUNT} loaded items while tracking missing items:  {missing_only:?}");
+}
+
+/// The average time to load `ITEM_COUNT` items while an effect tracks the cache with `track`
+/// and looks for missing items.
+fn measure(track: fn(&Cache<usize>)) -> Duration {
+    let mut total = Duration::ZERO;
+
+    for _ in 0..ITERATIONS {
+        let cache = WindowLink::<usize>::new().cache();
+
+        let _effect = ImmediateEffect::new_isomorphic(move || {
+            track(&cache);
+            black_box(untrack(|| cache.missing_range(0..ITEM_COUNT)));
+        });
+
+        let start = Instant::now();
+
+        cache.write_loading(0..ITEM_COUNT);
+        cache.write_loaded(
+            Ok(LoadedItems {
+                items: (0..ITEM_COUNT).collect::<Vec<_>>(),
+                range: 0..ITEM_COUNT,
+            }),
+            0..ITEM_COUNT,
+        );
+
+        total += start.elapsed();
+    }
+
+    total / ITERATIONS
+}
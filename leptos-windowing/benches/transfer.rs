@@ -0,0 +1,69 @@
+//! Compares the transfer formats by the size of an encoded chunk of items and how long it takes to
+//! encode and decode it.
+//!
+//! Run with `cargo bench -p leptos-windowing --bench transfer --features postcard,bincode`.
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use leptos_windowing::{Json, TransferFormat};
+use serde::{Deserialize, Serialize};
+
+const ITEM_COUNT: usize = 10_000;
+const ITERATIONS: u32 = 20;
+
+#[derive(Serialize, Deserialize)]
+struct Row {
+    id: u64,
+    name: String,
+    price: f64,
+    in_stock: bool,
+}
+
+fn main() {
+    let rows = (0..ITEM_COUNT as u64)
+        .map(|id| Row {
+            id,
+            name: format!("Item number {id}"),
+            price: id as f64 * 0.25,
+            in_stock: id % 3 != 0,
+        })
+        .collect::<Vec<_>>();
+
+    report::<Json>("json", &rows);
+
+    #[cfg(feature = "postcard")]
+    report::<leptos_windowing::Postcard>("postcard", &rows);
+
+    #[cfg(feature = "bincode")]
+    report::<leptos_windowing::Bincode>("bincode", &rows);
+}
+
+fn report<F: TransferFormat>(name: &str, rows: &[Row]) {
+    let (len, encode, decode) = measure::<F>(rows);
+
+    println!("{name:<8} {ITEM_COUNT} rows: {len:>8} bytes, encode {encode:?}, decode {decode:?}");
+}
+
+/// The size of the encoded `rows` and the average time to encode and decode them.
+fn measure<F: TransferFormat>(rows: &[Row]) -> (usize, Duration, Duration) {
+    let mut encode = Duration::ZERO;
+    let mut decode = Duration::ZERO;
+    let mut len = 0;
+
+    for _ in 0..ITERATIONS {
+        let start = Instant::now();
+        let bytes = F::encode(rows).unwrap();
+        encode += start.elapsed();
+
+        let start = Instant::now();
+        black_box(F::decode::<Vec<Row>>(&bytes).unwrap());
+        decode += start.elapsed();
+
+        len = bytes.len();
+    }
+
+    (len, encode / ITERATIONS, decode / ITERATIONS)
+}
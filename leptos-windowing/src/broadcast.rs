@@ -0,0 +1,91 @@
+use std::cell::RefCell;
+
+use leptos::wasm_bindgen::{JsCast, JsValue, closure::Closure};
+use web_sys::{BroadcastChannel, MessageEvent};
+
+use crate::tags::invalidate_tag_in_this_tab;
+
+/// The name of the channel that all tabs of the app share unless it's changed with
+/// [`set_broadcast_channel_name`].
+pub const DEFAULT_BROADCAST_CHANNEL_NAME: &str = "leptos-windowing-invalidation";
+
+thread_local! {
+    static CHANNEL_NAME: RefCell<String> = RefCell::new(DEFAULT_BROADCAST_CHANNEL_NAME.to_string());
+
+    // Opened once per tab. Outside of the browser there are no other tabs to notify.
+    static CHANNEL: RefCell<Option<OpenChannel>> = const { RefCell::new(None) };
+}
+
+struct OpenChannel {
+    channel: BroadcastChannel,
+    // Keeps the message handler alive as long as the channel is open.
+    _on_message: Closure<dyn FnMut(MessageEvent)>,
+}
+
+impl Drop for OpenChannel {
+    fn drop(&mut self) {
+        self.channel.set_onmessage(None);
+        self.channel.close();
+    }
+}
+
+/// Changes the name of the `BroadcastChannel` that [`invalidate_tag`](crate::invalidate_tag)
+/// uses to reach the other tabs. Requires the feature `broadcast`.
+///
+/// Only tabs that use the same name invalidate each other's lists. Use this if several apps on
+/// the same origin shouldn't reload each other's lists. Call it once at startup in every tab.
+/// Defaults to [`DEFAULT_BROADCAST_CHANNEL_NAME`].
+pub fn set_broadcast_channel_name(name: impl Into<String>) {
+    let name = name.into();
+
+    let is_changed = CHANNEL_NAME.with_borrow_mut(|channel_name| {
+        let is_changed = *channel_name != name;
+        *channel_name = name;
+        is_changed
+    });
+
+    // Reopen the channel with the new name if it's been opened already
+    if is_changed && CHANNEL.with_borrow_mut(|channel| channel.take()).is_some() {
+        listen_to_other_tabs();
+    }
+}
+
+fn open_channel() -> Option<OpenChannel> {
+    if !cfg!(target_arch = "wasm32") {
+        return None;
+    }
+
+    let channel = CHANNEL_NAME.with_borrow(|name| BroadcastChannel::new(name).ok())?;
+
+    let on_message = Closure::<dyn FnMut(MessageEvent)>::new(|event: MessageEvent| {
+        if let Some(tag) = event.data().as_string() {
+            invalidate_tag_in_this_tab(&tag);
+        }
+    });
+    channel.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+
+    Some(OpenChannel {
+        channel,
+        _on_message: on_message,
+    })
+}
+
+/// Starts receiving the tags that are invalidated in the other tabs.
+pub(crate) fn listen_to_other_tabs() {
+    CHANNEL.with_borrow_mut(|channel| {
+        if channel.is_none() {
+            *channel = open_channel();
+        }
+    });
+}
+
+/// Invalidates `tag` in the other tabs of the app. The sending tab doesn't receive it.
+pub(crate) fn broadcast_tag(tag: &str) {
+    listen_to_other_tabs();
+
+    CHANNEL.with_borrow(|channel| {
+        if let Some(OpenChannel { channel, .. }) = channel {
+            let _ = channel.post_message(&JsValue::from_str(tag));
+        }
+    });
+}
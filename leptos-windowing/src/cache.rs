@@ -1,11 +1,18 @@
 use leptos::prelude::*;
 use reactive_stores::{Store, StoreFieldIterator, Subfield};
 use std::{
+    collections::{HashMap, HashSet},
+    hash::{DefaultHasher, Hash, Hasher},
     ops::{Index, Range},
     sync::Arc,
+    time::Duration,
 };
 
-use crate::{ItemWindow, LoadedItems, item_state::ItemState};
+use crate::{
+    ItemWindow, LiveUpdate, LoadedItems,
+    item_state::{ItemError, ItemState},
+    strict::misuse,
+};
 
 /// This is a cache for items used internally to track
 /// which items are already loaded, which are still loading and which are missing.
@@ -17,8 +24,34 @@ where
     pub(crate) pause_reactive_loading: Callback<()>,
     pub(crate) resume_reactive_loading: Callback<()>,
     pub(crate) is_reactive_loading_active: Signal<bool>,
+    pub(crate) reload: Callback<()>,
+    pub(crate) load_range: Callback<Range<usize>>,
+    pub(crate) refresh_range: Callback<Range<usize>>,
+    /// How long loaded items stay fresh. See [`Cache::is_stale`].
+    pub(crate) ttl: Option<Duration>,
+    listeners: StoredValue<CacheListeners<T>>,
+    /// When the items were loaded or failed to load in milliseconds since the Unix epoch.
+    /// Indexed like the items. Items that are marked stale have [`MARKED_STALE`].
+    loaded_at: StoredValue<Vec<f64>>,
+    item_key: StoredValue<Option<ItemKey<T>>>,
+    /// The index of the loaded items by their key if an [`ItemKey`] is set. Entries can be
+    /// outdated, e.g. after the item has been invalidated, so they're checked against the items.
+    key_indices: StoredValue<HashMap<u64, usize>>,
+    /// The indices of the items that are hidden with [`Cache::hide_item`].
+    hidden: RwSignal<HashSet<usize>>,
+    /// The current indices of the items that are being removed with [`Cache::optimistic_remove`].
+    pending_removals: StoredValue<PendingRemovals>,
+    missing_trigger: Trigger,
+    failed_trigger: Trigger,
+    stale_trigger: Trigger,
+    /// Notified once per batch of items that start or finish loading.
+    load_trigger: Trigger,
 }
 
+/// The load time of items that are being loaded again while they're displayed. As it's older
+/// than any other time they're also stale for every ttl.
+const MARKED_STALE: f64 = f64::NEG_INFINITY;
+
 impl<T> Clone for Cache<T>
 where
     T: Send + Sync + 'static,
@@ -56,6 +89,20 @@ impl<T: Send + Sync + 'static> Cache<T> {
             pause_reactive_loading: (|| {}).into(),
             resume_reactive_loading: (|| {}).into(),
             is_reactive_loading_active: Signal::stored(true),
+            reload: (|| {}).into(),
+            load_range: Callback::new(|_| {}),
+            refresh_range: Callback::new(|_| {}),
+            ttl: None,
+            listeners: StoredValue::new(CacheListeners::default()),
+            loaded_at: StoredValue::new(Vec::new()),
+            item_key: StoredValue::new(None),
+            key_indices: StoredValue::new(HashMap::new()),
+            hidden: RwSignal::new(HashSet::new()),
+            pending_removals: StoredValue::new(PendingRemovals::default()),
+            missing_trigger: Trigger::new(),
+            failed_trigger: Trigger::new(),
+            stale_trigger: Trigger::new(),
+            load_trigger: Trigger::new(),
         }
     }
 
@@ -86,11 +133,332 @@ impl<T: Send + Sync + 'static> Cache<T> {
         ret
     }
 
+    #[inline]
+    /// Clears the cache and reloads the item count and the items in the load range with the loader.
+    pub fn reload(&self) {
+        self.reload.run(());
+    }
+
+    #[inline]
+    /// Loads the items in the given range into the cache if they're not loaded or loading already.
+    ///
+    /// This is useful to warm the cache before the range is displayed.
+    pub fn prefetch(&self, range: Range<usize>) {
+        self.load_range.run(range);
+    }
+
+    /// Loads the loaded items in the given range again while they stay displayed.
+    ///
+    /// Meanwhile they're [stale](Cache::is_stale). Use this for example to make sure that the
+    /// record opened in a detail view is up to date. The loader may load the whole chunk or page
+    /// that contains the range.
+    pub fn refresh(&self, range: Range<usize>) {
+        self.mark_stale(range.clone());
+        self.refresh_range.run(range);
+    }
+
+    /// Loads the item at `index` again with the loader and replaces it, e.g. after it has been
+    /// edited so it shows the data source's version.
+    ///
+    /// Unlike [`Cache::refresh`] this also loads the item if it's missing or failed to load.
+    /// A loaded item stays displayed while it's loaded again. Nothing happens if it's already
+    /// loading. The loader may load the whole chunk or page that contains the item.
+    pub fn reload_item(&self, index: usize) {
+        let range = index..index + 1;
+
+        // Read first so the guard is released before the write
+        let is_loaded = match self.inner.items().read_untracked().get(index) {
+            None | Some(ItemState::Loading) => return,
+            Some(row) => matches!(row, ItemState::Loaded(_)),
+        };
+
+        if is_loaded {
+            self.mark_stale(range.clone());
+        } else {
+            self.write_loading(range.clone());
+        }
+
+        self.refresh_range.run(range);
+    }
+
+    /// Whether the item at the given index is loaded but possibly outdated. This is the case while
+    /// it's loaded again with [`Cache::refresh`] or [`Cache::reload`] in stale-while-revalidate
+    /// mode or when it has been loaded longer than the `ttl` option ago.
+    ///
+    /// This is reactive for the item but isn't notified when the ttl passes.
+    pub fn is_stale(&self, index: usize) -> bool {
+        self.inner.items().at_unkeyed(index).track();
+        self.stale_trigger.track();
+
+        let loaded_at = self
+            .loaded_at
+            .with_value(|loaded_at| loaded_at.get(index).copied())
+            .filter(|loaded_at| !loaded_at.is_nan());
+
+        loaded_at.is_some_and(|loaded_at| {
+            loaded_at == MARKED_STALE
+                || self
+                    .ttl
+                    .is_some_and(|ttl| now_millis() - loaded_at >= ttl.as_secs_f64() * 1000.0)
+        })
+    }
+
+    /// Marks the loaded items in `range` as stale until they're loaded again.
+    pub(crate) fn mark_stale(&self, range: Range<usize>) {
+        self.loaded_at.update_value(|loaded_at| {
+            let items = self.inner.items().read_untracked();
+            let end = range.end.min(loaded_at.len()).min(items.len());
+
+            for (loaded_at, item) in loaded_at[range.start.min(end)..end]
+                .iter_mut()
+                .zip(&items[range.start.min(end)..end])
+            {
+                if matches!(item, ItemState::Loaded(_)) {
+                    *loaded_at = MARKED_STALE;
+                }
+            }
+        });
+
+        self.stale_trigger.notify();
+    }
+
+    /// Marks the loaded or errored items in the given range as missing.
+    ///
+    /// If they are inside the range to load, they are reloaded with the loader.
+    pub fn invalidate_range(&self, range: Range<usize>) {
+        let range = range.start..range.end.min(self.inner.items().read_untracked().len());
+        if range.start >= range.end {
+            return;
+        }
+
+        for row in self.inner.items().write()[range.clone()].iter_mut() {
+            if !matches!(row, ItemState::Loading) {
+                *row = ItemState::Placeholder;
+            }
+        }
+
+        self.missing_trigger.notify();
+        self.failed_trigger.notify();
+
+        self.emit(|| CacheEvent::Invalidated { range });
+    }
+
+    /// Loads the loaded items in the given range that were loaded longer than `ttl` ago again
+    /// while they stay displayed.
+    ///
+    /// Like with [`Cache::refresh`] they're [stale](Cache::is_stale) meanwhile, so they can be
+    /// dimmed instead of flashing the loading state. Items that are already being loaded again
+    /// aren't requested a second time.
+    pub fn refresh_stale(&self, range: Range<usize>, ttl: Duration) {
+        let now = now_millis();
+        let ttl = ttl.as_secs_f64() * 1000.0;
+
+        let expired = self.loaded_at.with_value(|loaded_at| {
+            let items = self.inner.items().read_untracked();
+            let range = range.start.min(loaded_at.len())..range.end.min(loaded_at.len());
+
+            range
+                .filter(|index| loaded_at[*index] != MARKED_STALE && now - loaded_at[*index] >= ttl)
+                .filter(|index| matches!(items.get(*index), Some(ItemState::Loaded(_))))
+                .collect::<Vec<_>>()
+        });
+
+        for range in contiguous_ranges(expired) {
+            self.refresh(range);
+        }
+    }
+
+    /// Marks the items in the given range that failed to load longer than `ttl` ago as missing.
+    ///
+    /// If they are inside the range to load, they are loaded again with the loader. This lets
+    /// transient failures heal when the user scrolls or pages back to them.
+    pub fn invalidate_expired_errors(&self, range: Range<usize>, ttl: Duration) {
+        if let Some(range) =
+            self.invalidate_older_than(range, ttl, |row| matches!(row, ItemState::Error(_)))
+        {
+            self.missing_trigger.notify();
+            self.failed_trigger.notify();
+
+            self.emit(|| CacheEvent::Invalidated { range });
+        }
+    }
+
+    /// Sets the rows in `range` that match `predicate` and were written longer than `ttl` ago to
+    /// placeholders. Returns the range spanning the invalidated rows if there are any.
+    fn invalidate_older_than(
+        &self,
+        range: Range<usize>,
+        ttl: Duration,
+        predicate: impl Fn(&ItemState<T>) -> bool,
+    ) -> Option<Range<usize>> {
+        let now = now_millis();
+        let ttl = ttl.as_secs_f64() * 1000.0;
+
+        let stale = self.loaded_at.with_value(|loaded_at| {
+            let items = self.inner.items().read_untracked();
+            let range = range.start.min(loaded_at.len())..range.end.min(loaded_at.len());
+
+            range
+                .filter(|index| now - loaded_at[*index] >= ttl)
+                .filter(|index| items.get(*index).is_some_and(&predicate))
+                .collect::<Vec<_>>()
+        });
+
+        let (&first, &last) = (stale.first()?, stale.last()?);
+
+        let items_field = self.inner.items();
+        let mut items = items_field.write();
+
+        for index in stale {
+            items[index] = ItemState::Placeholder;
+        }
+
+        Some(first..last + 1)
+    }
+
     #[inline]
     pub fn track(&self) {
         self.inner.track();
     }
 
+    #[inline]
+    /// Tracks only the changes that can make items go missing, i.e. become placeholders again or shift.
+    ///
+    /// Unlike [`Cache::track`] this isn't notified when items are loaded, so an effect that checks
+    /// [`Cache::missing_range`] doesn't rerun for every single loaded item.
+    pub fn track_missing(&self) {
+        self.missing_trigger.track();
+    }
+
+    #[inline]
+    /// Tracks the changes that start or finish loading items or make them go missing.
+    ///
+    /// Like [`Cache::track_missing`] this isn't notified for every single loaded item but once
+    /// per load. Use it in effects that look at the state of many items.
+    pub fn track_load_state(&self) {
+        self.load_trigger.track();
+        self.missing_trigger.track();
+        self.failed_trigger.track();
+    }
+
+    /// Returns the ranges of items that failed to load.
+    ///
+    /// This is reactive but, like [`Cache::track_missing`], isn't notified for every loaded item.
+    pub fn failed_ranges(&self) -> Vec<Range<usize>> {
+        self.failed_trigger.track();
+
+        let mut ranges = Vec::<Range<usize>>::new();
+
+        for (index, row) in self.inner.items().read_untracked().iter().enumerate() {
+            if !matches!(row, ItemState::Error(_)) {
+                continue;
+            }
+
+            match ranges.last_mut() {
+                Some(range) if range.end == index => range.end = index + 1,
+                _ => ranges.push(index..index + 1),
+            }
+        }
+
+        ranges
+    }
+
+    /// Returns the fraction between 0 and 1 of the items in `range` that finished loading.
+    ///
+    /// Items that failed to load count as finished so the fraction reaches 1 once all loads are done.
+    /// An empty range is complete.
+    ///
+    /// This is reactive but, like [`Cache::track_missing`], isn't notified for every loaded item.
+    pub fn loaded_fraction(&self, range: Range<usize>) -> f32 {
+        if range.is_empty() {
+            return 1.0;
+        }
+
+        self.track_load_state();
+
+        let items = self.inner.items().read_untracked();
+        let finished = items
+            .get(range.start.min(items.len())..range.end.min(items.len()))
+            .unwrap_or_default()
+            .iter()
+            .filter(|row| matches!(row, ItemState::Loaded(_) | ItemState::Error(_)))
+            .count();
+
+        finished as f32 / range.len() as f32
+    }
+
+    /// Whether any of the items in `range` is being loaded.
+    ///
+    /// This is reactive but, like [`Cache::track_missing`], isn't notified for every loaded item.
+    pub fn is_loading(&self, range: Range<usize>) -> bool {
+        self.track_load_state();
+
+        let items = self.inner.items().read_untracked();
+
+        items
+            .get(range.start.min(items.len())..range.end.min(items.len()))
+            .unwrap_or_default()
+            .iter()
+            .any(|row| matches!(row, ItemState::Loading))
+    }
+
+    /// Marks all items that failed to load as missing so they are loaded again without clearing
+    /// the rest of the cache.
+    ///
+    /// Only the items inside the range to load are reloaded right away. The others are loaded
+    /// once they're needed. See also the option `error_ttl` of the hooks to do this automatically.
+    pub fn retry_all_failed(&self) {
+        for range in untrack(|| self.failed_ranges()) {
+            self.invalidate_range(range);
+        }
+    }
+
+    /// Calls `listener` for every change of the cache until the current reactive owner is cleaned up.
+    ///
+    /// This can be used to mirror the loaded data into external stores without polling signals.
+    pub fn on_change(&self, listener: impl Fn(&CacheEvent<T>) + Send + Sync + 'static) {
+        let listeners = self.listeners;
+
+        let id = listeners
+            .try_update_value(|listeners| {
+                let id = listeners.next_id;
+                listeners.next_id += 1;
+                listeners.listeners.push((id, Arc::new(listener)));
+                id
+            })
+            .unwrap_or_default();
+
+        on_cleanup(move || {
+            listeners.try_update_value(|listeners| {
+                listeners
+                    .listeners
+                    .retain(|(listener_id, _)| *listener_id != id);
+            });
+        });
+    }
+
+    fn emit(&self, event: impl FnOnce() -> CacheEvent<T>) {
+        let listeners = self
+            .listeners
+            .try_with_value(|listeners| {
+                listeners
+                    .listeners
+                    .iter()
+                    .map(|(_, listener)| Arc::clone(listener))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        if listeners.is_empty() {
+            return;
+        }
+
+        let event = event();
+        for listener in listeners {
+            listener(&event);
+        }
+    }
+
     #[inline]
     /// Length of the items of cache.
     pub fn len(&self) -> usize {
@@ -109,6 +477,15 @@ impl<T: Send + Sync + 'static> Cache<T> {
         self.inner.item_count()
     }
 
+    /// Sets the total number of items, e.g. when it's restored from a snapshot.
+    ///
+    /// Subscribers are only notified if it changed.
+    pub fn set_item_count(&self, item_count: Option<usize>) {
+        if self.inner.item_count().get_untracked() != item_count {
+            self.inner.item_count().set(item_count);
+        }
+    }
+
     #[inline]
     pub fn items(&self) -> Subfield<Store<CacheInner<T>>, CacheInner<T>, Vec<ItemState<T>>> {
         self.inner.items()
@@ -121,38 +498,73 @@ impl<T: Send + Sync + 'static> Cache<T> {
             .items()
             .write()
             .resize(len, ItemState::Placeholder);
+        self.loaded_at
+            .update_value(|loaded_at| loaded_at.truncate(len));
+
+        self.missing_trigger.notify();
+        self.failed_trigger.notify();
     }
 
     /// Grow the cache size to the specified length.
+    ///
+    /// Like all the write methods of the cache this doesn't track the cache so it's safe to call in effects.
     pub fn grow(&mut self, len: usize) {
-        if self.inner.items().read().len() < len {
+        if self.inner.items().read_untracked().len() < len {
             self.inner
                 .items()
                 .write()
                 .resize(len, ItemState::Placeholder);
+
+            self.missing_trigger.notify();
+        }
+    }
+
+    /// Marks the items in the specified range that are still loading as missing again.
+    ///
+    /// This is used when a load is aborted.
+    pub fn cancel_loading(&self, range: Range<usize>) {
+        let range = range.start..range.end.min(self.inner.items().read_untracked().len());
+        if range.start >= range.end {
+            return;
+        }
+
+        for row in self.inner.items().write()[range].iter_mut() {
+            if matches!(row, ItemState::Loading) {
+                *row = ItemState::Placeholder;
+            }
         }
+
+        self.missing_trigger.notify();
+        self.load_trigger.notify();
     }
 
     /// Marks the specified range of items as loading.
     pub fn write_loading(&self, range: Range<usize>) {
-        if range.end > self.inner.items().read().len() {
+        if range.end > self.inner.items().read_untracked().len() {
             self.inner
                 .items()
                 .write()
                 .resize(range.end, ItemState::Placeholder);
         }
 
-        for row in &mut self
-            .inner
-            .items()
-            .iter_unkeyed()
-            .skip(range.start)
-            .take(range.len())
-        {
-            if let Some(mut row) = row.try_write() {
-                *row = ItemState::Loading;
+        // iterating tracks the items which would make a calling effect rerun on its own write
+        untrack(|| {
+            for row in &mut self
+                .inner
+                .items()
+                .iter_unkeyed()
+                .skip(range.start)
+                .take(range.len())
+            {
+                if let Some(mut row) = row.try_write() {
+                    *row = ItemState::Loading;
+                }
             }
-        }
+        });
+
+        // Errored items inside the range are retried
+        self.failed_trigger.notify();
+        self.load_trigger.notify();
     }
 
     /// Called after the loader has finished loading items.
@@ -160,7 +572,7 @@ impl<T: Send + Sync + 'static> Cache<T> {
     /// This will update the respective range of items with the loaded data (or errors).
     pub fn write_loaded(
         &self,
-        loading_result: Result<LoadedItems<T>, String>,
+        loading_result: Result<LoadedItems<T>, ItemError>,
         requested_load_range: Range<usize>,
     ) {
         match loading_result {
@@ -174,33 +586,178 @@ impl<T: Send + Sync + 'static> Cache<T> {
                     writer.resize(range.end, ItemState::Placeholder);
                 }
 
-                for (self_row, loaded_row) in self
-                    .inner
-                    .items()
-                    .iter_unkeyed()
-                    .skip(range.start)
-                    .zip(items)
-                {
-                    if let Some(mut writer) = self_row.try_write() {
-                        *writer = ItemState::Loaded(Arc::new(loaded_row));
+                let items = items.into_iter().map(Arc::new).collect::<Vec<_>>();
+
+                untrack(|| {
+                    for (self_row, loaded_row) in self
+                        .inner
+                        .items()
+                        .iter_unkeyed()
+                        .skip(range.start)
+                        .zip(&items)
+                    {
+                        if let Some(mut writer) = self_row.try_write() {
+                            *writer = ItemState::Loaded(Arc::clone(loaded_row));
+                        }
                     }
-                }
+                });
+
+                let now = now_millis();
+                self.loaded_at.update_value(|loaded_at| {
+                    if loaded_at.len() < range.end {
+                        loaded_at.resize(range.end, f64::NAN);
+                    }
+                    loaded_at[range.clone()].fill(now);
+                });
+
+                let duplicates = self.find_duplicates(&range, &items);
+
+                self.load_trigger.notify();
+
+                self.emit(|| CacheEvent::Loaded { range, items });
+
+                self.invalidate_rows(duplicates);
             }
             Err(error) => {
                 let range = requested_load_range.start
                     ..requested_load_range
                         .end
-                        .min(self.inner.items().read().len());
+                        .min(self.inner.items().read_untracked().len());
                 if range.start >= range.end {
                     return;
                 }
 
-                for row in self.inner.items().iter_unkeyed() {
-                    if let Some(mut writer) = row.try_write() {
-                        *writer = ItemState::Error(error.clone());
+                untrack(|| {
+                    for row in self
+                        .inner
+                        .items()
+                        .iter_unkeyed()
+                        .skip(range.start)
+                        .take(range.len())
+                    {
+                        if let Some(mut writer) = row.try_write() {
+                            *writer = ItemState::Error(error.clone());
+                        }
+                    }
+                });
+
+                let now = now_millis();
+                self.loaded_at.update_value(|loaded_at| {
+                    if loaded_at.len() < range.end {
+                        loaded_at.resize(range.end, f64::NAN);
+                    }
+                    loaded_at[range.clone()].fill(now);
+                });
+
+                self.failed_trigger.notify();
+                self.load_trigger.notify();
+
+                self.emit(|| CacheEvent::LoadFailed { range, error });
+            }
+        }
+    }
+
+    /// Sets how the identity of an item is determined. See [`ItemKey`].
+    pub fn set_item_key(&self, item_key: Option<ItemKey<T>>) {
+        self.item_key.set_value(item_key);
+    }
+
+    /// Returns the indices outside of `range` that contain one of the just loaded `items` and
+    /// records the indices of their keys.
+    ///
+    /// These are stale copies of items that have shifted in the data source since they were loaded.
+    fn find_duplicates(&self, range: &Range<usize>, items: &[Arc<T>]) -> Vec<usize> {
+        self.item_key.with_value(|item_key| {
+            let Some(item_key) = item_key else {
+                return vec![];
+            };
+
+            let rows = self.inner.items().read_untracked();
+
+            self.key_indices
+                .try_update_value(|key_indices| {
+                    let mut duplicates = vec![];
+
+                    for (index, item) in range.clone().zip(items) {
+                        let key = item_key.key(item);
+
+                        if let Some(previous) = key_indices.insert(key, index)
+                            && !range.contains(&previous)
+                            && matches!(
+                                rows.get(previous),
+                                Some(ItemState::Loaded(item)) if item_key.key(item) == key
+                            )
+                        {
+                            duplicates.push(previous);
+                        }
                     }
+
+                    duplicates
+                })
+                .unwrap_or_default()
+        })
+    }
+
+    /// Records the index of the key of `item` if an [`ItemKey`] is set.
+    fn record_key(&self, index: usize, item: &T) {
+        self.item_key.with_value(|item_key| {
+            if let Some(item_key) = item_key {
+                let key = item_key.key(item);
+                self.key_indices.update_value(|key_indices| {
+                    key_indices.insert(key, index);
+                });
+            }
+        });
+    }
+
+    /// The key of the loaded item at `index` if an [`ItemKey`] is set. This is reactive for the item.
+    pub fn key_at(&self, index: usize) -> Option<u64> {
+        let rows = self.inner.items();
+        if index >= rows.read_untracked().len() {
+            return None;
+        }
+
+        let row = rows.at_unkeyed(index).read();
+        let ItemState::Loaded(item) = &*row else {
+            return None;
+        };
+
+        self.item_key
+            .with_value(|item_key| item_key.as_ref().map(|item_key| item_key.key(item)))
+    }
+
+    /// The index of the loaded item with the given key if an [`ItemKey`] is set.
+    ///
+    /// This is a lookup instead of a search through all items. It's reactive for items that
+    /// shift, e.g. when an item before it is inserted or removed.
+    pub fn index_of_key(&self, key: u64) -> Option<usize> {
+        self.missing_trigger.track();
+
+        let index = self
+            .key_indices
+            .with_value(|key_indices| key_indices.get(&key).copied())?;
+
+        (self.key_at(index) == Some(key)).then_some(index)
+    }
+
+    /// Marks the given rows as missing.
+    fn invalidate_rows(&self, indices: Vec<usize>) {
+        if indices.is_empty() {
+            return;
+        }
+
+        untrack(|| {
+            for &index in &indices {
+                if let Some(mut writer) = self.inner.items().at_unkeyed(index).try_write() {
+                    *writer = ItemState::Placeholder;
                 }
             }
+        });
+
+        self.missing_trigger.notify();
+
+        for range in contiguous_ranges(indices) {
+            self.emit(|| CacheEvent::Invalidated { range });
         }
     }
 
@@ -252,118 +809,203 @@ impl<T: Send + Sync + 'static> Cache<T> {
         )
     }
 
-    #[inline]
-    /// Sets all items in the cache to the placeholder state.
-    pub fn clear(&self) {
-        self.inner.items().write().fill(ItemState::Placeholder);
-        self.inner.item_count().set(None);
-    }
-
-    /// Updates an item in the cache.
-    ///
-    /// This doesn't trigger a reload.
+    /// Returns the ranges of items that are missing from the cache inside the given range
+    /// without the items that are currently being loaded.
     ///
-    /// The user is responsible for updating the data source accordingly.
-    pub fn update_item(&self, index: usize, new: T) {
-        self.with_reactive_loading_paused(|| {
-            *self.inner.items().at_unkeyed(index).write() = ItemState::Loaded(Arc::new(new));
-        });
+    /// The rows that are in the loading state track the loads in flight. Splitting around them
+    /// makes sure that overlapping requests (for example from several windows sharing this cache)
+    /// don't load the same items twice but wait for the running load instead.
+    /// Within every split the ranges are computed like [`Cache::missing_range`].
+    pub fn missing_ranges(&self, range_to_load: Range<usize>) -> Vec<Range<usize>> {
+        let mut segments = Vec::new();
+        let mut start = range_to_load.start;
+
+        {
+            let items = self.inner.items().read();
+            let existing_range_end = items.len().min(range_to_load.end);
+
+            for index in range_to_load.start..existing_range_end {
+                if matches!(items[index], ItemState::Loading) {
+                    if start < index {
+                        segments.push(start..index);
+                    }
+                    start = index + 1;
+                }
+            }
+        }
+
+        if start < range_to_load.end {
+            segments.push(start..range_to_load.end);
+        }
+
+        segments
+            .into_iter()
+            .filter_map(|segment| self.missing_range(segment))
+            .collect()
     }
 
-    /// Removes the item at the given index from the cache and updates the item count.
-    ///
-    /// This doesn't trigger a reload.
+    /// Turns loaded items back into placeholders until at most `max_cached_items` are loaded.
     ///
-    /// The user is responsible for updating the data source accordingly.
-    pub fn remove_item(&self, index: usize) {
-        self.with_reactive_loading_paused(|| {
-            self.inner.items().write().remove(index);
+    /// The items farthest away from `keep_range` are evicted first. Items inside `keep_range` are
+    /// never evicted. Evicted items are loaded again once they're inside the range to load.
+    pub fn evict_far_from(&self, keep_range: Range<usize>, max_cached_items: usize) {
+        let loaded_count = self
+            .inner
+            .items()
+            .read_untracked()
+            .iter()
+            .filter(|row| matches!(row, ItemState::Loaded(_)))
+            .count();
 
-            if let Some(len) = self.inner.item_count().get_untracked() {
-                self.inner.item_count().set(Some(len - 1));
+        let Some(mut excess) = loaded_count.checked_sub(max_cached_items) else {
+            return;
+        };
+        if excess == 0 {
+            return;
+        }
+
+        let mut evicted = Vec::new();
+
+        {
+            let items_field = self.inner.items();
+            let mut items = items_field.write();
+
+            let mut low = 0;
+            let mut high = items.len();
+
+            // Evict from whichever end is farther away from the range to keep.
+            while excess > 0 && (low < keep_range.start || high > keep_range.end) {
+                let low_distance = keep_range.start.saturating_sub(low);
+                let high_distance = high.saturating_sub(keep_range.end);
+
+                let index = if low < keep_range.start && low_distance >= high_distance {
+                    low += 1;
+                    low - 1
+                } else {
+                    high -= 1;
+                    high
+                };
+
+                if matches!(items[index], ItemState::Loaded(_)) {
+                    items[index] = ItemState::Placeholder;
+                    evicted.push(index);
+                    excess -= 1;
+                }
             }
-        });
+        }
+
+        self.load_trigger.notify();
+
+        for range in contiguous_ranges(evicted) {
+            self.emit(|| CacheEvent::Invalidated { range });
+        }
     }
 
-    /// Inserts an item at the given index in the cache and updates the item count.
-    ///
-    /// This doesn't trigger a reload.
+    /// Copies the loaded items and the item count of `source` into this cache so they don't have
+    /// to be loaded again. Items that are already loaded in this cache are kept and only the copied
+    /// rows emit a [`CacheEvent::Loaded`].
     ///
-    /// The user is responsible for updating the data source accordingly.
-    pub fn insert_item(&self, index: usize, new: T) {
-        self.with_reactive_loading_paused(|| {
-            self.inner
-                .items()
-                .write()
-                .insert(index, ItemState::Loaded(Arc::new(new)));
+    /// Use this to hand over the items of one window to a new window of the same data and query,
+    /// e.g. when opening a full-screen view of a list. Both caches stay independent afterwards.
+    /// To keep them in sync share one cache with [`WindowLink`](crate::WindowLink) instead.
+    pub fn copy_loaded_from(&self, source: Cache<T>) {
+        let runs = source.inner.items().with_untracked(|items| {
+            let mut runs = Vec::<(usize, Vec<Arc<T>>)>::new();
 
-            if let Some(len) = self.inner.item_count().get_untracked() {
-                self.inner.item_count().set(Some(len + 1));
+            for (index, row) in items.iter().enumerate() {
+                let ItemState::Loaded(item) = row else {
+                    continue;
+                };
+
+                match runs.last_mut() {
+                    Some((start, run)) if *start + run.len() == index => run.push(Arc::clone(item)),
+                    _ => runs.push((index, vec![Arc::clone(item)])),
+                }
             }
+
+            runs
         });
-    }
-}
 
-impl<T: Sync + Send> Index<Range<usize>> for CacheInner<T> {
-    type Output = [ItemState<T>];
+        if self.inner.item_count().get_untracked().is_none()
+            && let Some(item_count) = source.inner.item_count().get_untracked()
+        {
+            self.set_item_count(Some(item_count));
+        }
 
-    #[inline]
-    fn index(&self, index: Range<usize>) -> &Self::Output {
-        &self.items[index]
-    }
-}
+        let Some(end) = runs.last().map(|(start, run)| start + run.len()) else {
+            return;
+        };
 
-impl<T: Send + Sync> Index<usize> for CacheInner<T> {
-    type Output = ItemState<T>;
+        let source_loaded_at = source.loaded_at.get_value();
 
-    #[inline]
-    fn index(&self, index: usize) -> &Self::Output {
-        &self.items[index]
-    }
-}
+        let written = {
+            let items_field = self.inner.items();
+            let mut items = items_field.write();
 
-/// This can be used to get write access to the cache.
-pub struct CacheController<T>
-where
-    T: Send + Sync + 'static,
-{
-    cache: StoredValue<Option<Cache<T>>>,
-}
+            if items.len() < end {
+                items.resize(end, ItemState::Placeholder);
+            }
 
-impl<T> Clone for CacheController<T>
-where
-    T: Send + Sync + 'static,
-{
-    fn clone(&self) -> Self {
-        *self
-    }
-}
+            let mut written = Vec::<(usize, Arc<T>)>::new();
 
-impl<T> Copy for CacheController<T> where T: Send + Sync + 'static {}
+            for (start, run) in &runs {
+                for (index, item) in (*start..).zip(run) {
+                    if !matches!(items[index], ItemState::Loaded(_)) {
+                        items[index] = ItemState::Loaded(Arc::clone(item));
+                        written.push((index, Arc::clone(item)));
+                    }
+                }
+            }
 
-impl<T> Default for CacheController<T>
-where
-    T: Send + Sync + 'static,
-{
-    fn default() -> Self {
-        Self {
-            cache: StoredValue::new(None),
+            written
+        };
+
+        if written.is_empty() {
+            return;
         }
-    }
-}
 
-impl<T> CacheController<T>
-where
-    T: Send + Sync + 'static,
-{
-    /// You know what this does.
-    pub fn new() -> Self {
-        Default::default()
+        self.loaded_at.update_value(|loaded_at| {
+            if loaded_at.len() < end {
+                loaded_at.resize(end, f64::NAN);
+            }
+            for (index, _) in &written {
+                loaded_at[*index] = source_loaded_at.get(*index).copied().unwrap_or(f64::NAN);
+            }
+        });
+
+        for (index, item) in &written {
+            self.record_key(*index, item);
+        }
+
+        self.load_trigger.notify();
+
+        let mut written_runs = Vec::<(usize, Vec<Arc<T>>)>::new();
+        for (index, item) in written {
+            match written_runs.last_mut() {
+                Some((start, run)) if *start + run.len() == index => run.push(item),
+                _ => written_runs.push((index, vec![item])),
+            }
+        }
+
+        for (start, items) in written_runs {
+            self.emit(|| CacheEvent::Loaded {
+                range: start..start + items.len(),
+                items,
+            });
+        }
     }
 
-    /// This is called in the components to init the connection to the cache
-    pub fn init_with_item_window(&self, window: ItemWindow<T>) {
-        self.cache.set_value(Some(window.cache));
+    #[inline]
+    /// Sets all items in the cache to the placeholder state.
+    pub fn clear(&self) {
+        self.inner.items().write().fill(ItemState::Placeholder);
+        self.inner.item_count().set(None);
+        self.loaded_at.update_value(Vec::clear);
+        self.shift_indices(|_| None);
+        self.missing_trigger.notify();
+        self.failed_trigger.notify();
+
+        self.emit(|| CacheEvent::Cleared);
     }
 
     /// Updates an item in the cache.
@@ -372,13 +1014,184 @@ where
     ///
     /// The user is responsible for updating the data source accordingly.
     pub fn update_item(&self, index: usize, new: T) {
-        if let Some(cache) = self.cache.get_value() {
-            cache.update_item(index, new);
+        if !self.is_index_in_cache(index, "Update item") {
+            return;
+        }
+
+        let item = Arc::new(new);
+        self.record_key(index, &item);
+
+        self.with_reactive_loading_paused(|| {
+            *self.inner.items().at_unkeyed(index).write() = ItemState::Loaded(Arc::clone(&item));
+        });
+
+        self.loaded_at.update_value(|loaded_at| {
+            if let Some(loaded_at) = loaded_at.get_mut(index) {
+                *loaded_at = now_millis();
+            }
+        });
+        self.failed_trigger.notify();
+
+        self.emit(|| CacheEvent::Updated { index, item });
+    }
+
+    /// Updates an item in the cache right away and then awaits `commit` which should update the
+    /// data source accordingly.
+    ///
+    /// If `commit` fails, the previous state of the item is restored and the error is returned.
+    /// It isn't restored if the item has been changed in the meantime, e.g. by a reload.
+    pub async fn optimistic_update<E>(
+        &self,
+        index: usize,
+        new: T,
+        commit: impl Future<Output = Result<(), E>>,
+    ) -> Result<(), E> {
+        let previous = self.inner.items().read_untracked().get(index).cloned();
+
+        self.update_item(index, new);
+
+        let optimistic = match self.inner.items().read_untracked().get(index) {
+            Some(ItemState::Loaded(item)) => Some(Arc::clone(item)),
+            _ => None,
+        };
+
+        let result = commit.await;
+
+        if result.is_err()
+            && let (Some(previous), Some(optimistic)) = (previous, optimistic)
+        {
+            self.roll_back_item(index, previous, &optimistic);
+        }
+
+        result
+    }
+
+    /// Restores the `previous` state of the item at `index` if it's still `optimistic`.
+    fn roll_back_item(&self, index: usize, previous: ItemState<T>, optimistic: &Arc<T>) {
+        let is_unchanged = self
+            .inner
+            .items()
+            .try_read_untracked()
+            .is_some_and(|items| match items.get(index) {
+                Some(ItemState::Loaded(item)) => Arc::ptr_eq(item, optimistic),
+                _ => false,
+            });
+
+        if !is_unchanged {
+            return;
+        }
+
+        let restored = match &previous {
+            ItemState::Loaded(item) => Some(Arc::clone(item)),
+            _ => None,
+        };
+
+        self.with_reactive_loading_paused(|| {
+            *self.inner.items().at_unkeyed(index).write() = previous;
+        });
+
+        self.missing_trigger.notify();
+        self.failed_trigger.notify();
+
+        if let Some(item) = restored {
+            self.emit(|| CacheEvent::Updated { index, item });
         } else {
-            leptos::logging::error!(
-                "Update item is called on a cache controller before the controller has been initialized."
-            )
+            self.emit(|| CacheEvent::Invalidated {
+                range: index..index + 1,
+            });
+        }
+    }
+
+    /// Hides the item at the given index without removing it, e.g. while it's being archived.
+    ///
+    /// The indices and the item count stay the same so the other items don't move. Windows skip
+    /// hidden items when rendering. See [`ItemWindow::displayed_indices`].
+    pub fn hide_item(&self, index: usize) {
+        self.hidden.update(|hidden| {
+            hidden.insert(index);
+        });
+    }
+
+    /// Displays an item again that has been hidden with [`Cache::hide_item`].
+    pub fn unhide_item(&self, index: usize) {
+        if self.hidden.read_untracked().contains(&index) {
+            self.hidden.update(|hidden| {
+                hidden.remove(&index);
+            });
+        }
+    }
+
+    /// Whether the item at the given index is hidden. This is reactive.
+    pub fn is_hidden(&self, index: usize) -> bool {
+        self.hidden.read().contains(&index)
+    }
+
+    /// Hides an item right away and then awaits `commit` which should remove it from the data
+    /// source.
+    ///
+    /// If `commit` succeeds, the item is removed with [`Cache::remove_item`]. Otherwise it's
+    /// displayed again and the error is returned. If other items are inserted or removed in the
+    /// meantime, the item is followed to its new index. If it's gone, e.g. because the cache has
+    /// been cleared, nothing is changed.
+    pub async fn optimistic_remove<E>(
+        &self,
+        index: usize,
+        commit: impl Future<Output = Result<(), E>>,
+    ) -> Result<(), E> {
+        self.hide_item(index);
+        let token = self
+            .pending_removals
+            .try_update_value(|pending| pending.insert(index));
+
+        let result = commit.await;
+
+        let index = token.and_then(|token| {
+            self.pending_removals
+                .try_update_value(|pending| pending.indices.remove(&token))
+                .flatten()
+        });
+
+        if let Some(index) = index {
+            if result.is_ok() {
+                self.remove_item(index);
+            } else {
+                self.unhide_item(index);
+            }
+        }
+
+        result
+    }
+
+    /// Moves the hidden indices, the indices of the pending removals and the indices of the item
+    /// keys to `shift(index)` or forgets them if it returns `None`.
+    fn shift_indices(&self, shift: impl Fn(usize) -> Option<usize>) {
+        self.pending_removals.update_value(|pending| {
+            pending.indices.retain(|_, index| match shift(*index) {
+                Some(shifted) => {
+                    *index = shifted;
+                    true
+                }
+                None => false,
+            });
+        });
+
+        self.key_indices.update_value(|key_indices| {
+            key_indices.retain(|_, index| match shift(*index) {
+                Some(shifted) => {
+                    *index = shifted;
+                    true
+                }
+                None => false,
+            });
+        });
+
+        if self.hidden.read_untracked().is_empty() {
+            return;
         }
+
+        self.hidden.update(|hidden| {
+            *hidden = hidden.drain().filter_map(&shift).collect();
+        });
     }
 
     /// Removes the item at the given index from the cache and updates the item count.
@@ -387,13 +1200,34 @@ where
     ///
     /// The user is responsible for updating the data source accordingly.
     pub fn remove_item(&self, index: usize) {
-        if let Some(cache) = self.cache.get_value() {
-            cache.remove_item(index);
-        } else {
-            leptos::logging::error!(
-                "Remove item is called on a cache controller before the controller has been initialized."
-            )
+        if !self.is_index_in_cache(index, "Remove item") {
+            return;
         }
+
+        self.shift_indices(|other| match other.cmp(&index) {
+            std::cmp::Ordering::Less => Some(other),
+            std::cmp::Ordering::Equal => None,
+            std::cmp::Ordering::Greater => Some(other - 1),
+        });
+
+        self.with_reactive_loading_paused(|| {
+            self.inner.items().write().remove(index);
+
+            if let Some(len) = self.inner.item_count().get_untracked() {
+                self.inner.item_count().set(Some(len - 1));
+            }
+        });
+
+        self.loaded_at.update_value(|loaded_at| {
+            if index < loaded_at.len() {
+                loaded_at.remove(index);
+            }
+        });
+
+        self.missing_trigger.notify();
+        self.failed_trigger.notify();
+
+        self.emit(|| CacheEvent::Removed { index });
     }
 
     /// Inserts an item at the given index in the cache and updates the item count.
@@ -402,43 +1236,1810 @@ where
     ///
     /// The user is responsible for updating the data source accordingly.
     pub fn insert_item(&self, index: usize, new: T) {
-        if let Some(cache) = self.cache.get_value() {
-            cache.insert_item(index, new);
-        } else {
-            leptos::logging::error!(
-                "Insert item is called on a cache controller before the controller has been initialized."
-            )
+        let len = self.inner.items().read_untracked().len();
+        if index > len {
+            misuse!(
+                "Insert item is called with the index {index} past the end of the {len} cached items."
+            );
+            return;
         }
+
+        let item = Arc::new(new);
+
+        self.shift_indices(|other| Some(if other >= index { other + 1 } else { other }));
+        self.record_key(index, &item);
+
+        self.with_reactive_loading_paused(|| {
+            self.inner
+                .items()
+                .write()
+                .insert(index, ItemState::Loaded(Arc::clone(&item)));
+
+            if let Some(len) = self.inner.item_count().get_untracked() {
+                self.inner.item_count().set(Some(len + 1));
+            }
+        });
+
+        self.loaded_at.update_value(|loaded_at| {
+            if index <= loaded_at.len() {
+                loaded_at.insert(index, now_millis());
+            }
+        });
+
+        self.missing_trigger.notify();
+        self.failed_trigger.notify();
+
+        self.emit(|| CacheEvent::Inserted { index, item });
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Moves the item at `from` to the index `to`. The items in between shift by one.
+    ///
+    /// This doesn't trigger a reload.
+    ///
+    /// The user is responsible for updating the data source accordingly.
+    pub fn move_item(&self, from: usize, to: usize) {
+        self.batch(|batch| {
+            batch.move_item(from, to);
+        });
+    }
 
-    #[test]
-    fn test_missing_range() {
-        let cache = Cache::<i32>::new();
+    /// Swaps the items at the indices `a` and `b`.
+    ///
+    /// This doesn't trigger a reload.
+    ///
+    /// The user is responsible for updating the data source accordingly.
+    pub fn swap_items(&self, a: usize, b: usize) {
+        self.batch(|batch| {
+            batch.swap_items(a, b);
+        });
+    }
 
-        assert_eq!(cache.missing_range(0..10), Some(0..10));
-        assert_eq!(cache.missing_range(5..10), Some(5..10));
+    /// Applies several changes at once. The items are written only once so the UI is updated a
+    /// single time and never shows the changes partially applied.
+    ///
+    /// The changes are applied in the order they're added to the [`CacheBatch`]. Every index
+    /// refers to the items after the changes before it.
+    ///
+    /// This doesn't trigger a reload.
+    ///
+    /// The user is responsible for updating the data source accordingly.
+    pub fn batch(&self, f: impl FnOnce(&mut CacheBatch<T>)) {
+        let mut batch = CacheBatch {
+            changes: Vec::new(),
+        };
+        f(&mut batch);
 
-        cache.write_loaded(
-            Ok(LoadedItems {
-                items: (0..5).collect::<Vec<_>>(),
-                range: 0..5,
-            }),
-            0..5,
-        );
+        if batch.changes.is_empty() {
+            return;
+        }
 
-        assert_eq!(cache.missing_range(0..10), Some(5..10));
-        assert_eq!(cache.missing_range(5..10), Some(5..10));
-        assert_eq!(cache.missing_range(5..20), Some(5..20));
+        let now = now_millis();
+        let mut item_count_delta = 0_isize;
+        let mut events = Vec::with_capacity(batch.changes.len());
 
-        cache.write_loading(5..9);
+        self.with_reactive_loading_paused(|| {
+            let items_field = self.inner.items();
+            let mut items = items_field.write();
 
-        assert_eq!(cache.missing_range(0..10), Some(9..10));
-        assert_eq!(cache.missing_range(5..10), Some(9..10));
-        assert_eq!(cache.missing_range(5..20), Some(9..20));
+            self.loaded_at.update_value(|loaded_at| {
+                for change in batch.changes {
+                    if !change.is_in_bounds(items.len()) {
+                        misuse!(
+                            "{change:?} in a batch refers to an index outside of the {} cached items.",
+                            items.len()
+                        );
+                        continue;
+                    }
+
+                    match change {
+                        BatchChange::Update { index, item } => {
+                            items[index] = ItemState::Loaded(Arc::clone(&item));
+                            if let Some(loaded_at) = loaded_at.get_mut(index) {
+                                *loaded_at = now;
+                            }
+                            self.record_key(index, &item);
+
+                            events.push(CacheEvent::Updated { index, item });
+                        }
+                        BatchChange::Insert { index, item } => {
+                            items.insert(index, ItemState::Loaded(Arc::clone(&item)));
+                            if index <= loaded_at.len() {
+                                loaded_at.insert(index, now);
+                            }
+                            self.shift_indices(|other| {
+                                Some(if other >= index { other + 1 } else { other })
+                            });
+                            self.record_key(index, &item);
+                            item_count_delta += 1;
+
+                            events.push(CacheEvent::Inserted { index, item });
+                        }
+                        BatchChange::Remove { index } => {
+                            items.remove(index);
+                            if index < loaded_at.len() {
+                                loaded_at.remove(index);
+                            }
+                            self.shift_indices(|other| match other.cmp(&index) {
+                                std::cmp::Ordering::Less => Some(other),
+                                std::cmp::Ordering::Equal => None,
+                                std::cmp::Ordering::Greater => Some(other - 1),
+                            });
+                            item_count_delta -= 1;
+
+                            events.push(CacheEvent::Removed { index });
+                        }
+                        BatchChange::Move { from, to } => {
+                            let item = items.remove(from);
+                            items.insert(to, item);
+                            if loaded_at.len() < items.len() {
+                                loaded_at.resize(items.len(), f64::NAN);
+                            }
+                            let item_loaded_at = loaded_at.remove(from);
+                            loaded_at.insert(to, item_loaded_at);
+                            self.shift_indices(|other| Some(moved_index(other, from, to)));
+
+                            events.push(CacheEvent::Moved { from, to });
+                        }
+                        BatchChange::Swap { a, b } => {
+                            items.swap(a, b);
+                            if loaded_at.len() < items.len() {
+                                loaded_at.resize(items.len(), f64::NAN);
+                            }
+                            loaded_at.swap(a, b);
+                            self.shift_indices(|other| {
+                                Some(match other {
+                                    other if other == a => b,
+                                    other if other == b => a,
+                                    other => other,
+                                })
+                            });
+
+                            events.push(CacheEvent::Swapped { a, b });
+                        }
+                    }
+                }
+            });
+
+            drop(items);
+
+            if item_count_delta != 0 {
+                self.shift_item_count(item_count_delta);
+            }
+        });
+
+        self.missing_trigger.notify();
+        self.failed_trigger.notify();
+
+        for event in events {
+            self.emit(|| event);
+        }
+    }
+
+    /// Replaces every loaded item that matches `predicate` with the result of `update` and returns
+    /// how many items were updated. Useful if you only know the id of a changed item but not its
+    /// index. Items that aren't loaded are left alone.
+    ///
+    /// This doesn't trigger a reload.
+    ///
+    /// The user is responsible for updating the data source accordingly.
+    pub fn update_where(&self, predicate: impl Fn(&T) -> bool, update: impl Fn(&T) -> T) -> usize {
+        let updates = self
+            .loaded_matching(predicate)
+            .into_iter()
+            .map(|(index, item)| (index, update(&item)))
+            .collect::<Vec<_>>();
+        let count = updates.len();
+
+        self.batch(|batch| {
+            for (index, new) in updates {
+                batch.update_item(index, new);
+            }
+        });
+
+        count
+    }
+
+    /// Removes every loaded item that matches `predicate`, updates the item count and returns
+    /// how many items were removed. Items that aren't loaded are left alone.
+    ///
+    /// This doesn't trigger a reload.
+    ///
+    /// The user is responsible for updating the data source accordingly.
+    pub fn remove_where(&self, predicate: impl Fn(&T) -> bool) -> usize {
+        let indices = self.loaded_matching(predicate);
+        let count = indices.len();
+
+        self.batch(|batch| {
+            // Back to front so the indices of the items that are still to be removed don't shift
+            for (index, _) in indices.into_iter().rev() {
+                batch.remove_item(index);
+            }
+        });
+
+        count
+    }
+
+    /// The indices and the items of the loaded items that match `predicate`.
+    fn loaded_matching(&self, predicate: impl Fn(&T) -> bool) -> Vec<(usize, Arc<T>)> {
+        self.inner
+            .items()
+            .read_untracked()
+            .iter()
+            .enumerate()
+            .filter_map(|(index, row)| match row {
+                ItemState::Loaded(item) if predicate(item) => Some((index, Arc::clone(item))),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Applies a change pushed by a live data source. See [`LiveLoader`](crate::LiveLoader).
+    ///
+    /// Changes of items that aren't cached yet only adjust the item count.
+    pub fn apply_live_update(&self, update: LiveUpdate<T>) {
+        let len = self.inner.items().read_untracked().len();
+
+        match update {
+            LiveUpdate::Insert { index, item } if index <= len => self.insert_item(index, item),
+            LiveUpdate::Update { index, item } if index < len => self.update_item(index, item),
+            LiveUpdate::Delete { index } if index < len => self.remove_item(index),
+            LiveUpdate::Insert { .. } => self.shift_item_count(1),
+            LiveUpdate::Update { .. } => {}
+            LiveUpdate::Delete { .. } => self.shift_item_count(-1),
+        }
+    }
+
+    /// Whether `index` refers to an item in the cache. Otherwise a misuse is reported.
+    fn is_index_in_cache(&self, index: usize, method: &str) -> bool {
+        let len = self.inner.items().read_untracked().len();
+
+        if index >= len {
+            misuse!("{method} is called with the index {index} outside of the {len} cached items.");
+            return false;
+        }
+
+        true
+    }
+
+    fn shift_item_count(&self, delta: isize) {
+        if let Some(count) = self.inner.item_count().get_untracked() {
+            self.inner
+                .item_count()
+                .set(Some(count.saturating_add_signed(delta)));
+        }
+    }
+}
+
+/// A change of the cache. See [`Cache::on_change`].
+pub enum CacheEvent<T> {
+    /// Items have been loaded by the loader.
+    Loaded {
+        range: Range<usize>,
+        items: Vec<Arc<T>>,
+    },
+    /// The loader failed to load the given range.
+    LoadFailed {
+        range: Range<usize>,
+        error: ItemError,
+    },
+    /// An item has been updated.
+    Updated { index: usize, item: Arc<T> },
+    /// An item has been inserted.
+    Inserted { index: usize, item: Arc<T> },
+    /// An item has been removed.
+    Removed { index: usize },
+    /// An item has been moved from one index to another. The items in between shifted by one.
+    Moved { from: usize, to: usize },
+    /// Two items have been swapped.
+    Swapped { a: usize, b: usize },
+    /// The items in the range have been marked as missing.
+    Invalidated { range: Range<usize> },
+    /// The whole cache has been cleared.
+    Cleared,
+}
+
+impl<T> std::fmt::Debug for CacheEvent<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CacheEvent::Loaded { range, .. } => write!(f, "Loaded({range:?})"),
+            CacheEvent::LoadFailed { range, error } => write!(f, "LoadFailed({range:?}, {error})"),
+            CacheEvent::Updated { index, .. } => write!(f, "Updated({index})"),
+            CacheEvent::Inserted { index, .. } => write!(f, "Inserted({index})"),
+            CacheEvent::Removed { index } => write!(f, "Removed({index})"),
+            CacheEvent::Moved { from, to } => write!(f, "Moved({from}, {to})"),
+            CacheEvent::Swapped { a, b } => write!(f, "Swapped({a}, {b})"),
+            CacheEvent::Invalidated { range } => write!(f, "Invalidated({range:?})"),
+            CacheEvent::Cleared => write!(f, "Cleared"),
+        }
+    }
+}
+
+/// Collects the changes for [`Cache::batch`].
+pub struct CacheBatch<T> {
+    changes: Vec<BatchChange<T>>,
+}
+
+enum BatchChange<T> {
+    Update { index: usize, item: Arc<T> },
+    Insert { index: usize, item: Arc<T> },
+    Remove { index: usize },
+    Move { from: usize, to: usize },
+    Swap { a: usize, b: usize },
+}
+
+impl<T> BatchChange<T> {
+    /// Whether the indices of this change are valid for a cache with `len` items.
+    fn is_in_bounds(&self, len: usize) -> bool {
+        match self {
+            BatchChange::Update { index, .. } | BatchChange::Remove { index } => *index < len,
+            BatchChange::Insert { index, .. } => *index <= len,
+            BatchChange::Move { from, to } => *from < len && *to < len,
+            BatchChange::Swap { a, b } => *a < len && *b < len,
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for BatchChange<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BatchChange::Update { index, .. } => write!(f, "Update({index})"),
+            BatchChange::Insert { index, .. } => write!(f, "Insert({index})"),
+            BatchChange::Remove { index } => write!(f, "Remove({index})"),
+            BatchChange::Move { from, to } => write!(f, "Move({from}, {to})"),
+            BatchChange::Swap { a, b } => write!(f, "Swap({a}, {b})"),
+        }
+    }
+}
+
+impl<T> CacheBatch<T> {
+    /// Updates an item. See [`Cache::update_item`].
+    pub fn update_item(&mut self, index: usize, new: T) -> &mut Self {
+        self.changes.push(BatchChange::Update {
+            index,
+            item: Arc::new(new),
+        });
+        self
+    }
+
+    /// Inserts an item and increases the item count. See [`Cache::insert_item`].
+    pub fn insert_item(&mut self, index: usize, new: T) -> &mut Self {
+        self.changes.push(BatchChange::Insert {
+            index,
+            item: Arc::new(new),
+        });
+        self
+    }
+
+    /// Removes an item and decreases the item count. See [`Cache::remove_item`].
+    pub fn remove_item(&mut self, index: usize) -> &mut Self {
+        self.changes.push(BatchChange::Remove { index });
+        self
+    }
+
+    /// Moves an item. See [`Cache::move_item`].
+    pub fn move_item(&mut self, from: usize, to: usize) -> &mut Self {
+        self.changes.push(BatchChange::Move { from, to });
+        self
+    }
+
+    /// Swaps two items. See [`Cache::swap_items`].
+    pub fn swap_items(&mut self, a: usize, b: usize) -> &mut Self {
+        self.changes.push(BatchChange::Swap { a, b });
+        self
+    }
+}
+
+/// Where the item at `index` ends up when the item at `from` is moved to `to`.
+fn moved_index(index: usize, from: usize, to: usize) -> usize {
+    if index == from {
+        to
+    } else if from < index && index <= to {
+        index - 1
+    } else if to <= index && index < from {
+        index + 1
+    } else {
+        index
+    }
+}
+
+/// Merges the given indices into the fewest ranges that cover exactly them.
+fn contiguous_ranges(mut indices: Vec<usize>) -> Vec<Range<usize>> {
+    indices.sort_unstable();
+    indices.dedup();
+
+    let mut ranges = Vec::<Range<usize>>::new();
+
+    for index in indices {
+        match ranges.last_mut() {
+            Some(range) if range.end == index => range.end = index + 1,
+            _ => ranges.push(index..index + 1),
+        }
+    }
+
+    ranges
+}
+
+type CacheListener<T> = Arc<dyn Fn(&CacheEvent<T>) + Send + Sync>;
+
+struct CacheListeners<T> {
+    next_id: usize,
+    listeners: Vec<(usize, CacheListener<T>)>,
+}
+
+impl<T> Default for CacheListeners<T> {
+    fn default() -> Self {
+        Self {
+            next_id: 0,
+            listeners: Vec::new(),
+        }
+    }
+}
+
+/// The items that are being removed with [`Cache::optimistic_remove`] by a token per call.
+#[derive(Default)]
+struct PendingRemovals {
+    next_token: usize,
+    indices: HashMap<usize, usize>,
+}
+
+impl PendingRemovals {
+    /// Tracks the item at `index` and returns the token to look up its current index.
+    fn insert(&mut self, index: usize) -> usize {
+        let token = self.next_token;
+        self.next_token += 1;
+        self.indices.insert(token, index);
+        token
+    }
+}
+
+impl<T: Sync + Send> Index<Range<usize>> for CacheInner<T> {
+    type Output = [ItemState<T>];
+
+    #[inline]
+    fn index(&self, index: Range<usize>) -> &Self::Output {
+        &self.items[index]
+    }
+}
+
+impl<T: Send + Sync> Index<usize> for CacheInner<T> {
+    type Output = ItemState<T>;
+
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.items[index]
+    }
+}
+
+/// This can be used to get write access to the cache.
+pub struct CacheController<T>
+where
+    T: Send + Sync + 'static,
+{
+    cache: StoredValue<Option<Cache<T>>>,
+}
+
+impl<T> Clone for CacheController<T>
+where
+    T: Send + Sync + 'static,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for CacheController<T> where T: Send + Sync + 'static {}
+
+impl<T> Default for CacheController<T>
+where
+    T: Send + Sync + 'static,
+{
+    fn default() -> Self {
+        Self {
+            cache: StoredValue::new(None),
+        }
+    }
+}
+
+impl<T> CacheController<T>
+where
+    T: Send + Sync + 'static,
+{
+    /// You know what this does.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// This is called in the components to init the connection to the cache
+    pub fn init_with_item_window(&self, window: ItemWindow<T>) {
+        self.cache.set_value(Some(window.cache));
+    }
+
+    /// Updates an item in the cache.
+    ///
+    /// This doesn't trigger a reload.
+    ///
+    /// The user is responsible for updating the data source accordingly.
+    pub fn update_item(&self, index: usize, new: T) {
+        if let Some(cache) = self.cache.get_value() {
+            cache.update_item(index, new);
+        } else {
+            misuse!(
+                "Update item is called on a cache controller before the controller has been initialized."
+            )
+        }
+    }
+
+    /// Updates an item in the cache right away and then awaits `commit` which should update the
+    /// data source accordingly.
+    ///
+    /// If `commit` fails, the previous item is restored and the error is returned so it can be
+    /// displayed to the user.
+    ///
+    /// ```
+    /// # use leptos::{prelude::*, task::spawn_local};
+    /// # use leptos_windowing::cache::CacheController;
+    /// #
+    /// # #[derive(Clone)]
+    /// # pub struct Book { title: String }
+    /// # async fn save_book(book: Book) -> Result<(), String> { Ok(()) }
+    /// #
+    /// # fn rename(cache_controller: CacheController<Book>, index: usize) {
+    /// spawn_local(async move {
+    ///     let renamed = Book { title: "The Hobbit".to_string() };
+    ///
+    ///     if let Err(error) = cache_controller
+    ///         .optimistic_update(index, renamed.clone(), save_book(renamed))
+    ///         .await
+    ///     {
+    ///         leptos::logging::error!("Failed to rename the book: {error}");
+    ///     }
+    /// });
+    /// # }
+    /// ```
+    pub async fn optimistic_update<E>(
+        &self,
+        index: usize,
+        new: T,
+        commit: impl Future<Output = Result<(), E>>,
+    ) -> Result<(), E> {
+        if let Some(cache) = self.cache.get_value() {
+            cache.optimistic_update(index, new, commit).await
+        } else {
+            misuse!(
+                "Optimistic update is called on a cache controller before the controller has been initialized."
+            );
+            commit.await
+        }
+    }
+
+    /// Loads the item at `index` again with the loader and replaces it. See [`Cache::reload_item`].
+    pub fn reload_item(&self, index: usize) {
+        if let Some(cache) = self.cache.get_value() {
+            cache.reload_item(index);
+        } else {
+            misuse!(
+                "Reload item is called on a cache controller before the controller has been initialized."
+            )
+        }
+    }
+
+    /// Marks the loaded or errored items in the given range as missing so only they are loaded
+    /// again, e.g. after a known change in the data source. See [`Cache::invalidate_range`].
+    pub fn invalidate_range(&self, range: Range<usize>) {
+        if let Some(cache) = self.cache.get_value() {
+            cache.invalidate_range(range);
+        } else {
+            misuse!(
+                "Invalidate range is called on a cache controller before the controller has been initialized."
+            )
+        }
+    }
+
+    /// Moves the item at `from` to the index `to`. See [`Cache::move_item`].
+    ///
+    /// The user is responsible for updating the data source accordingly.
+    pub fn move_item(&self, from: usize, to: usize) {
+        if let Some(cache) = self.cache.get_value() {
+            cache.move_item(from, to);
+        } else {
+            misuse!(
+                "Move item is called on a cache controller before the controller has been initialized."
+            )
+        }
+    }
+
+    /// Swaps the items at the indices `a` and `b`. See [`Cache::swap_items`].
+    ///
+    /// The user is responsible for updating the data source accordingly.
+    pub fn swap_items(&self, a: usize, b: usize) {
+        if let Some(cache) = self.cache.get_value() {
+            cache.swap_items(a, b);
+        } else {
+            misuse!(
+                "Swap items is called on a cache controller before the controller has been initialized."
+            )
+        }
+    }
+
+    /// Applies several changes at once so the UI is only updated a single time.
+    /// See [`Cache::batch`].
+    ///
+    /// ```
+    /// # use leptos_windowing::cache::CacheController;
+    /// #
+    /// # fn example(cache_controller: CacheController<String>) {
+    /// cache_controller.batch(|batch| {
+    ///     batch
+    ///         .remove_item(3)
+    ///         .insert_item(0, "New".to_string())
+    ///         .swap_items(1, 2);
+    /// });
+    /// # }
+    /// ```
+    ///
+    /// The user is responsible for updating the data source accordingly.
+    pub fn batch(&self, f: impl FnOnce(&mut CacheBatch<T>)) {
+        if let Some(cache) = self.cache.get_value() {
+            cache.batch(f);
+        } else {
+            misuse!(
+                "Batch is called on a cache controller before the controller has been initialized."
+            )
+        }
+    }
+
+    /// Replaces every loaded item that matches `predicate` with the result of `update` and returns
+    /// how many items were updated. See [`Cache::update_where`].
+    ///
+    /// ```
+    /// # use leptos_windowing::cache::CacheController;
+    /// #
+    /// # #[derive(Clone)]
+    /// # pub struct Customer { id: u32, name: String }
+    /// # fn example(cache_controller: CacheController<Customer>, changed: Customer) {
+    /// // A server push says that a customer has changed
+    /// cache_controller.update_where(|customer| customer.id == changed.id, |_| changed.clone());
+    /// # }
+    /// ```
+    ///
+    /// The user is responsible for updating the data source accordingly.
+    pub fn update_where(&self, predicate: impl Fn(&T) -> bool, update: impl Fn(&T) -> T) -> usize {
+        if let Some(cache) = self.cache.get_value() {
+            cache.update_where(predicate, update)
+        } else {
+            misuse!(
+                "Update where is called on a cache controller before the controller has been initialized."
+            );
+            0
+        }
+    }
+
+    /// Removes every loaded item that matches `predicate` and returns how many items were
+    /// removed. See [`Cache::remove_where`].
+    ///
+    /// The user is responsible for updating the data source accordingly.
+    pub fn remove_where(&self, predicate: impl Fn(&T) -> bool) -> usize {
+        if let Some(cache) = self.cache.get_value() {
+            cache.remove_where(predicate)
+        } else {
+            misuse!(
+                "Remove where is called on a cache controller before the controller has been initialized."
+            );
+            0
+        }
+    }
+
+    /// Hides the item at the given index without changing the indices or the item count, e.g.
+    /// while it's being archived. See [`Cache::hide_item`].
+    pub fn hide_item(&self, index: usize) {
+        if let Some(cache) = self.cache.get_value() {
+            cache.hide_item(index);
+        } else {
+            misuse!(
+                "Hide item is called on a cache controller before the controller has been initialized."
+            )
+        }
+    }
+
+    /// Displays an item again that has been hidden with [`CacheController::hide_item`].
+    pub fn unhide_item(&self, index: usize) {
+        if let Some(cache) = self.cache.get_value() {
+            cache.unhide_item(index);
+        } else {
+            misuse!(
+                "Unhide item is called on a cache controller before the controller has been initialized."
+            )
+        }
+    }
+
+    /// Hides an item right away and then awaits `commit` which should remove it from the data
+    /// source.
+    ///
+    /// If `commit` succeeds, the item is removed from the cache. Otherwise it's displayed again
+    /// and the error is returned. See [`Cache::optimistic_remove`].
+    pub async fn optimistic_remove<E>(
+        &self,
+        index: usize,
+        commit: impl Future<Output = Result<(), E>>,
+    ) -> Result<(), E> {
+        if let Some(cache) = self.cache.get_value() {
+            cache.optimistic_remove(index, commit).await
+        } else {
+            misuse!(
+                "Optimistic remove is called on a cache controller before the controller has been initialized."
+            );
+            commit.await
+        }
+    }
+
+    /// Removes the item at the given index from the cache and updates the item count.
+    ///
+    /// This doesn't trigger a reload.
+    ///
+    /// The user is responsible for updating the data source accordingly.
+    pub fn remove_item(&self, index: usize) {
+        if let Some(cache) = self.cache.get_value() {
+            cache.remove_item(index);
+        } else {
+            misuse!(
+                "Remove item is called on a cache controller before the controller has been initialized."
+            )
+        }
+    }
+
+    /// Inserts an item at the given index in the cache and updates the item count.
+    ///
+    /// This doesn't trigger a reload.
+    ///
+    /// The user is responsible for updating the data source accordingly.
+    pub fn insert_item(&self, index: usize, new: T) {
+        if let Some(cache) = self.cache.get_value() {
+            cache.insert_item(index, new);
+        } else {
+            misuse!(
+                "Insert item is called on a cache controller before the controller has been initialized."
+            )
+        }
+    }
+}
+
+/// Determines the identity of an item, usually by its id.
+///
+/// With an item key the cache reconciles loaded items by identity instead of only by index.
+/// When rows are inserted or removed in the data source between loads, items shift to other
+/// indices. Copies of the just loaded items at their old indices are then marked as missing
+/// so they aren't displayed twice. The components also key the rendered items by it, so their
+/// views move along with them.
+///
+/// The key is hashed into a `u64`. Two different keys that hash to the same value are treated as
+/// the same item, which is very unlikely but possible. In that case one of them is marked as
+/// missing and loaded again, and the components fall back to rendering them by index.
+///
+/// ```
+/// # use leptos_windowing::cache::ItemKey;
+/// #
+/// pub struct Book {
+///     id: u32,
+///     title: String,
+/// }
+///
+/// let item_key = ItemKey::new(|book: &Book| book.id);
+/// ```
+pub struct ItemKey<T>(Arc<dyn Fn(&T) -> u64 + Send + Sync>);
+
+impl<T> ItemKey<T> {
+    /// Creates a new item key from a function that returns the identity of an item.
+    pub fn new<K: Hash>(key: impl Fn(&T) -> K + Send + Sync + 'static) -> Self {
+        Self(Arc::new(move |item| {
+            let mut hasher = DefaultHasher::new();
+            key(item).hash(&mut hasher);
+            hasher.finish()
+        }))
+    }
+
+    /// Returns the hashed key of `item`.
+    #[inline]
+    pub fn key(&self, item: &T) -> u64 {
+        (self.0)(item)
+    }
+}
+
+impl<T> Clone for ItemKey<T> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<T> std::fmt::Debug for ItemKey<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ItemKey")
+    }
+}
+
+impl<T, K, F> From<F> for ItemKey<T>
+where
+    K: Hash,
+    F: Fn(&T) -> K + Send + Sync + 'static,
+{
+    fn from(key: F) -> Self {
+        Self::new(key)
+    }
+}
+
+/// The current time in milliseconds since the Unix epoch. This is the clock of the load times of
+/// the cache.
+pub fn now_millis() -> f64 {
+    #[cfg(target_arch = "wasm32")]
+    {
+        js_sys::Date::now()
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0.0, |duration| duration.as_secs_f64() * 1000.0)
+    }
+}
+
+/// A random number between 0 and 1 for the jitter of [`RetryPolicy::delay`](crate::RetryPolicy::delay).
+///
+/// Outside of the browser this is always `0.5`.
+#[cfg(not(feature = "ssr"))]
+pub(crate) fn random_jitter() -> f64 {
+    #[cfg(target_arch = "wasm32")]
+    {
+        js_sys::Math::random()
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        0.5
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_range() {
+        let cache = Cache::<i32>::new();
+
+        assert_eq!(cache.missing_range(0..10), Some(0..10));
+        assert_eq!(cache.missing_range(5..10), Some(5..10));
+
+        cache.write_loaded(
+            Ok(LoadedItems {
+                items: (0..5).collect::<Vec<_>>(),
+                range: 0..5,
+            }),
+            0..5,
+        );
+
+        assert_eq!(cache.missing_range(0..10), Some(5..10));
+        assert_eq!(cache.missing_range(5..10), Some(5..10));
+        assert_eq!(cache.missing_range(5..20), Some(5..20));
+
+        cache.write_loading(5..9);
+
+        assert_eq!(cache.missing_range(0..10), Some(9..10));
+        assert_eq!(cache.missing_range(5..10), Some(9..10));
+        assert_eq!(cache.missing_range(5..20), Some(9..20));
+    }
+
+    #[test]
+    fn test_missing_ranges_skip_loading() {
+        let cache = Cache::<i32>::new();
+
+        assert_eq!(cache.missing_ranges(0..10), vec![0..10]);
+
+        cache.write_loading(5..10);
+        assert_eq!(cache.missing_ranges(0..10), vec![0..5]);
+        assert_eq!(cache.missing_ranges(0..20), [0..5, 10..20]);
+        assert!(cache.missing_ranges(6..8).is_empty());
+
+        cache.write_loaded(
+            Ok(LoadedItems {
+                items: (0..3).collect::<Vec<_>>(),
+                range: 0..3,
+            }),
+            0..3,
+        );
+        cache.write_loading(14..15);
+        assert_eq!(cache.missing_ranges(0..20), [3..5, 10..14, 15..20]);
+    }
+
+    /// Runs `f` in an immediate effect and returns a function that tells how often it has run so far.
+    ///
+    /// The effect lives as long as the returned function.
+    fn count_effect_runs(f: impl Fn() + Send + Sync + 'static) -> impl Fn() -> usize {
+        use leptos::reactive::effect::ImmediateEffect;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let runs = Arc::new(AtomicUsize::new(0));
+
+        let effect = ImmediateEffect::new_isomorphic({
+            let runs = Arc::clone(&runs);
+            move || {
+                runs.fetch_add(1, Ordering::Relaxed);
+                f();
+            }
+        });
+
+        move || {
+            let _effect = &effect;
+            runs.load(Ordering::Relaxed)
+        }
+    }
+
+    #[test]
+    fn test_writes_are_untracked() {
+        let mut cache = Cache::<i32>::new();
+
+        let runs = count_effect_runs(move || {
+            let mut cache = cache;
+            cache.grow(5);
+            cache.write_loading(0..5);
+        });
+
+        assert_eq!(runs(), 1);
+
+        cache.write_loaded(
+            Ok(LoadedItems {
+                items: (0..5).collect::<Vec<_>>(),
+                range: 0..5,
+            }),
+            0..5,
+        );
+        cache.grow(10);
+        cache.write_loaded(Err("error".into()), 5..10);
+
+        assert_eq!(runs(), 1);
+    }
+
+    #[test]
+    fn test_write_loaded_only_notifies_its_rows() {
+        let cache = Cache::<i32>::new();
+
+        cache.write_loading(0..10);
+
+        let runs = count_effect_runs(move || {
+            let _ = cache.items().at_unkeyed(5).read();
+        });
+
+        // Like a batch of a streaming loader
+        cache.write_loaded(
+            Ok(LoadedItems {
+                items: vec![0, 1],
+                range: 0..2,
+            }),
+            0..10,
+        );
+        assert_eq!(runs(), 1);
+        assert!(matches!(
+            cache.items().read_untracked()[1],
+            ItemState::Loaded(_)
+        ));
+        assert!(matches!(
+            cache.items().read_untracked()[2],
+            ItemState::Loading
+        ));
+
+        cache.write_loaded(
+            Ok(LoadedItems {
+                items: vec![5],
+                range: 5..6,
+            }),
+            5..6,
+        );
+        assert_eq!(runs(), 2);
+    }
+
+    #[test]
+    fn test_loading_doesnt_notify_missing() {
+        const ITEM_COUNT: usize = 10_000;
+
+        let cache = Cache::<usize>::new();
+
+        let runs = count_effect_runs(move || {
+            cache.track_missing();
+            let _ = untrack(|| cache.missing_range(0..ITEM_COUNT));
+        });
+
+        cache.write_loading(0..ITEM_COUNT);
+        cache.write_loaded(
+            Ok(LoadedItems {
+                items: (0..ITEM_COUNT).collect::<Vec<_>>(),
+                range: 0..ITEM_COUNT,
+            }),
+            0..ITEM_COUNT,
+        );
+        cache.update_item(0, 1);
+
+        // previously every single loaded item reran the check
+        assert_eq!(runs(), 1);
+
+        cache.invalidate_range(0..10);
+        assert_eq!(runs(), 2);
+        assert_eq!(cache.missing_range(0..10), Some(0..10));
+        assert_eq!(cache.missing_range(10..ITEM_COUNT), None);
+
+        cache.remove_item(0);
+        cache.clear();
+        assert_eq!(runs(), 4);
+    }
+
+    #[test]
+    fn test_failed_ranges() {
+        let cache = Cache::<i32>::new();
+
+        cache.write_loading(0..10);
+        cache.write_loaded(Err("error".into()), 0..10);
+        cache.update_item(2, 2);
+
+        assert_eq!(cache.failed_ranges(), [0..2, 3..10]);
+
+        cache.retry_all_failed();
+
+        assert!(cache.failed_ranges().is_empty());
+        assert_eq!(cache.missing_range(0..2), Some(0..2));
+    }
+
+    #[test]
+    fn test_load_error_only_marks_requested_range() {
+        let cache = Cache::<i32>::new();
+
+        cache.write_loading(0..30);
+        cache.write_loaded(
+            Ok(LoadedItems {
+                items: (0..10).collect(),
+                range: 0..10,
+            }),
+            0..10,
+        );
+        cache.write_loaded(Err("error".into()), 10..20);
+
+        assert_eq!(cache.failed_ranges(), vec![10..20]);
+        assert!(matches!(
+            cache.items().read_untracked()[0],
+            ItemState::Loaded(_)
+        ));
+        assert!(matches!(
+            cache.items().read_untracked()[20],
+            ItemState::Loading
+        ));
+    }
+
+    #[test]
+    fn test_invalidate_expired_errors() {
+        let cache = Cache::<i32>::new();
+
+        cache.write_loading(0..4);
+        cache.write_loaded(Err("error".into()), 0..4);
+
+        cache.invalidate_expired_errors(0..4, Duration::from_secs(60));
+        assert_eq!(cache.missing_range(0..4), None);
+
+        cache.invalidate_expired_errors(1..3, Duration::ZERO);
+        assert_eq!(cache.failed_ranges(), [0..1, 3..4]);
+        assert_eq!(cache.missing_range(1..3), Some(1..3));
+
+        cache.retry_all_failed();
+        assert!(cache.failed_ranges().is_empty());
+    }
+
+    #[test]
+    fn test_copy_loaded_from() {
+        let source = Cache::<i32>::new();
+        source.write_loading(0..6);
+        source.write_loaded(
+            Ok(LoadedItems {
+                items: vec![0, 1, 2],
+                range: 0..3,
+            }),
+            0..3,
+        );
+        source.write_loaded(
+            Ok(LoadedItems {
+                items: vec![4, 5],
+                range: 4..6,
+            }),
+            4..6,
+        );
+        source.item_count().set(Some(10));
+
+        let mut target = Cache::<i32>::new();
+        target.grow(2);
+        target.update_item(1, 11);
+
+        let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+        target.on_change({
+            let events = Arc::clone(&events);
+            move |event| {
+                if let CacheEvent::Loaded { range, items } = event {
+                    events.lock().unwrap().push((
+                        range.clone(),
+                        items.iter().map(|item| **item).collect::<Vec<_>>(),
+                    ));
+                }
+            }
+        });
+
+        target.copy_loaded_from(source);
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![(0..1, vec![0]), (2..3, vec![2]), (4..6, vec![4, 5])]
+        );
+
+        assert_eq!(target.item_count().get_untracked(), Some(10));
+        assert_eq!(target.missing_range(0..4), Some(3..4));
+
+        let loaded = target
+            .items()
+            .read_untracked()
+            .iter()
+            .map(|row| match row {
+                ItemState::Loaded(item) => Some(**item),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(loaded, [Some(0), Some(11), Some(2), None, Some(4), Some(5)]);
+    }
+
+    #[test]
+    fn test_loaded_fraction() {
+        let cache = Cache::<i32>::new();
+
+        assert_eq!(cache.loaded_fraction(0..0), 1.0);
+        assert_eq!(cache.loaded_fraction(0..4), 0.0);
+
+        cache.write_loading(0..4);
+        cache.write_loaded(
+            Ok(LoadedItems {
+                items: vec![0, 1],
+                range: 0..2,
+            }),
+            0..2,
+        );
+        assert_eq!(cache.loaded_fraction(0..4), 0.5);
+        assert_eq!(cache.loaded_fraction(0..8), 0.25);
+
+        cache.write_loaded(Err("error".into()), 2..4);
+        assert_eq!(cache.loaded_fraction(0..4), 1.0);
+    }
+
+    #[test]
+    fn test_loaded_fraction_is_notified_once_per_load() {
+        let cache = Cache::<i32>::new();
+        cache.write_loading(0..100);
+
+        let runs = count_effect_runs(move || {
+            let _ = cache.loaded_fraction(0..100);
+        });
+
+        cache.write_loaded(
+            Ok(LoadedItems {
+                items: (0..100).collect::<Vec<_>>(),
+                range: 0..100,
+            }),
+            0..100,
+        );
+
+        assert_eq!(runs(), 2);
+    }
+
+    #[test]
+    fn test_is_loading() {
+        let cache = Cache::<i32>::new();
+
+        assert!(!cache.is_loading(0..4));
+
+        cache.write_loading(0..4);
+        assert!(cache.is_loading(0..4));
+
+        cache.write_loaded(
+            Ok(LoadedItems {
+                items: vec![0, 1],
+                range: 0..2,
+            }),
+            0..2,
+        );
+        assert!(!cache.is_loading(0..2));
+        assert!(cache.is_loading(0..8));
+    }
+
+    #[test]
+    fn test_evict_far_from() {
+        let cache = Cache::<i32>::new();
+        let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+        cache.on_change({
+            let events = Arc::clone(&events);
+            move |event| events.lock().unwrap().push(format!("{event:?}"))
+        });
+
+        cache.write_loaded(
+            Ok(LoadedItems {
+                items: (0..10).collect::<Vec<_>>(),
+                range: 0..10,
+            }),
+            0..10,
+        );
+
+        cache.evict_far_from(3..5, 6);
+
+        let is_loaded =
+            |index: usize| matches!(cache.items().read_untracked()[index], ItemState::Loaded(_));
+        assert_eq!(
+            (0..10).map(is_loaded).collect::<Vec<_>>(),
+            [
+                false, true, true, true, true, true, true, false, false, false
+            ]
+        );
+        // One event per evicted span, not per item
+        assert_eq!(
+            events.lock().unwrap()[1..],
+            ["Invalidated(0..1)", "Invalidated(7..10)"]
+        );
+
+        cache.evict_far_from(3..5, 0);
+        assert_eq!(cache.missing_range(0..10), Some(0..10));
+        assert_eq!(cache.missing_range(3..5), None);
+    }
+
+    #[test]
+    fn test_refresh_stale() {
+        let mut cache = Cache::<i32>::new();
+        let refreshed = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+        cache.refresh_range = Callback::new({
+            let refreshed = Arc::clone(&refreshed);
+            move |range| refreshed.lock().unwrap().push(range)
+        });
+
+        cache.write_loaded(
+            Ok(LoadedItems {
+                items: (0..5).collect::<Vec<_>>(),
+                range: 0..5,
+            }),
+            0..5,
+        );
+
+        cache.refresh_stale(0..10, Duration::from_secs(60));
+        assert!(refreshed.lock().unwrap().is_empty());
+        assert!(!cache.is_stale(1));
+
+        // The expired items stay displayed while they're loaded again
+        cache.refresh_stale(1..3, Duration::ZERO);
+        assert_eq!(*refreshed.lock().unwrap(), vec![1..3]);
+        assert_eq!(cache.missing_range(0..5), None);
+        assert!(cache.is_stale(1) && cache.is_stale(2) && !cache.is_stale(3));
+
+        // They're not requested again while they're being loaded
+        cache.refresh_stale(0..5, Duration::ZERO);
+        assert_eq!(*refreshed.lock().unwrap(), [1..3, 0..1, 3..5]);
+
+        cache.insert_item(0, 10);
+        cache.refresh_stale(0..1, Duration::from_secs(60));
+        assert_eq!(refreshed.lock().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_refresh() {
+        let mut cache = Cache::<i32>::new();
+        let refreshed = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+        cache.refresh_range = Callback::new({
+            let refreshed = Arc::clone(&refreshed);
+            move |range| refreshed.lock().unwrap().push(range)
+        });
+
+        cache.write_loaded(
+            Ok(LoadedItems {
+                items: (0..5).collect::<Vec<_>>(),
+                range: 0..5,
+            }),
+            0..5,
+        );
+        cache.grow(7);
+
+        // Only the loaded items are stale while they stay displayed
+        cache.refresh(3..7);
+        assert_eq!(*refreshed.lock().unwrap(), vec![3..7]);
+        assert_eq!(cache.missing_range(0..5), None);
+        assert!(!cache.is_stale(2) && cache.is_stale(3) && cache.is_stale(4));
+        assert!(!cache.is_stale(5));
+
+        // Loading them again makes them fresh
+        cache.write_loaded(
+            Ok(LoadedItems {
+                items: vec![3, 4],
+                range: 3..5,
+            }),
+            3..5,
+        );
+        assert!(!cache.is_stale(3) && !cache.is_stale(4));
+
+        // A missing item is loaded instead of marked as stale
+        cache.reload_item(6);
+        assert_eq!(*refreshed.lock().unwrap(), [3..7, 6..7]);
+        assert!(!cache.is_stale(6));
+        assert!(matches!(
+            cache.items().read_untracked()[6],
+            ItemState::Loading
+        ));
+
+        // Nothing happens while it's loading
+        cache.reload_item(6);
+        assert_eq!(refreshed.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_is_stale_is_reactive() {
+        let cache = Cache::<i32>::new();
+
+        cache.write_loaded(
+            Ok(LoadedItems {
+                items: (0..5).collect::<Vec<_>>(),
+                range: 0..5,
+            }),
+            0..5,
+        );
+
+        let runs = count_effect_runs(move || {
+            let _ = cache.is_stale(1);
+        });
+
+        cache.mark_stale(0..3);
+        assert!(cache.is_stale(1));
+        assert_eq!(runs(), 2);
+    }
+
+    #[test]
+    fn test_optimistic_update() {
+        let cache = Cache::<i32>::new();
+
+        cache.write_loaded(
+            Ok(LoadedItems {
+                items: (0..3).collect::<Vec<_>>(),
+                range: 0..3,
+            }),
+            0..3,
+        );
+
+        let item = |index: usize| match &cache.items().read_untracked()[index] {
+            ItemState::Loaded(item) => Some(**item),
+            _ => None,
+        };
+
+        // The item is updated before the commit has finished
+        let result = futures::executor::block_on(cache.optimistic_update(1, 10, async {
+            assert_eq!(item(1), Some(10));
+            Ok::<_, ()>(())
+        }));
+        assert_eq!(result, Ok(()));
+        assert_eq!(item(1), Some(10));
+
+        // A failed commit restores the previous item
+        let result =
+            futures::executor::block_on(cache.optimistic_update(1, 11, async { Err("failed") }));
+        assert_eq!(result, Err("failed"));
+        assert_eq!(item(1), Some(10));
+
+        // Unless it has been changed in the meantime, e.g. by a reload
+        let result = futures::executor::block_on(cache.optimistic_update(2, 12, async {
+            cache.update_item(2, 22);
+            Err("failed")
+        }));
+        assert_eq!(result, Err("failed"));
+        assert_eq!(item(2), Some(22));
+    }
+
+    #[test]
+    fn test_hide_item() {
+        let cache = Cache::<i32>::new();
+
+        cache.write_loaded(
+            Ok(LoadedItems {
+                items: (0..5).collect::<Vec<_>>(),
+                range: 0..5,
+            }),
+            0..5,
+        );
+        cache.item_count().set(Some(5));
+
+        cache.hide_item(1);
+        cache.hide_item(3);
+        assert!(cache.is_hidden(1) && cache.is_hidden(3));
+        assert_eq!(cache.item_count().get_untracked(), Some(5));
+
+        // The hidden items move with the other items
+        cache.insert_item(0, 10);
+        assert!(cache.is_hidden(2) && cache.is_hidden(4));
+        cache.remove_item(4);
+        assert!(cache.is_hidden(2) && !(3..5).any(|index| cache.is_hidden(index)));
+
+        cache.unhide_item(2);
+        assert!(!cache.is_hidden(2));
+        cache.unhide_item(2);
+        assert!(!(0..5).any(|index| cache.is_hidden(index)));
+    }
+
+    #[test]
+    fn test_overlapping_optimistic_removes() {
+        use futures::{channel::oneshot, executor::LocalPool, task::LocalSpawnExt};
+
+        let cache = Cache::<i32>::new();
+
+        cache.write_loaded(
+            Ok(LoadedItems {
+                items: (0..5).collect::<Vec<_>>(),
+                range: 0..5,
+            }),
+            0..5,
+        );
+        cache.item_count().set(Some(5));
+
+        let loaded = || {
+            cache
+                .items()
+                .read_untracked()
+                .iter()
+                .map(|item| match item {
+                    ItemState::Loaded(item) => **item,
+                    _ => -1,
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let mut pool = LocalPool::new();
+        let remove = |index: usize| {
+            let (commit, committed) = oneshot::channel::<Result<(), ()>>();
+            pool.spawner()
+                .spawn_local(async move {
+                    let _ = cache
+                        .optimistic_remove(index, async { committed.await.unwrap() })
+                        .await;
+                })
+                .unwrap();
+            commit
+        };
+
+        let commit_1 = remove(1);
+        let commit_3 = remove(3);
+        // Removes the same item twice
+        let commit_3_again = remove(3);
+        pool.run_until_stalled();
+        assert!(cache.is_hidden(1) && cache.is_hidden(3));
+
+        // Both pending removals follow their items
+        cache.insert_item(0, 10);
+        assert_eq!(loaded(), [10, 0, 1, 2, 3, 4]);
+
+        commit_3.send(Ok(())).unwrap();
+        pool.run_until_stalled();
+        assert_eq!(loaded(), [10, 0, 1, 2, 4]);
+        assert!(cache.is_hidden(2));
+
+        // The item is gone already
+        commit_3_again.send(Ok(())).unwrap();
+        pool.run_until_stalled();
+        assert_eq!(loaded(), [10, 0, 1, 2, 4]);
+
+        commit_1.send(Err(())).unwrap();
+        pool.run_until_stalled();
+        assert_eq!(loaded(), [10, 0, 1, 2, 4]);
+        assert!(!(0..5).any(|index| cache.is_hidden(index)));
+        assert_eq!(cache.item_count().get_untracked(), Some(5));
+    }
+
+    #[test]
+    fn test_item_key_removes_shifted_duplicates() {
+        let cache = Cache::<(u32, &str)>::new();
+        cache.set_item_key(Some(ItemKey::new(|item: &(u32, &str)| item.0)));
+
+        cache.write_loaded(
+            Ok(LoadedItems {
+                items: vec![(1, "a"), (2, "b"), (3, "c")],
+                range: 0..3,
+            }),
+            0..3,
+        );
+
+        // An item has been inserted at the start in the data source so everything shifted by one.
+        cache.write_loaded(
+            Ok(LoadedItems {
+                items: vec![(3, "c"), (4, "d")],
+                range: 3..5,
+            }),
+            3..5,
+        );
+
+        assert_eq!(cache.missing_range(0..3), Some(2..3));
+        assert!(matches!(
+            &cache.items().read_untracked()[3],
+            ItemState::Loaded(item) if item.1 == "c"
+        ));
+    }
+
+    #[test]
+    fn test_item_key_duplicates_of_overlapping_ranges() {
+        let cache = Cache::<(u32, &str)>::new();
+        let item_key = ItemKey::new(|item: &(u32, &str)| item.0);
+        cache.set_item_key(Some(item_key.clone()));
+
+        cache.write_loaded(
+            Ok(LoadedItems {
+                items: vec![(1, "a"), (2, "b"), (3, "c"), (4, "d")],
+                range: 0..4,
+            }),
+            0..4,
+        );
+
+        // An item has been inserted at the start in the data source so everything shifted by one.
+        cache.write_loaded(
+            Ok(LoadedItems {
+                items: vec![(2, "b"), (3, "c"), (4, "d"), (5, "e")],
+                range: 2..6,
+            }),
+            2..6,
+        );
+
+        // Only the copy outside of the loaded range is a duplicate
+        assert_eq!(cache.missing_range(0..2), Some(1..2));
+        assert_eq!(cache.missing_range(2..6), None);
+        assert_eq!(cache.index_of_key(item_key.key(&(2, "b"))), Some(2));
+        assert_eq!(cache.key_at(0), Some(item_key.key(&(1, "a"))));
+    }
+
+    #[test]
+    fn test_item_key_duplicates_after_local_shift() {
+        let cache = Cache::<(u32, &str)>::new();
+        cache.set_item_key(Some(ItemKey::new(|item: &(u32, &str)| item.0)));
+
+        cache.write_loaded(
+            Ok(LoadedItems {
+                items: vec![(1, "a"), (2, "b"), (3, "c")],
+                range: 0..3,
+            }),
+            0..3,
+        );
+        cache.insert_item(0, (0, "z"));
+
+        // The data source has dropped the first two items in the meantime.
+        cache.write_loaded(
+            Ok(LoadedItems {
+                items: vec![(2, "b"), (3, "c")],
+                range: 0..2,
+            }),
+            0..2,
+        );
+
+        assert_eq!(cache.missing_range(0..4), Some(2..4));
+    }
+
+    #[test]
+    fn test_index_of_key() {
+        let item_key = ItemKey::new(|item: &(u32, &str)| item.0);
+        let cache = Cache::<(u32, &str)>::new();
+        cache.set_item_key(Some(item_key.clone()));
+
+        cache.write_loaded(
+            Ok(LoadedItems {
+                items: vec![(1, "a"), (2, "b"), (3, "c")],
+                range: 0..3,
+            }),
+            0..3,
+        );
+
+        let key = item_key.key(&(3, "c"));
+        assert_eq!(cache.key_at(2), Some(key));
+        assert_eq!(cache.index_of_key(key), Some(2));
+
+        cache.insert_item(0, (0, "z"));
+        assert_eq!(cache.index_of_key(key), Some(3));
+
+        cache.remove_item(1);
+        cache.remove_item(1);
+        assert_eq!(cache.index_of_key(key), Some(1));
+
+        cache.remove_item(1);
+        assert_eq!(cache.index_of_key(key), None);
+        assert_eq!(cache.index_of_key(item_key.key(&(0, "z"))), Some(0));
+    }
+
+    #[test]
+    fn test_cancel_loading() {
+        let cache = Cache::<i32>::new();
+
+        cache.write_loading(0..10);
+        cache.write_loaded(
+            Ok(LoadedItems {
+                items: (0..3).collect::<Vec<_>>(),
+                range: 0..3,
+            }),
+            0..10,
+        );
+        cache.cancel_loading(2..20);
+
+        assert_eq!(cache.missing_range(0..10), Some(3..10));
+        assert!(matches!(
+            cache.items().read_untracked()[2],
+            ItemState::Loaded(_)
+        ));
+    }
+
+    #[test]
+    fn test_apply_live_update() {
+        let cache = Cache::<i32>::new();
+
+        cache.write_loaded(
+            Ok(LoadedItems {
+                items: (0..5).collect::<Vec<_>>(),
+                range: 0..5,
+            }),
+            0..5,
+        );
+        cache.item_count().set(Some(20));
+
+        let loaded = || {
+            cache
+                .items()
+                .read_untracked()
+                .iter()
+                .map(|item| match item {
+                    ItemState::Loaded(item) => **item,
+                    _ => -1,
+                })
+                .collect::<Vec<_>>()
+        };
+
+        cache.apply_live_update(LiveUpdate::Insert { index: 1, item: 10 });
+        cache.apply_live_update(LiveUpdate::Update { index: 0, item: 20 });
+        cache.apply_live_update(LiveUpdate::Delete { index: 3 });
+        assert_eq!(loaded(), [20, 10, 1, 3, 4]);
+        assert_eq!(cache.item_count().get_untracked(), Some(20));
+
+        // outside of the cached items only the count changes
+        cache.apply_live_update(LiveUpdate::Insert {
+            index: 10,
+            item: 30,
+        });
+        cache.apply_live_update(LiveUpdate::Update {
+            index: 10,
+            item: 40,
+        });
+        assert_eq!(loaded(), [20, 10, 1, 3, 4]);
+        assert_eq!(cache.item_count().get_untracked(), Some(21));
+
+        cache.apply_live_update(LiveUpdate::Delete { index: 10 });
+        assert_eq!(cache.item_count().get_untracked(), Some(20));
+    }
+
+    #[test]
+    fn test_batch() {
+        let cache = Cache::<i32>::new();
+
+        cache.write_loaded(
+            Ok(LoadedItems {
+                items: (0..5).collect::<Vec<_>>(),
+                range: 0..5,
+            }),
+            0..5,
+        );
+        cache.item_count().set(Some(5));
+        cache.hide_item(4);
+
+        let loaded = || {
+            cache
+                .items()
+                .read_untracked()
+                .iter()
+                .map(|item| match item {
+                    ItemState::Loaded(item) => **item,
+                    _ => -1,
+                })
+                .collect::<Vec<_>>()
+        };
+
+        cache.move_item(4, 0);
+        assert_eq!(loaded(), [4, 0, 1, 2, 3]);
+        assert!(cache.is_hidden(0));
+
+        cache.swap_items(1, 2);
+        assert_eq!(loaded(), [4, 1, 0, 2, 3]);
+
+        cache.batch(|batch| {
+            batch
+                .remove_item(0)
+                .insert_item(2, 10)
+                .update_item(0, 20)
+                .move_item(0, 3);
+        });
+        assert_eq!(loaded(), [0, 10, 2, 20, 3]);
+        assert_eq!(cache.item_count().get_untracked(), Some(5));
+        assert!(!(0..5).any(|index| cache.is_hidden(index)));
+    }
+
+    #[test]
+    fn test_update_and_remove_where() {
+        let cache = Cache::<i32>::new();
+
+        cache.write_loaded(
+            Ok(LoadedItems {
+                items: (0..6).collect::<Vec<_>>(),
+                range: 0..6,
+            }),
+            0..6,
+        );
+        cache.item_count().set(Some(10));
+
+        assert_eq!(
+            cache.update_where(|item| *item % 2 == 0, |item| item * 10),
+            3
+        );
+        assert_eq!(cache.remove_where(|item| *item > 5), 2);
+        assert_eq!(cache.remove_where(|item| *item > 100), 0);
+
+        let loaded = cache
+            .items()
+            .read_untracked()
+            .iter()
+            .map(|item| match item {
+                ItemState::Loaded(item) => **item,
+                _ => -1,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(loaded, [0, 1, 3, 5]);
+        assert_eq!(cache.item_count().get_untracked(), Some(8));
+    }
+
+    #[test]
+    fn test_moved_index() {
+        assert_eq!(moved_index(2, 2, 5), 5);
+        assert_eq!(moved_index(3, 2, 5), 2);
+        assert_eq!(moved_index(5, 2, 5), 4);
+        assert_eq!(moved_index(6, 2, 5), 6);
+        assert_eq!(moved_index(2, 5, 2), 3);
+        assert_eq!(moved_index(4, 5, 2), 5);
+        assert_eq!(moved_index(1, 5, 2), 1);
+    }
+
+    #[test]
+    fn test_contiguous_ranges() {
+        assert!(contiguous_ranges(vec![]).is_empty());
+        assert_eq!(
+            contiguous_ranges(vec![9, 0, 8, 1, 4, 1]),
+            [0..2, 4..5, 8..10]
+        );
+    }
+
+    #[test]
+    fn test_on_change() {
+        let cache = Cache::<i32>::new();
+        let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+        cache.on_change({
+            let events = Arc::clone(&events);
+            move |event| events.lock().unwrap().push(format!("{event:?}"))
+        });
+
+        cache.write_loaded(
+            Ok(LoadedItems {
+                items: (0..5).collect::<Vec<_>>(),
+                range: 0..5,
+            }),
+            0..5,
+        );
+        cache.update_item(1, 10);
+        cache.invalidate_range(2..4);
+        cache.clear();
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec!["Loaded(0..5)", "Updated(1)", "Invalidated(2..4)", "Cleared"]
+        );
     }
 }
@@ -0,0 +1,149 @@
+use std::time::Duration;
+
+/// Adjusts how many items are requested per load based on how long the loads take.
+/// See [`UseLoadOnDemandOptions`](crate::hook::UseLoadOnDemandOptions).
+///
+/// Missing items are requested in chunks of the current size. When a chunk arrives in less than
+/// half of `target_latency` the size is doubled, when it takes longer than `target_latency` it is
+/// halved. It always stays between `min` and `max`. So lists of small items are loaded with few
+/// large requests and lists of heavy items with many small ones without configuring each of them.
+///
+/// If the loader has a fixed chunk size, like the paginated loaders, the size is rounded up to a
+/// multiple of it.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use leptos_windowing::AdaptiveChunkSize;
+/// #
+/// let chunk_size = AdaptiveChunkSize::default()
+///     .max(200)
+///     .target_latency(Duration::from_millis(500));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdaptiveChunkSize {
+    /// The size of the first chunks.
+    pub initial: usize,
+
+    /// The lower limit of the chunk size.
+    pub min: usize,
+
+    /// The upper limit of the chunk size.
+    pub max: usize,
+
+    /// How long loading one chunk should take.
+    pub target_latency: Duration,
+}
+
+impl AdaptiveChunkSize {
+    /// Sets `initial`.
+    pub fn initial(self, initial: usize) -> Self {
+        Self { initial, ..self }
+    }
+
+    /// Sets `min`.
+    pub fn min(self, min: usize) -> Self {
+        Self { min, ..self }
+    }
+
+    /// Sets `max`.
+    pub fn max(self, max: usize) -> Self {
+        Self { max, ..self }
+    }
+
+    /// Sets `target_latency`.
+    pub fn target_latency(self, target_latency: Duration) -> Self {
+        Self {
+            target_latency,
+            ..self
+        }
+    }
+
+    /// The chunk size after a chunk of `current` items took `elapsed` to load.
+    ///
+    /// `loader_chunk_size` is the fixed chunk size of the loader, if any.
+    pub fn next(
+        &self,
+        current: usize,
+        elapsed: Duration,
+        loader_chunk_size: Option<usize>,
+    ) -> usize {
+        let next = if elapsed < self.target_latency / 2 {
+            current.saturating_mul(2)
+        } else if elapsed > self.target_latency {
+            current / 2
+        } else {
+            current
+        };
+
+        self.clamp(next, loader_chunk_size)
+    }
+
+    /// Limits `size` to `min..=max` and rounds it up to a multiple of `loader_chunk_size`.
+    pub fn clamp(&self, size: usize, loader_chunk_size: Option<usize>) -> usize {
+        let size = size.min(self.max).max(self.min).max(1);
+
+        match loader_chunk_size {
+            Some(loader_chunk_size) if loader_chunk_size > 0 => {
+                size.div_ceil(loader_chunk_size) * loader_chunk_size
+            }
+            _ => size,
+        }
+    }
+}
+
+/// Starts with 50 items per chunk that can grow up to 1000 and shrink down to 10 items
+/// with a target latency of 300ms.
+impl Default for AdaptiveChunkSize {
+    fn default() -> Self {
+        Self {
+            initial: 50,
+            min: 10,
+            max: 1000,
+            target_latency: Duration::from_millis(300),
+        }
+    }
+}
+
+/// Splits `range` into consecutive ranges of at most `chunk_size` items.
+#[cfg(any(not(feature = "ssr"), test))]
+pub(crate) fn split_into_chunks(
+    range: std::ops::Range<usize>,
+    chunk_size: usize,
+) -> impl Iterator<Item = std::ops::Range<usize>> {
+    let chunk_size = chunk_size.max(1);
+
+    range
+        .clone()
+        .step_by(chunk_size)
+        .map(move |start| start..(start + chunk_size).min(range.end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next() {
+        let chunk_size = AdaptiveChunkSize::default().min(10).max(100);
+        let target = chunk_size.target_latency;
+
+        assert_eq!(chunk_size.next(40, target / 4, None), 80);
+        assert_eq!(chunk_size.next(80, target / 4, None), 100);
+        assert_eq!(chunk_size.next(40, target, None), 40);
+        assert_eq!(chunk_size.next(40, target * 2, None), 20);
+        assert_eq!(chunk_size.next(15, target * 2, None), 10);
+
+        assert_eq!(chunk_size.next(40, target * 2, Some(30)), 30);
+        assert_eq!(chunk_size.next(40, target / 4, Some(30)), 90);
+        assert_eq!(chunk_size.clamp(0, None), 10);
+    }
+
+    #[test]
+    fn test_split_into_chunks() {
+        assert_eq!(
+            split_into_chunks(5..27, 10).collect::<Vec<_>>(),
+            [5..15, 15..25, 25..27]
+        );
+        assert_eq!(split_into_chunks(5..5, 10).count(), 0);
+    }
+}
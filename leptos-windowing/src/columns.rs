@@ -0,0 +1,221 @@
+use std::ops::Range;
+
+use default_struct_builder::DefaultBuilder;
+use leptos::prelude::*;
+use leptos_use::{
+    UseElementSizeReturn, UseScrollReturn,
+    core::{ElementMaybeSignal, IntoElementMaybeSignal},
+    use_element_size, use_scroll,
+};
+
+/// Horizontal windowing of columns for wide tables.
+///
+/// Only the columns that are visible inside the horizontally scrolling `container` (plus some overscan)
+/// are returned so a table with hundreds of columns only renders a few of them. This is independent of
+/// how the rows are windowed so it can be combined with row pagination or virtualization.
+///
+/// ## Params
+///
+/// - `container`: The element that scrolls horizontally.
+/// - `column_count`: The total number of columns.
+/// - `column_width`: An estimate of the width in pixels of the column with the given index.
+/// - `options`: Additional options. See [`UseColumnWindowOptions`].
+///
+/// Render the returned range of columns and put spacers with the widths `width_before` and `width_after`
+/// before and after them to keep the scroll width correct.
+///
+/// ## Pinned columns
+///
+/// With [`UseColumnWindowOptions::pinned_column_count`] the first columns are always rendered, e.g. to keep
+/// identifiers visible during horizontal scrolling. Render them before the spacer with `position: sticky`
+/// and the left offsets from [`ColumnWindow::pinned_left`]. They are excluded from [`ColumnWindow::range`].
+#[must_use]
+pub fn use_column_window<El, M>(
+    container: El,
+    column_count: impl Into<Signal<usize>>,
+    column_width: impl Fn(usize) -> f64 + Send + Sync + 'static,
+    options: UseColumnWindowOptions,
+) -> ColumnWindow
+where
+    El: IntoElementMaybeSignal<web_sys::Element, M>,
+{
+    let UseColumnWindowOptions {
+        overscan_column_count,
+        pinned_column_count,
+    } = options;
+
+    let container: ElementMaybeSignal<web_sys::Element> = container.into_element_maybe_signal();
+    let column_count = column_count.into();
+
+    let UseScrollReturn { x: scroll_left, .. } = use_scroll(container);
+    let UseElementSizeReturn { width, .. } = use_element_size(container);
+
+    let column_offsets = Memo::new(move |_| column_offsets(column_count.get(), &column_width));
+
+    let range = Memo::new(move |_| {
+        column_offsets.with(|offsets| {
+            visible_column_range(
+                offsets,
+                scroll_left.get(),
+                width.get(),
+                overscan_column_count,
+                pinned_column_count,
+            )
+        })
+    });
+
+    let pinned_count =
+        Signal::derive(move || pinned_column_count.min(column_offsets.with(|o| o.len() - 1)));
+
+    ColumnWindow {
+        range: range.into(),
+        pinned_range: Signal::derive(move || 0..pinned_count.get()),
+        pinned_left: Signal::derive(move || {
+            column_offsets.with(|offsets| offsets[..pinned_count.get()].to_vec())
+        }),
+        pinned_width: Signal::derive(move || {
+            column_offsets.with(|offsets| offsets[pinned_count.get()])
+        }),
+        width_before: Signal::derive(move || {
+            column_offsets.with(|offsets| offsets[range.get().start] - offsets[pinned_count.get()])
+        }),
+        width_after: Signal::derive(move || {
+            column_offsets.with(|offsets| offsets[offsets.len() - 1] - offsets[range.get().end])
+        }),
+        total_width: Signal::derive(move || {
+            column_offsets.with(|offsets| offsets[offsets.len() - 1])
+        }),
+    }
+}
+
+/// Options for [`use_column_window`].
+#[derive(Debug, Clone, DefaultBuilder)]
+pub struct UseColumnWindowOptions {
+    /// How many columns to render before and after the visible columns.
+    ///
+    /// Defaults to 2.
+    overscan_column_count: usize,
+
+    /// How many leading columns are pinned, i.e. always rendered and not scrolled horizontally.
+    ///
+    /// Defaults to 0.
+    pinned_column_count: usize,
+}
+
+impl Default for UseColumnWindowOptions {
+    fn default() -> Self {
+        Self {
+            overscan_column_count: 2,
+            pinned_column_count: 0,
+        }
+    }
+}
+
+/// Return type of [`use_column_window`].
+#[derive(Debug, Clone, Copy)]
+pub struct ColumnWindow {
+    /// The range of column indices to render. This never includes pinned columns.
+    pub range: Signal<Range<usize>>,
+
+    /// The range of pinned column indices. These are always rendered.
+    pub pinned_range: Signal<Range<usize>>,
+
+    /// The `left` offsets to use for the sticky positioning of the pinned columns.
+    pub pinned_left: Signal<Vec<f64>>,
+
+    /// The total width of all pinned columns.
+    pub pinned_width: Signal<f64>,
+
+    /// The total width of all non-pinned columns before [`range`](Self::range).
+    pub width_before: Signal<f64>,
+
+    /// The total width of all columns after [`range`](Self::range).
+    pub width_after: Signal<f64>,
+
+    /// The total width of all columns.
+    pub total_width: Signal<f64>,
+}
+
+/// Returns the start offsets of all columns followed by the total width.
+///
+/// The returned `Vec` has a length of `column_count + 1`.
+pub fn column_offsets(column_count: usize, column_width: impl Fn(usize) -> f64) -> Vec<f64> {
+    let mut offsets = Vec::with_capacity(column_count + 1);
+    let mut offset = 0.0;
+
+    offsets.push(offset);
+    for index in 0..column_count {
+        offset += column_width(index).max(0.0);
+        offsets.push(offset);
+    }
+
+    offsets
+}
+
+/// Returns the range of columns that are (partially) visible in the viewport, extended by `overscan` columns
+/// on both sides.
+///
+/// `offsets` are the column offsets as returned by [`column_offsets`].
+/// The first `pinned` columns are always visible and cover the start of the viewport. They are not part of
+/// the returned range.
+pub fn visible_column_range(
+    offsets: &[f64],
+    scroll_left: f64,
+    viewport_width: f64,
+    overscan: usize,
+    pinned: usize,
+) -> Range<usize> {
+    let column_count = offsets.len().saturating_sub(1);
+    let pinned = pinned.min(column_count);
+    if column_count == pinned {
+        return pinned..pinned;
+    }
+
+    // the pinned columns cover the start of the viewport
+    let viewport_start = scroll_left + offsets[pinned];
+    let viewport_end = scroll_left + viewport_width;
+
+    // first column that ends after the start of the viewport
+    let start = offsets[1..]
+        .partition_point(|&end| end <= viewport_start)
+        .clamp(pinned, column_count);
+    // first column that starts at or after the end of the viewport
+    let end = offsets[..column_count].partition_point(|&start| start < viewport_end);
+
+    let end = end.max(start).saturating_add(overscan).min(column_count);
+    let start = start.saturating_sub(overscan).max(pinned);
+
+    start..end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_visible_column_range() {
+        let offsets = column_offsets(10, |_| 100.0);
+
+        assert_eq!(offsets.len(), 11);
+        assert_eq!(offsets[10], 1000.0);
+
+        assert_eq!(visible_column_range(&offsets, 0.0, 250.0, 0, 0), 0..3);
+        assert_eq!(visible_column_range(&offsets, 100.0, 200.0, 0, 0), 1..3);
+        assert_eq!(visible_column_range(&offsets, 150.0, 200.0, 0, 0), 1..4);
+        assert_eq!(visible_column_range(&offsets, 150.0, 200.0, 2, 0), 0..6);
+        assert_eq!(visible_column_range(&offsets, 900.0, 500.0, 1, 0), 8..10);
+        assert_eq!(visible_column_range(&offsets, 0.0, 0.0, 0, 0), 0..0);
+        assert_eq!(visible_column_range(&[0.0], 0.0, 100.0, 2, 0), 0..0);
+    }
+
+    #[test]
+    fn test_visible_column_range_pinned() {
+        let offsets = column_offsets(10, |_| 100.0);
+
+        assert_eq!(visible_column_range(&offsets, 0.0, 350.0, 0, 1), 1..4);
+        // columns 1 and 2 are scrolled below the pinned column 0
+        assert_eq!(visible_column_range(&offsets, 200.0, 350.0, 0, 1), 3..6);
+        assert_eq!(visible_column_range(&offsets, 200.0, 350.0, 5, 1), 1..10);
+        assert_eq!(visible_column_range(&offsets, 0.0, 350.0, 0, 20), 10..10);
+    }
+}
@@ -0,0 +1,33 @@
+/// Version of the interface between this crate and the facade crates leptos-pagination and
+/// leptos-virtualization.
+///
+/// It's increased whenever the loader traits, their marker types or the cache change in a way
+/// that the facade crates have to be updated for. The facade crates check it at compile time with
+/// [`assert_compatible`].
+pub const COMPAT_VERSION: u32 = 1;
+
+/// Fails to compile with a readable message if `version` isn't this crate's [`COMPAT_VERSION`].
+///
+/// The facade crates compare the version they are written against with the [`COMPAT_VERSION`] of
+/// the leptos-windowing they are compiled with and expose it as `WINDOWING_COMPAT_VERSION`.
+///
+/// If two versions of leptos-windowing end up in your dependency tree, loaders implemented against
+/// one of them don't work with the components of the other and the compiler reports confusing
+/// errors about unsatisfied trait bounds. If you depend on leptos-windowing directly next to
+/// leptos-pagination or leptos-virtualization, add this check to find out right away:
+///
+/// ```ignore
+/// const _: () =
+///     leptos_windowing::assert_compatible(leptos_pagination::WINDOWING_COMPAT_VERSION);
+/// ```
+///
+/// With leptos-virtualization it's `leptos_virtualization::WINDOWING_COMPAT_VERSION` instead.
+pub const fn assert_compatible(version: u32) {
+    if version != COMPAT_VERSION {
+        panic!(
+            "Mismatching versions of leptos-windowing in the dependency tree. \
+             Update leptos-windowing, leptos-pagination and leptos-virtualization to matching \
+             versions (see `cargo tree -i leptos-windowing`)."
+        );
+    }
+}
@@ -0,0 +1,106 @@
+use std::ops::Range;
+
+use crate::{LoadedItems, strict::misuse};
+
+/// Reports every way in which `loaded` breaks the contract of the loader traits as a misuse.
+///
+/// Only called in debug builds. Without this, a loader that returns inconsistent ranges or counts
+/// leaves items loading forever or writes them to the wrong rows without any hint why.
+pub(crate) fn warn_on_violations<T>(
+    requested: &Range<usize>,
+    chunk_size: Option<usize>,
+    loaded: &LoadedItems<T>,
+    item_count: Option<usize>,
+) {
+    for violation in violations(
+        requested,
+        chunk_size,
+        &loaded.range,
+        loaded.items.len(),
+        item_count,
+    ) {
+        misuse!("Loader contract violated: {violation}");
+    }
+}
+
+/// Checks that a loader that returned `loaded_len` items for `loaded_range` after `requested`
+/// was requested kept its contract.
+fn violations(
+    requested: &Range<usize>,
+    chunk_size: Option<usize>,
+    loaded_range: &Range<usize>,
+    loaded_len: usize,
+    item_count: Option<usize>,
+) -> Vec<String> {
+    let mut violations = Vec::new();
+
+    // Loaders with a fixed chunk size load whole chunks
+    let allowed = match chunk_size {
+        Some(chunk_size) if chunk_size > 0 => {
+            (requested.start / chunk_size) * chunk_size
+                ..requested.end.div_ceil(chunk_size) * chunk_size
+        }
+        _ => requested.clone(),
+    };
+
+    // Past the end of the data some loaders return an empty range at the item count
+    if !loaded_range.is_empty()
+        && (loaded_range.start < allowed.start || loaded_range.end > allowed.end)
+    {
+        violations.push(format!(
+            "returned the range {loaded_range:?} although {requested:?} was requested. \
+             The range has to lie within the requested one."
+        ));
+    }
+
+    if loaded_len > loaded_range.len() {
+        violations.push(format!(
+            "returned {loaded_len} items for the range {loaded_range:?}. \
+             `range.len()` has to be equal to `items.len()`."
+        ));
+    }
+
+    if let Some(item_count) = item_count {
+        let expected_end = loaded_range.end.min(item_count);
+
+        if loaded_range.start + loaded_len > item_count {
+            violations.push(format!(
+                "returned items up to index {} although the item count is {item_count}. \
+                 The item count has to be stable for a query.",
+                loaded_range.start + loaded_len
+            ));
+        } else if loaded_range.start + loaded_len < expected_end {
+            violations.push(format!(
+                "returned {loaded_len} items for the range {loaded_range:?} although the item \
+                 count is {item_count}. The items {:?} will never finish loading.",
+                loaded_range.start + loaded_len..expected_end
+            ));
+        }
+    }
+
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_violations() {
+        // Fine
+        assert!(violations(&(0..10), None, &(0..10), 10, Some(100)).is_empty());
+        assert!(violations(&(20..30), None, &(20..30), 5, Some(25)).is_empty());
+        assert!(violations(&(20..30), None, &(20..30), 5, None).is_empty());
+        assert!(violations(&(5..15), Some(10), &(0..20), 20, None).is_empty());
+        assert!(violations(&(40..50), None, &(25..25), 0, Some(25)).is_empty());
+
+        // Outside of the requested range
+        assert_eq!(violations(&(0..10), None, &(5..15), 10, None).len(), 1);
+        // More items than the range
+        assert_eq!(violations(&(0..10), None, &(0..10), 12, None).len(), 1);
+        // Items past the item count
+        assert_eq!(violations(&(0..10), None, &(0..10), 10, Some(8)).len(), 1);
+        // Fewer items than there are
+        assert_eq!(violations(&(0..10), None, &(0..10), 8, Some(100)).len(), 1);
+    }
+}
@@ -0,0 +1,324 @@
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    hash::Hash,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use leptos::prelude::*;
+use leptos_use::use_interval_fn;
+
+/// Normalized storage of entities that can be shared between several lists.
+///
+/// Every entity is stored once by its key. Lists only hold [`Entity`] handles that point into this store,
+/// so if the same entity appears in multiple lists (e.g. orders on two screens) updating it once
+/// updates it everywhere.
+///
+/// Use [`NormalizedLoader`](crate::NormalizedLoader) to put loaded items into this store automatically.
+///
+/// Entities that aren't referenced by any list anymore can be dropped with [`EntityStore::collect_garbage`]
+/// or periodically with [`use_entity_garbage_collection`].
+pub struct EntityStore<K, T>
+where
+    K: Eq + Hash + Clone + Send + Sync + 'static,
+    T: Send + Sync + 'static,
+{
+    entities: StoredValue<HashMap<K, Arc<EntitySlot<T>>>>,
+    reclaimed_count: RwSignal<usize>,
+}
+
+impl<K, T> Clone for EntityStore<K, T>
+where
+    K: Eq + Hash + Clone + Send + Sync + 'static,
+    T: Send + Sync + 'static,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K, T> Copy for EntityStore<K, T>
+where
+    K: Eq + Hash + Clone + Send + Sync + 'static,
+    T: Send + Sync + 'static,
+{
+}
+
+impl<K, T> Default for EntityStore<K, T>
+where
+    K: Eq + Hash + Clone + Send + Sync + 'static,
+    T: Send + Sync + 'static,
+{
+    fn default() -> Self {
+        Self {
+            entities: StoredValue::new(HashMap::new()),
+            reclaimed_count: RwSignal::new(0),
+        }
+    }
+}
+
+impl<K, T> EntityStore<K, T>
+where
+    K: Eq + Hash + Clone + Send + Sync + 'static,
+    T: Send + Sync + 'static,
+{
+    /// Creates a new empty store.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Inserts the entity or updates it if an entity with the same key already exists.
+    ///
+    /// Returns the handle of the entity.
+    pub fn upsert(&self, key: K, entity: T) -> Entity<K, T> {
+        let entity = Arc::new(entity);
+
+        let slot = self
+            .entities
+            .try_update_value(|entities| {
+                Arc::clone(
+                    entities
+                        .entry(key.clone())
+                        .and_modify(|slot| slot.value.set(Arc::clone(&entity)))
+                        .or_insert_with(|| Arc::new(EntitySlot::new(Arc::clone(&entity)))),
+                )
+            })
+            .unwrap_or_else(|| Arc::new(EntitySlot::new(entity)));
+
+        Entity { key, slot }
+    }
+
+    /// Updates the entity with the given key in every list it appears in.
+    ///
+    /// Returns `false` if there is no entity with this key.
+    pub fn update(&self, key: &K, entity: T) -> bool {
+        self.entities
+            .try_with_value(|entities| {
+                entities
+                    .get(key)
+                    .map(|slot| slot.value.set(Arc::new(entity)))
+                    .is_some()
+            })
+            .unwrap_or(false)
+    }
+
+    /// Returns the handle of the entity with the given key.
+    pub fn get(&self, key: &K) -> Option<Entity<K, T>> {
+        self.entities
+            .try_with_value(|entities| {
+                entities.get(key).map(|slot| Entity {
+                    key: key.clone(),
+                    slot: Arc::clone(slot),
+                })
+            })
+            .flatten()
+    }
+
+    /// Removes the entity with the given key from the store.
+    ///
+    /// Existing handles keep their last value.
+    pub fn remove(&self, key: &K) {
+        self.entities.update_value(|entities| {
+            entities.remove(key);
+        });
+    }
+
+    /// Number of entities in the store.
+    pub fn len(&self) -> usize {
+        self.entities
+            .try_with_value(|entities| entities.len())
+            .unwrap_or_default()
+    }
+
+    /// True if there are no entities in the store.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drops the entities that aren't referenced by any [`Entity`] handle anymore, i.e. that don't appear in
+    /// any live list.
+    ///
+    /// An entity is only dropped if it was already unreferenced during the previous call.
+    /// This gives a grace period of one collection interval, so navigating from one route to another
+    /// that shows the same entities doesn't drop them in between.
+    ///
+    /// Returns the number of dropped entities.
+    pub fn collect_garbage(&self) -> usize {
+        let reclaimed = self
+            .entities
+            .try_update_value(|entities| {
+                let len_before = entities.len();
+
+                entities.retain(|_, slot| {
+                    // The store itself holds one reference
+                    if Arc::strong_count(slot) > 1 {
+                        slot.unreferenced.store(false, Ordering::Relaxed);
+                        true
+                    } else {
+                        !slot.unreferenced.swap(true, Ordering::Relaxed)
+                    }
+                });
+
+                len_before - entities.len()
+            })
+            .unwrap_or_default();
+
+        if reclaimed > 0 {
+            self.reclaimed_count.update(|count| *count += reclaimed);
+        }
+
+        reclaimed
+    }
+
+    /// The total number of entities that have been dropped by [`EntityStore::collect_garbage`].
+    pub fn reclaimed_count(&self) -> Signal<usize> {
+        self.reclaimed_count.into()
+    }
+}
+
+/// Periodically drops the entities of `store` that aren't displayed in any list anymore.
+///
+/// Every `grace_period_ms` milliseconds [`EntityStore::collect_garbage`] is called. This means that an
+/// entity is dropped after it has been unreferenced for at least `grace_period_ms` milliseconds.
+///
+/// This keeps long-lived single page apps from accumulating memory when the user navigates between routes.
+/// The number of dropped entities is available via [`EntityStore::reclaimed_count`].
+pub fn use_entity_garbage_collection<K, T>(
+    store: EntityStore<K, T>,
+    grace_period_ms: impl Into<Signal<u64>>,
+) where
+    K: Eq + Hash + Clone + Send + Sync + 'static,
+    T: Send + Sync + 'static,
+{
+    let _ = use_interval_fn(
+        move || {
+            store.collect_garbage();
+        },
+        grace_period_ms,
+    );
+}
+
+struct EntitySlot<T>
+where
+    T: Send + Sync + 'static,
+{
+    value: ArcRwSignal<Arc<T>>,
+    unreferenced: AtomicBool,
+}
+
+impl<T> EntitySlot<T>
+where
+    T: Send + Sync + 'static,
+{
+    fn new(value: Arc<T>) -> Self {
+        Self {
+            value: ArcRwSignal::new(value),
+            unreferenced: AtomicBool::new(false),
+        }
+    }
+}
+
+/// Handle to an entity in an [`EntityStore`].
+///
+/// Reading the entity is reactive, so views update when the entity is updated in the store.
+pub struct Entity<K, T>
+where
+    T: Send + Sync + 'static,
+{
+    key: K,
+    slot: Arc<EntitySlot<T>>,
+}
+
+impl<K, T> Clone for Entity<K, T>
+where
+    K: Clone,
+    T: Send + Sync + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            key: self.key.clone(),
+            slot: Arc::clone(&self.slot),
+        }
+    }
+}
+
+impl<K, T> Debug for Entity<K, T>
+where
+    K: Debug,
+    T: Send + Sync + 'static,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Entity").field(&self.key).finish()
+    }
+}
+
+impl<K, T> Entity<K, T>
+where
+    T: Send + Sync + 'static,
+{
+    /// The key of the entity.
+    #[inline]
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// The current value of the entity. This is tracked.
+    #[inline]
+    pub fn get(&self) -> Arc<T> {
+        self.slot.value.get()
+    }
+
+    /// The current value of the entity. This is not tracked.
+    #[inline]
+    pub fn get_untracked(&self) -> Arc<T> {
+        self.slot.value.get_untracked()
+    }
+
+    /// Replaces the entity in the store and thereby in all lists.
+    #[inline]
+    pub fn set(&self, entity: T) {
+        self.slot.value.set(Arc::new(entity));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_upsert_shares_entities() {
+        let store = EntityStore::<u32, &'static str>::new();
+
+        let first = store.upsert(1, "first");
+        let second = store.upsert(1, "updated");
+
+        assert_eq!(store.len(), 1);
+        assert_eq!(*first.get_untracked(), "updated");
+
+        assert!(store.update(&1, "again"));
+        assert!(!store.update(&2, "missing"));
+        assert_eq!(*second.get_untracked(), "again");
+    }
+
+    #[test]
+    fn test_collect_garbage() {
+        let store = EntityStore::<u32, &'static str>::new();
+
+        let kept = store.upsert(1, "kept");
+        drop(store.upsert(2, "dropped"));
+
+        // the first collection only marks the unreferenced entity
+        assert_eq!(store.collect_garbage(), 0);
+        assert_eq!(store.len(), 2);
+
+        assert_eq!(store.collect_garbage(), 1);
+        assert_eq!(store.len(), 1);
+        assert_eq!(store.reclaimed_count().get_untracked(), 1);
+        assert_eq!(store.get(&1).map(|e| *e.get_untracked()), Some("kept"));
+
+        drop(kept);
+    }
+}
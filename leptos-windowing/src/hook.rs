@@ -1,8 +1,12 @@
-use std::{fmt::Debug, ops::Range};
+use std::{fmt::Debug, ops::Range, sync::Arc, time::Duration};
 
+use default_struct_builder::DefaultBuilder;
 use leptos::prelude::*;
 
-use crate::{InternalLoader, ItemWindow, cache::Cache};
+use crate::{
+    AdaptiveChunkSize, InternalLoader, ItemWindow, RetryPolicy, Spawner,
+    cache::{Cache, ItemKey},
+};
 
 /// Load items on demand and cache them.
 ///
@@ -14,6 +18,7 @@ use crate::{InternalLoader, ItemWindow, cache::Cache};
 /// - `display_range`: A signal of the range of items to display. This will be used for the returned `ItemWindow`.
 /// - `loader`: The loader to use for loading items.
 /// - `query`: A signal of the query to use for loading items.
+/// - `options`: Additional options. See [`UseLoadOnDemandOptions`].
 ///
 /// ## Returns
 ///
@@ -30,6 +35,7 @@ pub fn use_load_on_demand<T, L, Q, E, M>(
     range_to_display: impl Into<Signal<Range<usize>>>,
     loader: L,
     query: impl Into<Signal<Q>>,
+    options: UseLoadOnDemandOptions<T>,
 ) -> UseLoadOnDemandResult<T, E>
 where
     T: Send + Sync + 'static,
@@ -37,37 +43,121 @@ where
     Q: Send + Sync + 'static,
     E: Send + Sync + Debug + 'static,
 {
+    let UseLoadOnDemandOptions {
+        cache,
+        retry,
+        abort_stale_loads,
+        max_cached_items,
+        ttl,
+        error_ttl,
+        item_key,
+        stale_while_revalidate,
+        load_display_range_first,
+        adaptive_chunk_size,
+        suspense,
+        keep_previous_data,
+        spawner,
+        tags,
+    } = options;
+
     #[cfg(not(feature = "ssr"))]
     {
-        use leptos::task::spawn_local;
+        use futures::{
+            StreamExt,
+            stream::{AbortHandle, Abortable},
+        };
         use leptos_use::{WatchPausableReturn, watch_pausable};
 
+        use crate::{
+            item_state::ItemError,
+            spawner::{resolve_spawner, spawn_with},
+            strict::misuse,
+            tags::register_tags,
+        };
+
         let range_to_load = range_to_load.into();
         let range_to_display = range_to_display.into();
 
-        let cached_range_to_display = RwSignal::new(0..0);
+        let spawner = resolve_spawner(spawner);
+
+        let mut cache = cache.unwrap_or_else(Cache::new);
+        if item_key.is_some() {
+            cache.set_item_key(item_key);
+        }
 
-        let mut cache = Cache::new();
+        let cached_range_to_display = RwSignal::new(untrack(|| {
+            initial_display_range(cache, range_to_display.get())
+        }));
 
         let loader = StoredValue::new_local(loader);
         let query = query.into();
 
-        let item_count_result = RwSignal::new(Ok(None));
+        let item_count_result = RwSignal::new(Ok(cache.item_count().get_untracked()));
+        let is_item_count_loading = RwSignal::new(true);
+
+        // Waiting for the loads with `suspense`. They're woken up whenever a load finishes.
+        let settle_waiters = StoredValue::new(Vec::<futures::channel::oneshot::Sender<()>>::new());
+        let wake_settle_waiters = move || {
+            for waiter in settle_waiters
+                .try_update_value(std::mem::take)
+                .unwrap_or_default()
+            {
+                let _ = waiter.send(());
+            }
+        };
 
         let set_item_count = move |count: Result<Option<usize>, E>| {
-            cache
-                .item_count()
-                .set(count.as_ref().ok().flatten().copied());
+            cache.set_item_count(count.as_ref().ok().flatten().copied());
             item_count_result.set(count);
         };
 
         let reload_counter = RwSignal::new(0_usize);
 
-        // Clear cache
-        Effect::new(move || {
-            query.track();
+        let in_flight_loads = StoredValue::new(InFlightLoads::default());
+
+        let chunk_size = RwSignal::new(
+            adaptive_chunk_size.map(|adaptive| adaptive.clamp(adaptive.initial, L::CHUNK_SIZE)),
+        );
+        // Incremented whenever the chunk size is adapted. Only the first load that finishes of
+        // the ones requested with the same chunk size adapts it, so a batch of chunks that are
+        // loaded in parallel doesn't double or halve it once per chunk.
+        let chunk_size_generation = StoredValue::new(0_usize);
+
+        // While this is set only the display range is loaded so the visible items arrive sooner.
+        let is_display_range_loading_first = RwSignal::new(load_display_range_first);
+        let end_display_range_first = move || {
+            let is_idle = in_flight_loads
+                .try_with_value(|loads| loads.loads.is_empty())
+                .unwrap_or(true);
+
+            if is_idle && is_display_range_loading_first.get_untracked() {
+                is_display_range_loading_first.set(false);
+            }
+        };
+
+        let reload = move || {
+            // Their results would be discarded anyway
+            for load in in_flight_loads
+                .try_update_value(InFlightLoads::take_all)
+                .unwrap_or_default()
+            {
+                load.abort_handle.abort();
+            }
+
             cache.clear();
             reload_counter.update(|counter| *counter = counter.wrapping_add(1));
+        };
+
+        // Clear cache
+        Effect::new(move |prev: Option<()>| {
+            query.track();
+
+            // A shared cache might already contain items for this query when this window is created.
+            if prev.is_some() {
+                reload();
+            } else {
+                reload_counter.update(|counter| *counter = counter.wrapping_add(1));
+            }
         });
 
         // Load item count
@@ -76,21 +166,243 @@ where
 
             reload_counter.track();
 
-            spawn_local(async move {
+            is_item_count_loading.set(true);
+
+            spawn_with(spawner, async move {
                 let latest_reload_count = reload_counter.try_get_untracked();
 
-                let count = loader
-                    .read_value()
-                    .item_count(&*query.read_untracked())
-                    .await;
+                let mut attempt = 0;
+                let count = loop {
+                    attempt += 1;
+
+                    let count = loader
+                        .read_value()
+                        .item_count(&*query.read_untracked())
+                        .await;
+
+                    if count.is_err()
+                        && retry.should_retry(attempt)
+                        && latest_reload_count == reload_counter.try_get_untracked()
+                    {
+                        sleep(retry.delay(attempt, crate::cache::random_jitter())).await;
+                    } else {
+                        break count;
+                    }
+                };
 
                 // make sure the loaded count is still valid
                 if latest_reload_count == reload_counter.try_get_untracked() {
                     set_item_count(count);
+                    is_item_count_loading.set(false);
+                    wake_settle_waiters();
+                }
+            });
+        });
+
+        // Apply live updates
+        Effect::new(move || {
+            // Subscribe again with the new query which triggers cache invalidation which triggers reload_trigger
+            reload_counter.track();
+
+            let Some(updates) = loader.read_value().updates(&*query.read_untracked()) else {
+                return;
+            };
+
+            let (abort_handle, abort_registration) = AbortHandle::new_pair();
+            on_cleanup(move || abort_handle.abort());
+
+            spawn_with(spawner, async move {
+                let mut updates = Abortable::new(updates, abort_registration);
+
+                while let Some(update) = updates.next().await {
+                    cache.apply_live_update(update);
                 }
             });
         });
 
+        let evict_far_items = move || {
+            if let Some(max_cached_items) = max_cached_items {
+                cache.evict_far_from(range_to_load.get_untracked(), max_cached_items);
+            }
+        };
+
+        // Loads the range and writes the result into the cache when it arrives.
+        let spawn_load = move |missing_range: Range<usize>, abortable: bool| {
+            let (abort_handle, abort_registration) = AbortHandle::new_pair();
+            let id = in_flight_loads
+                .try_update_value(|loads| {
+                    loads.insert(missing_range.clone(), abortable, abort_handle)
+                })
+                .unwrap_or_default();
+            let generation = chunk_size_generation.get_value();
+
+            // Dropping the loader's future when aborted also aborts the request of many HTTP clients.
+            let load_future = async move {
+                let latest_reload_count = reload_counter.try_get_untracked();
+
+                // make sure the loaded data is still valid
+                let is_valid = move || latest_reload_count == reload_counter.try_get_untracked();
+
+                let mut remaining_range = missing_range.clone();
+                let mut attempt = 0;
+
+                loop {
+                    attempt += 1;
+
+                    let started_at = crate::cache::now_millis();
+
+                    let mut loaded_end = remaining_range.start;
+
+                    // Streaming loaders hand over their items in several batches.
+                    // Write each one right away so they're displayed progressively.
+                    let result = loader
+                        .read_value()
+                        .load_items_streaming(
+                            remaining_range.clone(),
+                            &*query.read_untracked(),
+                            |loaded_items| {
+                                if is_valid() {
+                                    #[cfg(debug_assertions)]
+                                    crate::contract::warn_on_violations(
+                                        &remaining_range,
+                                        L::CHUNK_SIZE,
+                                        &loaded_items,
+                                        cache.item_count().get_untracked(),
+                                    );
+
+                                    loaded_end = loaded_items.range.end;
+                                    cache.write_loaded(Ok(loaded_items), remaining_range.clone());
+                                }
+                            },
+                        )
+                        .await;
+
+                    if !is_valid() {
+                        break;
+                    }
+
+                    match result {
+                        Ok(loaded_range) => {
+                            if loaded_range.end < remaining_range.end {
+                                set_item_count(Ok(Some(loaded_range.end)));
+                            }
+                            if let Some(adaptive) = adaptive_chunk_size
+                                && chunk_size_generation.try_get_value() == Some(generation)
+                            {
+                                chunk_size_generation.set_value(generation + 1);
+
+                                let elapsed = Duration::from_secs_f64(
+                                    (crate::cache::now_millis() - started_at).max(0.0) / 1000.0,
+                                );
+                                chunk_size.update(|chunk_size| {
+                                    *chunk_size = chunk_size.map(|current| {
+                                        adaptive.next(current, elapsed, L::CHUNK_SIZE)
+                                    });
+                                });
+                            }
+                            // Prefetched items are outside of the range to load and would be
+                            // evicted right away. They're evicted by the next regular load instead.
+                            if abortable {
+                                evict_far_items();
+                            }
+                            break;
+                        }
+                        Err(e) => {
+                            // Items that arrived before the error are kept
+                            remaining_range =
+                                loaded_end.max(remaining_range.start)..missing_range.end;
+
+                            if retry.should_retry(attempt) {
+                                sleep(retry.delay(attempt, crate::cache::random_jitter())).await;
+                            } else {
+                                cache.write_loaded(Err(ItemError::new(e)), remaining_range);
+                                break;
+                            }
+                        }
+                    }
+                }
+            };
+
+            spawn_with(spawner, async move {
+                let _ = Abortable::new(load_future, abort_registration).await;
+
+                in_flight_loads.try_update_value(|loads| loads.remove(id));
+                end_display_range_first();
+                wake_settle_waiters();
+            });
+        };
+
+        // With an adaptive chunk size large ranges are requested in several loads.
+        let spawn_chunks =
+            move |range: Range<usize>, abortable: bool| match chunk_size.get_untracked() {
+                Some(chunk_size) => {
+                    for chunk in crate::chunk_size::split_into_chunks(range, chunk_size) {
+                        spawn_load(chunk, abortable);
+                    }
+                }
+                None => spawn_load(range, abortable),
+            };
+
+        // This is also called by `Cache::prefetch` from user code which might run inside an effect.
+        // Untracked so that effect doesn't subscribe to the cache that is written here.
+        // Loads started for the range to load can be aborted when it changes. Prefetches can't.
+        // Items that are already being loaded (possibly by another window sharing the cache) aren't
+        // requested again. They're filled in when that load finishes.
+        let load = move |range: Range<usize>, abortable: bool| {
+            untrack(|| {
+                for missing_range in cache.missing_ranges(range) {
+                    cache.write_loading(missing_range.clone());
+                    spawn_chunks(missing_range, abortable);
+                }
+            })
+        };
+
+        // Keeps displaying the loaded items while they're loaded again in the background.
+        // Only the range to load is refreshed right away. Everything else is loaded again on demand.
+        let revalidate = move || {
+            for load in in_flight_loads
+                .try_update_value(InFlightLoads::take_all)
+                .unwrap_or_default()
+            {
+                load.abort_handle.abort();
+                cache.cancel_loading(load.range);
+            }
+
+            reload_counter.update(|counter| *counter = counter.wrapping_add(1));
+
+            untrack(|| {
+                let range = range_to_load.get();
+                let len = cache.items().read().len();
+
+                // They're displayed until they're replaced
+                cache.mark_stale(range.clone());
+
+                cache.invalidate_range(0..range.start);
+                cache.invalidate_range(range.end..len);
+
+                let range = range.start..range.end.min(len);
+                if range.start < range.end {
+                    // So the load effect doesn't request them a second time
+                    for missing_range in cache.missing_ranges(range.clone()) {
+                        cache.write_loading(missing_range);
+                    }
+                    spawn_chunks(range, true);
+                }
+            });
+        };
+
+        let load_range = move |range: Range<usize>| load(range, true);
+
+        let abort_loads_outside = move |range: Range<usize>| {
+            for load in in_flight_loads
+                .try_update_value(|loads| loads.take_outside(&range))
+                .unwrap_or_default()
+            {
+                load.abort_handle.abort();
+                cache.cancel_loading(load.range);
+            }
+        };
+
         // Load items
         let WatchPausableReturn {
             pause,
@@ -102,68 +414,329 @@ where
                 // we don't need to track the query here because it triggers cache invalidation which triggers reload_trigger
                 reload_counter.track();
 
-                cache.track();
+                range_to_load.track();
+                range_to_display.track();
+                cache.item_count().track();
+                is_display_range_loading_first.track();
+
+                // Tracking the whole cache would rerun this for every single loaded item.
+                cache.track_missing();
             },
             move |_, _, _| {
-                let missing_range = cache.missing_range(range_to_load.get());
-
-                if let Some(missing_range) = missing_range {
-                    cache.write_loading(missing_range.clone());
+                let (load, display) = (range_to_load.get(), range_to_display.get());
+                if !display.is_empty() && (display.start < load.start || display.end > load.end) {
+                    misuse!(
+                        "The display range {display:?} isn't contained in the load range {load:?}. \
+                         Items outside of the load range might never be loaded."
+                    );
+                }
 
-                    spawn_local(async move {
-                        let latest_reload_count = reload_counter.try_get_untracked();
+                if abort_stale_loads {
+                    abort_loads_outside(range_to_load.get());
+                }
 
-                        let result = loader
-                            .read_value()
-                            .load_items(missing_range.clone(), &*query.read_untracked())
-                            .await;
+                if let Some(ttl) = ttl {
+                    cache.refresh_stale(range_to_load.get(), ttl);
+                }
 
-                        // make sure the loaded data is still valid
-                        if latest_reload_count == reload_counter.try_get_untracked() {
-                            if let Ok(loaded_items) = &result
-                                && loaded_items.range.end < missing_range.end
-                            {
-                                set_item_count(Ok(Some(loaded_items.range.end)));
-                            }
+                if let Some(error_ttl) = error_ttl.get_untracked() {
+                    cache.invalidate_expired_errors(range_to_load.get(), error_ttl);
+                }
 
-                            cache.write_loaded(result.map_err(|e| format!("{e:?}")), missing_range);
-                        }
-                    });
+                if is_display_range_loading_first.get() {
+                    load_range(range_to_display.get());
+                    // Nothing to wait for if the display range is cached already
+                    end_display_range_first();
+                } else {
+                    load_range(range_to_load.get());
                 }
 
                 // Make sure that the cache is filled and then update the display range
-                let Range { start, end } = range_to_display.get();
-                cached_range_to_display
-                    .set(start..end.min(cache.item_count().get().unwrap_or(usize::MAX)));
+                if !keep_previous_data {
+                    cached_range_to_display.set(clamp_to_item_count(
+                        range_to_display.get(),
+                        cache.item_count().get(),
+                    ));
+                }
             },
         );
 
+        // Switch to the new display range only once its items are loaded. This tracks the range to
+        // display, the item count and the loaded fraction of both ranges, which is notified once
+        // per load. `ItemWindow::is_pending` tells whether the switch is still outstanding.
+        if keep_previous_data {
+            Effect::new(move || {
+                let range = clamp_to_item_count(range_to_display.get(), cache.item_count().get());
+                let displayed_range = cached_range_to_display.get_untracked();
+
+                // Nothing worth keeping if the displayed items aren't loaded, e.g. after a reload
+                if displayed_range.is_empty()
+                    || cache.loaded_fraction(displayed_range) < 1.0
+                    || cache.loaded_fraction(range.clone()) == 1.0
+                {
+                    cached_range_to_display.set(range);
+                }
+            });
+        }
+
         cache.pause_reactive_loading = pause.into();
         cache.resume_reactive_loading = resume.into();
         cache.is_reactive_loading_active = is_active;
+        cache.reload = Callback::new(move |_| {
+            if stale_while_revalidate {
+                revalidate();
+            } else {
+                reload();
+            }
+        });
+        cache.load_range = Callback::new(move |range| load(range, false));
+        // The items stay loaded until they're replaced so they aren't requested a second time.
+        cache.refresh_range = Callback::new(move |range| spawn_chunks(range, false));
+        register_tags(tags, Callback::new(move |_| revalidate()));
+        cache.ttl = ttl;
+
+        // The display range is settled once the item count and all of its items are loaded or failed.
+        let is_settled = move || {
+            untrack(|| {
+                !is_item_count_loading.get()
+                    && cache.loaded_fraction(clamp_to_item_count(
+                        range_to_display.get(),
+                        cache.item_count().get(),
+                    )) == 1.0
+            })
+        };
+
+        // The loads themselves are still spawned so the resource doesn't have to own them. It's
+        // fetched again for every new display range so `<Transition/>` keeps the previous items.
+        let suspense = suspense.then(|| {
+            LocalResource::new(move || {
+                reload_counter.track();
+                range_to_display.track();
+
+                async move {
+                    while !is_settled() {
+                        let (waiter, settled) = futures::channel::oneshot::channel();
+                        if settle_waiters
+                            .try_update_value(|waiters| waiters.push(waiter))
+                            .is_none()
+                        {
+                            // The window has been disposed
+                            return;
+                        }
+                        let _ = settled.await;
+                    }
+                }
+            })
+        });
 
         UseLoadOnDemandResult {
             item_count_result: item_count_result.into(),
             item_window: ItemWindow {
                 cache,
                 range: cached_range_to_display.into(),
+                overflow_past_end: Memo::new(move |_| {
+                    cache
+                        .item_count()
+                        .get()
+                        .is_some_and(|item_count| range_to_display.get().end > item_count)
+                })
+                .into(),
+                is_pending: Memo::new(move |_| {
+                    keep_previous_data
+                        && cached_range_to_display.get()
+                            != clamp_to_item_count(range_to_display.get(), cache.item_count().get())
+                })
+                .into(),
+                suspense,
+                loaded_fraction: Memo::new(move |_| {
+                    cache.loaded_fraction(cached_range_to_display.get())
+                })
+                .into(),
+                is_loading: Memo::new(move |_| cache.is_loading(cached_range_to_display.get()))
+                    .into(),
             },
+            chunk_size: chunk_size.into(),
         }
     }
 
     #[cfg(feature = "ssr")]
     {
         let _ = range_to_load;
-        let _ = range_to_display;
         let _ = loader;
         let _ = query;
+        let _ = retry;
+        let _ = abort_stale_loads;
+        let _ = max_cached_items;
+        let _ = ttl;
+        let _ = error_ttl;
+        let _ = item_key;
+        let _ = stale_while_revalidate;
+        let _ = load_display_range_first;
+        let _ = keep_previous_data;
+        let _ = spawner;
+        let _ = tags;
+
+        // Nothing is loaded on the server. Only items that are already in the cache, e.g. from
+        // `use_server_prefill`, are rendered.
+        let cache = cache.unwrap_or_else(Cache::new);
+        let range_to_display = range_to_display.into();
 
         UseLoadOnDemandResult {
-            item_count_result: Signal::stored(Ok(None)),
+            item_count_result: Signal::derive(move || Ok(cache.item_count().get())),
             item_window: ItemWindow {
-                cache: Cache::new(),
-                range: Signal::stored(0..0),
+                cache,
+                range: Signal::derive(move || initial_display_range(cache, range_to_display.get())),
+                overflow_past_end: Signal::derive(move || {
+                    cache
+                        .item_count()
+                        .get()
+                        .is_some_and(|item_count| range_to_display.get().end > item_count)
+                }),
+                is_pending: Signal::stored(false),
+                // Local resources are never loaded on the server so this renders the fallback of
+                // the `<Suspense/>` like the first render during hydration.
+                suspense: suspense.then(|| LocalResource::new(|| async {})),
+                loaded_fraction: Signal::derive(move || {
+                    cache.loaded_fraction(initial_display_range(cache, range_to_display.get()))
+                }),
+                is_loading: Signal::stored(false),
             },
+            chunk_size: Signal::stored(
+                adaptive_chunk_size.map(|adaptive| adaptive.clamp(adaptive.initial, L::CHUNK_SIZE)),
+            ),
+        }
+    }
+}
+
+/// Options for [`use_load_on_demand`].
+#[derive(DefaultBuilder)]
+pub struct UseLoadOnDemandOptions<T>
+where
+    T: Send + Sync + 'static,
+{
+    /// Use this cache instead of creating a new one.
+    ///
+    /// This is used to share one cache between several windows. See [`WindowLink`](crate::WindowLink).
+    /// Defaults to `None`.
+    #[builder(keep_type)]
+    cache: Option<Cache<T>>,
+
+    /// How failed loads of items and of the item count are retried.
+    ///
+    /// While retrying, the items stay in the loading state.
+    /// Defaults to [`RetryPolicy::none()`].
+    retry: RetryPolicy,
+
+    /// Abort the loads that don't overlap the range to load anymore when it changes, for example
+    /// when flipping through pages or scrolling quickly. Their items become missing again.
+    ///
+    /// The loader's future is dropped which also aborts the request for many HTTP clients.
+    /// Prefetches with [`Cache::prefetch`] are never aborted.
+    /// Defaults to `true`.
+    abort_stale_loads: bool,
+
+    /// The maximum number of loaded items to keep in the cache.
+    ///
+    /// After every load the items farthest away from the range to load are evicted until the limit
+    /// is met. They're loaded again when they're needed. This bounds the memory used by very long lists.
+    /// When the cache is shared with [`WindowLink`](crate::WindowLink), the limit applies to the
+    /// whole cache so it should be larger than the ranges to load of all windows together.
+    /// Defaults to `None` which keeps all items.
+    max_cached_items: Option<usize>,
+
+    /// How long loaded items stay fresh. Stale items are loaded again the next time they enter
+    /// the range to load while they stay displayed. See [`Cache::refresh_stale`]. This is useful
+    /// for data that changes frequently like dashboards.
+    /// Defaults to `None` which means items never become stale.
+    ttl: Option<Duration>,
+
+    /// How long items that failed to load keep their error. Afterwards they are loaded again the
+    /// next time they enter the range to load, so transient failures heal when scrolling or paging
+    /// back to them. Use [`Cache::retry_all_failed`] to do this manually. Changes apply from the
+    /// next change of the range to load.
+    /// Defaults to `None` which keeps the errors until the cache is cleared.
+    #[builder(into)]
+    error_ttl: Signal<Option<Duration>>,
+
+    /// Determines the identity of the items so that items that shifted in the data source
+    /// between loads aren't displayed twice. See [`ItemKey`].
+    /// Defaults to `None` which identifies items only by their index.
+    #[builder(keep_type)]
+    item_key: Option<ItemKey<T>>,
+
+    /// Keep displaying the loaded items when the cache is reloaded with [`Cache::reload`] and
+    /// replace them once they're loaded again in the background. Otherwise the whole cache is
+    /// cleared and the items are shown as loading until they arrive.
+    ///
+    /// Changing the query always clears the cache.
+    /// Defaults to `false`.
+    stale_while_revalidate: bool,
+
+    /// On the first load, request the items of the display range before the rest of the range to
+    /// load. The visible items then don't wait for the larger request that includes the overscan.
+    /// Defaults to `true`.
+    load_display_range_first: bool,
+
+    /// Request missing items in chunks whose size adapts to how long the loads take.
+    /// See [`AdaptiveChunkSize`]. The current size is returned as
+    /// [`UseLoadOnDemandResult::chunk_size`].
+    /// Defaults to `None` which requests every missing range at once.
+    adaptive_chunk_size: Option<AdaptiveChunkSize>,
+
+    /// Track the loading of the display range with a [`LocalResource`] so the window participates
+    /// in `<Suspense/>` and `<Transition/>`. See [`ItemWindow::suspense`].
+    ///
+    /// The resource resolves once the item count and all items of the display range are loaded
+    /// or failed. It is fetched again when the display range changes or the cache is reloaded.
+    /// Local resources aren't loaded on the server. To render the first page on the server
+    /// use [`use_server_prefill`](crate::use_server_prefill).
+    ///
+    /// Of the list components only `PaginatedFor` reads the resource. With the other lists or
+    /// your own components read [`ItemWindow::suspense`] inside of the `<Suspense/>` yourself.
+    /// Defaults to `false`.
+    suspense: bool,
+
+    /// Keep displaying the items of the previous display range until the items of the new one are
+    /// loaded, e.g. when changing the page. Meanwhile [`ItemWindow::is_pending`] is `true`.
+    ///
+    /// If the displayed items aren't loaded themselves, e.g. after changing the query, the new
+    /// range is displayed right away.
+    /// Defaults to `false`.
+    keep_previous_data: bool,
+
+    /// Runs the loads. See [`Spawner`].
+    /// Defaults to `None` which uses the spawner installed with
+    /// [`provide_spawner`](crate::provide_spawner) or Leptos' `spawn_local`.
+    #[builder(keep_type)]
+    spawner: Option<Arc<dyn Spawner>>,
+
+    /// Invalidation tags of the data of this window like `"customers"`. Calling
+    /// [`invalidate_tag`](crate::invalidate_tag) with one of them reloads the window while the
+    /// loaded items stay displayed.
+    /// Defaults to no tags.
+    tags: Vec<String>,
+}
+
+impl<T> Default for UseLoadOnDemandOptions<T>
+where
+    T: Send + Sync + 'static,
+{
+    fn default() -> Self {
+        Self {
+            cache: None,
+            retry: RetryPolicy::none(),
+            abort_stale_loads: true,
+            max_cached_items: None,
+            ttl: None,
+            error_ttl: Signal::stored(None),
+            item_key: None,
+            stale_while_revalidate: false,
+            load_display_range_first: true,
+            adaptive_chunk_size: None,
+            suspense: false,
+            keep_previous_data: false,
+            spawner: None,
+            tags: Vec::new(),
         }
     }
 }
@@ -176,6 +749,9 @@ where
 {
     pub item_count_result: Signal<Result<Option<usize>, E>>,
     pub item_window: ItemWindow<T>,
+    /// The current number of items per request if [`UseLoadOnDemandOptions::adaptive_chunk_size`]
+    /// is set.
+    pub chunk_size: Signal<Option<usize>>,
 }
 
 impl<T, E> Clone for UseLoadOnDemandResult<T, E>
@@ -194,3 +770,122 @@ where
     E: Send + Sync + Debug + 'static,
 {
 }
+
+/// The loads that are currently running.
+#[cfg(not(feature = "ssr"))]
+#[derive(Default)]
+struct InFlightLoads {
+    next_id: usize,
+    loads: Vec<InFlightLoad>,
+}
+
+#[cfg(not(feature = "ssr"))]
+struct InFlightLoad {
+    id: usize,
+    range: Range<usize>,
+    abortable: bool,
+    abort_handle: futures::stream::AbortHandle,
+}
+
+#[cfg(not(feature = "ssr"))]
+impl InFlightLoads {
+    fn insert(
+        &mut self,
+        range: Range<usize>,
+        abortable: bool,
+        abort_handle: futures::stream::AbortHandle,
+    ) -> usize {
+        let id = self.next_id;
+        self.next_id = self.next_id.wrapping_add(1);
+
+        self.loads.push(InFlightLoad {
+            id,
+            range,
+            abortable,
+            abort_handle,
+        });
+
+        id
+    }
+
+    fn remove(&mut self, id: usize) {
+        self.loads.retain(|load| load.id != id);
+    }
+
+    fn take_all(&mut self) -> Vec<InFlightLoad> {
+        std::mem::take(&mut self.loads)
+    }
+
+    /// Removes and returns the abortable loads that don't overlap `range`.
+    fn take_outside(&mut self, range: &Range<usize>) -> Vec<InFlightLoad> {
+        let (outside, inside) = std::mem::take(&mut self.loads)
+            .into_iter()
+            .partition(|load| {
+                load.abortable && (load.range.end <= range.start || load.range.start >= range.end)
+            });
+        self.loads = inside;
+
+        outside
+    }
+}
+
+/// Limits `range` to the items that exist once the item count is known, so no placeholders are
+/// displayed past the end of the data, e.g. on a last page that isn't full.
+fn clamp_to_item_count(range: Range<usize>, item_count: Option<usize>) -> Range<usize> {
+    let end = range.end.min(item_count.unwrap_or(usize::MAX));
+
+    range.start.min(end)..end
+}
+
+/// The display range before anything has been loaded: All of `range` if it's already in the
+/// cache, e.g. from [`use_server_prefill`](crate::use_server_prefill), otherwise nothing.
+///
+/// The server and the first render in the browser both use this so that hydration matches.
+fn initial_display_range<T>(cache: Cache<T>, range: Range<usize>) -> Range<usize>
+where
+    T: Send + Sync + 'static,
+{
+    let range = clamp_to_item_count(range, cache.item_count().get());
+
+    if !range.is_empty() && cache.loaded_fraction(range.clone()) == 1.0 {
+        range
+    } else {
+        0..0
+    }
+}
+
+/// Resolves after `duration`. Without a duration it resolves right away without a timer.
+#[cfg(not(feature = "ssr"))]
+pub(crate) async fn sleep(duration: std::time::Duration) {
+    if duration.is_zero() {
+        return;
+    }
+
+    let (tx, rx) = futures::channel::oneshot::channel();
+
+    set_timeout(
+        move || {
+            let _ = tx.send(());
+        },
+        duration,
+    );
+
+    let _ = rx.await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clamp_to_item_count() {
+        // Last page with 25 items and 10 items per page
+        assert_eq!(clamp_to_item_count(20..30, Some(25)), 20..25);
+        assert_eq!(clamp_to_item_count(10..20, Some(25)), 10..20);
+        // Unknown item count
+        assert_eq!(clamp_to_item_count(20..30, None), 20..30);
+        // Page past the end of the data
+        assert_eq!(clamp_to_item_count(20..30, Some(15)), 15..15);
+        assert_eq!(clamp_to_item_count(0..10, Some(0)), 0..0);
+    }
+}
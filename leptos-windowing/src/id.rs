@@ -0,0 +1,89 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+
+use leptos::prelude::*;
+
+/// Returns a new id like `"{prefix}-3"` for an element that has to be referenced by other elements,
+/// for example with `aria-controls`.
+///
+/// The ids are counted per app, i.e. per root reactive owner. Because the server and the browser
+/// create the components in the same order, the same ids are generated in both places and
+/// hydration doesn't break. The server starts counting from the beginning for every request.
+///
+/// Call this in the body of a component and not inside of a reactive closure. Otherwise the order
+/// of the generated ids can differ between the server and the browser.
+///
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos_windowing::use_unique_id;
+/// #
+/// # #[component]
+/// # pub fn App() -> impl IntoView {
+/// let panel_id = use_unique_id("panel");
+///
+/// view! {
+///     <button aria-controls=panel_id.clone()>"Toggle"</button>
+///     <div id=panel_id>"Content"</div>
+/// }
+/// # }
+/// ```
+pub fn use_unique_id(prefix: &str) -> String {
+    let id = match Owner::current() {
+        Some(owner) => root_id_counter(owner).next(),
+        // Without an owner there is no app to count in
+        None => FALLBACK_ID_COUNTER.fetch_add(1, Ordering::Relaxed),
+    };
+
+    format!("{prefix}-{id}")
+}
+
+static FALLBACK_ID_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Clone, Default)]
+struct IdCounter(Arc<AtomicUsize>);
+
+impl IdCounter {
+    fn next(&self) -> usize {
+        self.0.fetch_add(1, Ordering::Relaxed)
+    }
+}
+
+/// The counter is stored in the context of the root owner so it's shared by the whole app.
+fn root_id_counter(owner: Owner) -> IdCounter {
+    let mut root = owner;
+    while let Some(parent) = root.parent() {
+        root = parent;
+    }
+
+    root.with(|| {
+        use_context::<IdCounter>().unwrap_or_else(|| {
+            let counter = IdCounter::default();
+            provide_context(counter.clone());
+            counter
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ids_are_counted_per_root() {
+        let generate = || {
+            let root = Owner::new();
+            root.with(|| {
+                let first = use_unique_id("list");
+                let second = root.child().with(|| use_unique_id("list"));
+
+                (first, second)
+            })
+        };
+
+        assert_eq!(generate(), ("list-0".to_string(), "list-1".to_string()));
+        // Another request on the server generates the same ids.
+        assert_eq!(generate(), ("list-0".to_string(), "list-1".to_string()));
+    }
+}
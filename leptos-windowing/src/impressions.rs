@@ -0,0 +1,78 @@
+use std::collections::HashSet;
+
+use leptos::prelude::*;
+use leptos_use::use_intersection_observer;
+
+use crate::WindowItem;
+
+/// Reports every item once when it becomes visible in the viewport for the first time.
+///
+/// This can be used for analytics like impression tracking of search results or ads.
+/// Visibility is determined with an `IntersectionObserver` on the root element of the rendered item.
+pub struct ImpressionTracker<T>
+where
+    T: Send + Sync + 'static,
+{
+    seen: StoredValue<HashSet<usize>>,
+    on_item_visible: Callback<WindowItem<T>>,
+}
+
+impl<T> Clone for ImpressionTracker<T>
+where
+    T: Send + Sync + 'static,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ImpressionTracker<T> where T: Send + Sync + 'static {}
+
+impl<T> ImpressionTracker<T>
+where
+    T: Send + Sync + 'static,
+{
+    /// Creates a new tracker that calls `on_item_visible` once per item index.
+    pub fn new(on_item_visible: Callback<WindowItem<T>>) -> Self {
+        Self {
+            seen: StoredValue::new(HashSet::new()),
+            on_item_visible,
+        }
+    }
+
+    /// Forgets which items have already been reported.
+    ///
+    /// Call this when the indices don't refer to the same items anymore, e.g. after the query changed.
+    pub fn reset(&self) {
+        self.seen.update_value(|seen| seen.clear());
+    }
+
+    /// Returns `true` if the item at the given index has already been reported as visible.
+    pub fn was_visible(&self, index: usize) -> bool {
+        self.seen.with_value(|seen| seen.contains(&index))
+    }
+
+    /// Starts observing the element that renders `item`.
+    ///
+    /// The observer is stopped after the item has become visible or when the element is cleaned up.
+    pub fn observe(&self, el: web_sys::Element, item: WindowItem<T>) {
+        if self.was_visible(item.index) {
+            return;
+        }
+
+        let seen = self.seen;
+        let on_item_visible = self.on_item_visible;
+
+        let _ = use_intersection_observer(el, move |entries, observer| {
+            if !entries.iter().any(|entry| entry.is_intersecting()) {
+                return;
+            }
+
+            observer.disconnect();
+
+            if seen.try_update_value(|seen| seen.insert(item.index)) == Some(true) {
+                on_item_visible.run(item.clone());
+            }
+        });
+    }
+}
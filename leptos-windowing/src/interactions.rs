@@ -0,0 +1,188 @@
+use leptos::{
+    ev::{self, MouseEvent, on},
+    prelude::*,
+    tachys::{
+        html::attribute::{aria_selected, custom::custom_attribute, tabindex},
+        view::add_attr::AddAnyAttr,
+    },
+    wasm_bindgen::JsCast,
+};
+
+use crate::{ItemWindow, WindowItem, WindowLink};
+
+/// The attribute that identifies the element of an item among its siblings to move the focus.
+const ITEM_INDEX_ATTRIBUTE: &str = "data-window-index";
+
+/// The interactions with the loaded items that all list components share.
+///
+/// - With a [`WindowLink`] clicking an item selects it and the selected item is marked with
+///   `aria-selected`.
+/// - `on_item_context_menu` is called when the context menu of an item is opened, e.g. with a
+///   right click.
+/// - `on_item_activate` is called when an item is double-clicked or when Enter is pressed while
+///   it's focused. The items then get a roving `tabindex` so only one of them is in the tab order,
+///   and the arrow keys, Home and End move the focus between the displayed items.
+///
+/// The attributes and listeners are put on the root element of an item with [`ItemInteractions::apply`].
+pub struct ItemInteractions<T>
+where
+    T: Send + Sync + 'static,
+{
+    link: Option<WindowLink<T>>,
+    on_item_context_menu: Option<Callback<(WindowItem<T>, MouseEvent)>>,
+    on_item_activate: Option<Callback<WindowItem<T>>>,
+    focused_index: RwSignal<Option<usize>>,
+}
+
+impl<T> Clone for ItemInteractions<T>
+where
+    T: Send + Sync + 'static,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ItemInteractions<T> where T: Send + Sync + 'static {}
+
+impl<T> ItemInteractions<T>
+where
+    T: Send + Sync + 'static,
+{
+    /// Creates the interactions. Nothing is added to the items for the ones that are `None`.
+    pub fn new(
+        link: Option<WindowLink<T>>,
+        on_item_context_menu: Option<Callback<(WindowItem<T>, MouseEvent)>>,
+        on_item_activate: Option<Callback<WindowItem<T>>>,
+    ) -> Self {
+        Self {
+            link,
+            on_item_context_menu,
+            on_item_activate,
+            focused_index: RwSignal::new(None),
+        }
+    }
+
+    /// Puts the attributes and listeners for `item` of `window` on `view`.
+    pub fn apply(&self, view: AnyView, item: &WindowItem<T>, window: ItemWindow<T>) -> AnyView {
+        let index = item.index;
+        let mut view = view;
+
+        if let Some(link) = self.link {
+            view = view
+                .add_any_attr((
+                    aria_selected(move || (link.selected_index().get() == Some(index)).to_string()),
+                    on(ev::click, move |_| link.select(Some(index))),
+                ))
+                .into_any();
+        }
+
+        if let Some(on_item_context_menu) = self.on_item_context_menu {
+            let item = item.clone();
+            view = view
+                .add_any_attr(on(ev::contextmenu, move |event| {
+                    on_item_context_menu.run((item.clone(), event))
+                }))
+                .into_any();
+        }
+
+        if let Some(on_item_activate) = self.on_item_activate {
+            let focused_index = self.focused_index;
+            let dblclick_item = item.clone();
+            let keydown_item = item.clone();
+
+            view = view
+                .add_any_attr((
+                    custom_attribute(ITEM_INDEX_ATTRIBUTE, index.to_string()),
+                    tabindex(move || {
+                        let range = window.range.get();
+                        let focused = focused_index
+                            .get()
+                            .filter(|focused| range.contains(focused))
+                            .unwrap_or(range.start);
+
+                        if focused == index { "0" } else { "-1" }
+                    }),
+                    on(ev::focus, move |_| focused_index.set(Some(index))),
+                    on(ev::dblclick, move |_| {
+                        on_item_activate.run(dblclick_item.clone())
+                    }),
+                    on(ev::keydown, move |event| {
+                        // Ignore the keys pressed inside of nested elements like buttons
+                        if event.target() != event.current_target() {
+                            return;
+                        }
+
+                        if event.key() == "Enter" {
+                            on_item_activate.run(keydown_item.clone());
+                        } else if let Some(target) = item_focus_target(
+                            &event.key(),
+                            index,
+                            &untrack(|| window.displayed_indices()),
+                        ) {
+                            event.prevent_default();
+                            focus_item(&event, target);
+                        }
+                    }),
+                ))
+                .into_any();
+        }
+
+        view
+    }
+}
+
+/// Focuses the sibling of the current target of `event` that renders the item at `index`.
+fn focus_item(event: &web_sys::KeyboardEvent, index: usize) {
+    let Some(parent) = event
+        .current_target()
+        .and_then(|target| target.dyn_into::<web_sys::Element>().ok())
+        .and_then(|element| element.parent_element())
+    else {
+        return;
+    };
+
+    if let Ok(Some(element)) =
+        parent.query_selector(&format!(":scope > [{ITEM_INDEX_ATTRIBUTE}=\"{index}\"]"))
+        && let Ok(element) = element.dyn_into::<web_sys::HtmlElement>()
+    {
+        let _ = element.focus();
+    }
+}
+
+/// The index of the displayed item to focus when `key` is pressed on the item at `index`.
+/// `None` if the key doesn't move the focus.
+fn item_focus_target(key: &str, index: usize, displayed_indices: &[usize]) -> Option<usize> {
+    let position = displayed_indices.iter().position(|i| *i == index)?;
+    let last = displayed_indices.len() - 1;
+
+    let target = match key {
+        "ArrowUp" | "ArrowLeft" => position.saturating_sub(1),
+        "ArrowDown" | "ArrowRight" => (position + 1).min(last),
+        "Home" => 0,
+        "End" => last,
+        _ => return None,
+    };
+
+    Some(displayed_indices[target])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_item_focus_target() {
+        // 22 is hidden
+        let displayed = [20, 21, 23, 24];
+
+        assert_eq!(item_focus_target("ArrowDown", 21, &displayed), Some(23));
+        assert_eq!(item_focus_target("ArrowUp", 23, &displayed), Some(21));
+        assert_eq!(item_focus_target("ArrowUp", 20, &displayed), Some(20));
+        assert_eq!(item_focus_target("ArrowRight", 24, &displayed), Some(24));
+        assert_eq!(item_focus_target("Home", 23, &displayed), Some(20));
+        assert_eq!(item_focus_target("End", 20, &displayed), Some(24));
+        assert_eq!(item_focus_target("Enter", 20, &displayed), None);
+        assert_eq!(item_focus_target("ArrowDown", 30, &displayed), None);
+    }
+}
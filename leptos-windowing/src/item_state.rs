@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{any::Any, fmt::Debug, sync::Arc};
 
 pub enum ItemState<T: Send + Sync + 'static> {
     /// The row is not yet loaded and a placeholder is displayed if the row is visible in the viewport.
@@ -8,7 +8,7 @@ pub enum ItemState<T: Send + Sync + 'static> {
     /// The row has been loaded.
     Loaded(Arc<T>),
     /// The row failed to load.
-    Error(String),
+    Error(ItemError),
 }
 
 impl<T: Send + Sync + 'static> Clone for ItemState<T> {
@@ -32,3 +32,86 @@ impl<T: Send + Sync + 'static> std::fmt::Debug for ItemState<T> {
         }
     }
 }
+
+/// The error of an item that failed to load.
+///
+/// It keeps the error returned by the loader so you can react differently to different kinds of
+/// errors, e.g. redirect to the login when the session expired:
+///
+/// ```
+/// # use leptos_windowing::item_state::ItemError;
+/// #
+/// #[derive(Debug)]
+/// enum ApiError {
+///     Unauthorized,
+///     NotFound,
+/// }
+///
+/// let error = ItemError::new(ApiError::Unauthorized);
+///
+/// assert!(matches!(error.downcast_ref::<ApiError>(), Some(ApiError::Unauthorized)));
+/// assert_eq!(error.message(), "Unauthorized");
+/// ```
+#[derive(Clone)]
+pub struct ItemError {
+    message: String,
+    error: Option<Arc<dyn Any + Send + Sync>>,
+}
+
+impl ItemError {
+    /// Wraps an error returned by a loader. Its `Debug` output is used as the message.
+    pub fn new<E>(error: E) -> Self
+    where
+        E: Debug + Send + Sync + 'static,
+    {
+        Self {
+            message: format!("{error:?}"),
+            error: Some(Arc::new(error)),
+        }
+    }
+
+    /// The message of the error. This is the `Debug` output of the loader's error.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The error returned by the loader if it's of type `E`.
+    ///
+    /// `None` for errors that have been created from a message only.
+    pub fn downcast_ref<E: 'static>(&self) -> Option<&E> {
+        self.error.as_deref()?.downcast_ref()
+    }
+}
+
+impl From<String> for ItemError {
+    fn from(message: String) -> Self {
+        Self {
+            message,
+            error: None,
+        }
+    }
+}
+
+impl From<&str> for ItemError {
+    fn from(message: &str) -> Self {
+        message.to_string().into()
+    }
+}
+
+impl PartialEq for ItemError {
+    fn eq(&self, other: &Self) -> bool {
+        self.message == other.message
+    }
+}
+
+impl std::fmt::Display for ItemError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Debug for ItemError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
@@ -12,15 +12,100 @@
 //! - [`MemoryLoader`]: If your dataset is already in memory like in a `Vec`, `HashSet`, array, ...
 //! - [`PaginatedLoader`]: If your data source provides data in pages (independent of if you use UI pagination or virtualization).
 //! - [`ExactLoader`]: If your data source can provide an exact range of items (start index to end index).
+//! - [`CursorLoader`]: If your data source paginates by opaque cursor instead of by offset.
+//! - [`KeysetLoader`]: If your data source loads the items after the sort key of the last item (seek pagination).
+//! - [`RelayLoader`]: If your data source is a GraphQL API with Relay connections.
+//! - [`StreamLoader`]: If your data source delivers the items one by one as a stream. They're displayed as they arrive.
+//! - `ODataLoader`: If your data source is an OData service. Requires the feature `odata`.
+//! - `StartAfterLoader`: If your data source is a document database with `startAfter` cursors like Firestore. Requires the feature `start-after`.
+//! - `WorkerLoader`: If filtering and sorting your in-memory dataset is too slow for the main thread. Runs a [`MemoryLoader`] in a Web Worker. Requires the feature `worker`.
+//! - `MockLoader`: For tests and demos. Serves a fixed dataset with configurable latency and errors and records all calls. Requires the feature `test-utils`.
 //! - [`Loader`]: If none of the above fit your needs, you can implement this trait to provide your own loading logic.
 //!
 //! Please refer to the documentation and the examples to see how to implement these traits.
+//!
+//! To adapt an existing loader, e.g. to convert its items or to append another loader, use the
+//! combinators of [`LoaderExt`].
+//!
+//! Instead of defining a query struct for every list you can use [`ListQuery`] for the common
+//! search, filter and sort combination.
+//!
+//! To encode the sorting of a query for your backend you can use [`sql_order_by`], [`rest_sort_param`]
+//! or [`odata_order_by`]. To sort items in memory derive [`Sortable`](trait@Sortable) and use [`sort_items`].
+//!
+//! To restore a list instantly when navigating back to it, persist its cache to web storage with
+//! `use_persisted_cache`. Requires the feature `persist`. To keep the loaded items in memory while
+//! the list is unmounted wrap its loader in a [`CachedLoader`].
+//!
+//! With server side rendering the lists are rendered empty on the server and loaded after
+//! hydration. To render the first page on the server, load it with [`use_server_prefill`].
+//!
+//! To change the items of a list that should also work offline, implement [`MutableLoader`] and
+//! make the changes through [`use_mutation_outbox`]. It replays them when the connection returns.
+//!
+//! To refresh all lists that show some data after a mutation, tag them with the `tags` option and
+//! call [`invalidate_tag`]. With the feature `broadcast` the lists in the other open tabs are refreshed too.
+//!
+//! To test the loading logic of your lists in plain `cargo test`, use the `testing` module together
+//! with `MockLoader`. Requires the feature `test-utils`.
+//!
+//! Misuses like a `CacheController` that is used before it has been initialized or a change of an
+//! item outside of the cache are logged as errors. Enable the feature `strict` to make them panic in
+//! debug builds instead so they surface during development.
+
+// The `Sortable` derive refers to this crate by name.
+extern crate self as leptos_windowing;
 
+#[cfg(feature = "broadcast")]
+mod broadcast;
 pub mod cache;
+mod chunk_size;
+mod columns;
+mod compat;
+#[cfg(all(debug_assertions, not(feature = "ssr")))]
+mod contract;
+mod entities;
 pub mod hook;
+mod id;
+mod impressions;
+mod interactions;
 pub mod item_state;
+mod link;
 mod loaders;
+mod outbox;
+#[cfg(feature = "persist")]
+mod persist;
+mod query;
+mod retry;
+mod server_prefill;
+mod sorting;
+mod spawner;
+mod strict;
+mod tags;
+#[cfg(feature = "test-utils")]
+pub mod testing;
+mod transfer;
 mod window;
 
+#[cfg(feature = "broadcast")]
+pub use broadcast::*;
+pub use chunk_size::*;
+pub use columns::*;
+pub use compat::*;
+pub use entities::*;
+pub use id::*;
+pub use impressions::*;
+pub use interactions::*;
+pub use link::*;
 pub use loaders::*;
+pub use outbox::*;
+#[cfg(feature = "persist")]
+pub use persist::*;
+pub use query::*;
+pub use retry::*;
+pub use server_prefill::*;
+pub use sorting::*;
+pub use spawner::*;
+pub use tags::*;
+pub use transfer::*;
 pub use window::*;
@@ -0,0 +1,118 @@
+use std::ops::Range;
+
+use leptos::prelude::*;
+
+use crate::{ItemWindow, cache::Cache};
+
+/// Links several windows over the same data, e.g. a virtualized list and a paginated table of
+/// the same query in a master/detail or split view.
+///
+/// All linked windows share one cache, so items loaded by one window are immediately available in
+/// the others. They also share a selection and an anchor item: whenever one window navigates
+/// so that the anchor item isn't visible anymore, the first item of the new display range becomes the anchor and
+/// all other windows go to that item if it isn't already visible in them.
+///
+/// Please note that all linked windows have to use the same loader and query.
+pub struct WindowLink<T>
+where
+    T: Send + Sync + 'static,
+{
+    cache: Cache<T>,
+    selected_index: RwSignal<Option<usize>>,
+    anchor_index: RwSignal<Option<usize>>,
+}
+
+impl<T> Clone for WindowLink<T>
+where
+    T: Send + Sync + 'static,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for WindowLink<T> where T: Send + Sync + 'static {}
+
+impl<T> Default for WindowLink<T>
+where
+    T: Send + Sync + 'static,
+{
+    fn default() -> Self {
+        Self {
+            cache: Cache::new(),
+            selected_index: RwSignal::new(None),
+            anchor_index: RwSignal::new(None),
+        }
+    }
+}
+
+impl<T> WindowLink<T>
+where
+    T: Send + Sync + 'static,
+{
+    /// Creates a new link. Pass it to every window that should be linked.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// The cache that is shared between all linked windows.
+    #[inline]
+    pub fn cache(&self) -> Cache<T> {
+        self.cache
+    }
+
+    /// The index of the currently selected item.
+    #[inline]
+    pub fn selected_index(&self) -> Signal<Option<usize>> {
+        self.selected_index.into()
+    }
+
+    /// The index of the item that all linked windows are showing.
+    #[inline]
+    pub fn anchor_index(&self) -> Signal<Option<usize>> {
+        self.anchor_index.into()
+    }
+
+    /// Selects the item at the given index and makes all linked windows show it.
+    pub fn select(&self, index: Option<usize>) {
+        self.selected_index.set(index);
+
+        if let Some(index) = index {
+            self.anchor_index.set(Some(index));
+        }
+    }
+
+    /// This is called in the components to connect a window to this link.
+    ///
+    /// `go_to_index` has to make the item with the given index visible, i.e. go to its page or scroll to it.
+    pub fn connect(&self, window: ItemWindow<T>, go_to_index: Callback<usize>) {
+        let anchor_index = self.anchor_index;
+
+        let is_visible = |index: usize, range: &Range<usize>| range.contains(&index);
+
+        // This window navigated -> update the anchor if it isn't visible anymore.
+        Effect::new(move || {
+            let range = window.range.get();
+
+            if range.is_empty() {
+                return;
+            }
+
+            if !anchor_index
+                .get_untracked()
+                .is_some_and(|anchor| is_visible(anchor, &range))
+            {
+                anchor_index.set(Some(range.start));
+            }
+        });
+
+        // Another window navigated -> follow the anchor if it isn't visible here.
+        Effect::new(move || {
+            if let Some(anchor) = anchor_index.get()
+                && !is_visible(anchor, &window.range.get_untracked())
+            {
+                go_to_index.run(anchor);
+            }
+        });
+    }
+}
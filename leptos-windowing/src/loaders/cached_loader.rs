@@ -0,0 +1,473 @@
+use std::{collections::HashMap, hash::Hash, marker::PhantomData, ops::Range, time::Duration};
+
+use leptos::prelude::*;
+
+use super::{InternalLoader, LoadedItems, Loader};
+use crate::cache::now_millis;
+
+/// Loaded items per query that outlive the components that display them.
+///
+/// Create it in a component that stays mounted, like your `App`, and hand it to the lists via
+/// [`CachedLoader`]. Navigating away from a list and back then reuses the previously loaded items
+/// instead of loading everything again. You can also put it into the context with
+/// [`provide_loader_cache`] and get it back with [`use_loader_cache`].
+///
+/// The items of at most [`DEFAULT_MAX_CACHED_QUERIES`] queries are kept. When another query is
+/// loaded, the one that hasn't been used the longest is dropped. Change the limit with
+/// [`with_max_queries`](Self::with_max_queries) and let the items expire with
+/// [`with_ttl`](Self::with_ttl).
+pub struct LoaderCache<Q, T>
+where
+    Q: Eq + Hash + Clone + Send + Sync + 'static,
+    T: Clone + Send + Sync + 'static,
+{
+    state: StoredValue<LoaderCacheState<Q, T>>,
+}
+
+/// How many queries a [`LoaderCache`] keeps by default.
+pub const DEFAULT_MAX_CACHED_QUERIES: usize = 20;
+
+struct LoaderCacheState<Q, T> {
+    queries: HashMap<Q, CachedQuery<T>>,
+    max_queries: usize,
+    ttl: Option<Duration>,
+    /// Increased on every access to find the least recently used query.
+    use_counter: u64,
+}
+
+impl<Q, T> LoaderCacheState<Q, T>
+where
+    Q: Eq + Hash + Clone,
+{
+    /// The cached items of `query` if they haven't expired yet. Marks them as used.
+    fn get_mut(&mut self, query: &Q) -> Option<&mut CachedQuery<T>> {
+        if let Some(ttl) = self.ttl
+            && self.queries.get(query).is_some_and(|cached| {
+                now_millis() - cached.created_at >= ttl.as_secs_f64() * 1000.0
+            })
+        {
+            self.queries.remove(query);
+        }
+
+        self.use_counter += 1;
+        let cached = self.queries.get_mut(query)?;
+        cached.last_used = self.use_counter;
+
+        Some(cached)
+    }
+
+    /// Like [`get_mut`](Self::get_mut) but creates the entry if it doesn't exist. Drops the least
+    /// recently used queries to stay within `max_queries`.
+    fn entry(&mut self, query: &Q) -> &mut CachedQuery<T> {
+        if self.get_mut(query).is_none() {
+            while !self.queries.is_empty() && self.queries.len() >= self.max_queries.max(1) {
+                let least_recently_used = self
+                    .queries
+                    .iter()
+                    .min_by_key(|(_, cached)| cached.last_used)
+                    .map(|(query, _)| query.clone())
+                    .expect("not empty");
+                self.queries.remove(&least_recently_used);
+            }
+
+            self.queries.insert(
+                query.clone(),
+                CachedQuery {
+                    items: Vec::new(),
+                    item_count: None,
+                    created_at: now_millis(),
+                    last_used: self.use_counter,
+                },
+            );
+        }
+
+        self.queries.get_mut(query).expect("just inserted")
+    }
+}
+
+impl<Q, T> Clone for LoaderCache<Q, T>
+where
+    Q: Eq + Hash + Clone + Send + Sync + 'static,
+    T: Clone + Send + Sync + 'static,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Q, T> Copy for LoaderCache<Q, T>
+where
+    Q: Eq + Hash + Clone + Send + Sync + 'static,
+    T: Clone + Send + Sync + 'static,
+{
+}
+
+impl<Q, T> Default for LoaderCache<Q, T>
+where
+    Q: Eq + Hash + Clone + Send + Sync + 'static,
+    T: Clone + Send + Sync + 'static,
+{
+    fn default() -> Self {
+        Self {
+            state: StoredValue::new(LoaderCacheState {
+                queries: HashMap::new(),
+                max_queries: DEFAULT_MAX_CACHED_QUERIES,
+                ttl: None,
+                use_counter: 0,
+            }),
+        }
+    }
+}
+
+impl<Q, T> LoaderCache<Q, T>
+where
+    Q: Eq + Hash + Clone + Send + Sync + 'static,
+    T: Clone + Send + Sync + 'static,
+{
+    /// Creates a new empty cache.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Keeps the items of at most `max_queries` queries. Defaults to [`DEFAULT_MAX_CACHED_QUERIES`].
+    ///
+    /// When another query is loaded, the one that hasn't been used the longest is dropped.
+    pub fn with_max_queries(self, max_queries: usize) -> Self {
+        self.state
+            .update_value(|state| state.max_queries = max_queries);
+        self
+    }
+
+    /// Drops the items of a query `ttl` after they have first been loaded so they are loaded
+    /// again. By default they are kept until the query is dropped to stay within the
+    /// [`max_queries`](Self::with_max_queries).
+    pub fn with_ttl(self, ttl: Duration) -> Self {
+        self.state.update_value(|state| state.ttl = Some(ttl));
+        self
+    }
+
+    /// Drops the loaded items of `query` so they are loaded again the next time.
+    pub fn invalidate(&self, query: &Q) {
+        self.state.update_value(|state| {
+            state.queries.remove(query);
+        });
+    }
+
+    /// Drops the loaded items of all queries.
+    pub fn clear(&self) {
+        self.state.update_value(|state| state.queries.clear());
+    }
+
+    /// The items in `range` if all of them are cached for `query`.
+    ///
+    /// If the item count is known, the range is limited to it.
+    fn get(&self, range: Range<usize>, query: &Q) -> Option<LoadedItems<T>> {
+        self.state
+            .try_update_value(|state| {
+                let cached = state.get_mut(query)?;
+
+                let end = cached
+                    .item_count
+                    .map_or(range.end, |count| range.end.min(count));
+                let range = range.start.min(end)..end;
+
+                let items = cached
+                    .items
+                    .get(range.clone())?
+                    .iter()
+                    .cloned()
+                    .collect::<Option<Vec<_>>>()?;
+
+                Some(LoadedItems { items, range })
+            })
+            .flatten()
+    }
+
+    /// Stores `items` starting at index `start` for `query`.
+    fn insert(&self, start: usize, items: &[T], query: &Q) {
+        self.state.update_value(|state| {
+            let cached = state.entry(query);
+
+            let end = start + items.len();
+            if cached.items.len() < end {
+                cached.items.resize(end, None);
+            }
+
+            for (slot, item) in cached.items[start..end].iter_mut().zip(items) {
+                *slot = Some(item.clone());
+            }
+        });
+    }
+
+    fn item_count(&self, query: &Q) -> Option<usize> {
+        self.state
+            .try_update_value(|state| state.get_mut(query).and_then(|cached| cached.item_count))
+            .flatten()
+    }
+
+    fn set_item_count(&self, count: usize, query: &Q) {
+        self.state.update_value(|state| {
+            state.entry(query).item_count = Some(count);
+        });
+    }
+}
+
+/// Provides a new [`LoaderCache`] to all child components. Get it with [`use_loader_cache`].
+///
+/// Call this in a component that stays mounted while the lists come and go, like your `App`.
+pub fn provide_loader_cache<Q, T>() -> LoaderCache<Q, T>
+where
+    Q: Eq + Hash + Clone + Send + Sync + 'static,
+    T: Clone + Send + Sync + 'static,
+{
+    let cache = LoaderCache::new();
+    provide_context(cache);
+    cache
+}
+
+/// Returns the [`LoaderCache`] provided by [`provide_loader_cache`].
+///
+/// ## Panics
+///
+/// If no cache for these query and item types has been provided.
+pub fn use_loader_cache<Q, T>() -> LoaderCache<Q, T>
+where
+    Q: Eq + Hash + Clone + Send + Sync + 'static,
+    T: Clone + Send + Sync + 'static,
+{
+    expect_context()
+}
+
+/// The loaded items of one query.
+struct CachedQuery<T> {
+    items: Vec<Option<T>>,
+    item_count: Option<usize>,
+    /// When the first item of the query has been cached in milliseconds since the Unix epoch.
+    created_at: f64,
+    /// The value of [`LoaderCacheState::use_counter`] at the last access.
+    last_used: u64,
+}
+
+/// Wraps a loader and keeps the loaded items in a [`LoaderCache`].
+///
+/// Items that are already in the cache are returned without calling the wrapped loader. Because
+/// the cache outlives the list, a paginated route that is left and visited again displays the
+/// previously loaded pages right away.
+///
+/// ```
+/// # use std::ops::Range;
+/// # use leptos::prelude::*;
+/// # use leptos_windowing::{CachedLoader, MemoryLoader, provide_loader_cache, use_loader_cache};
+/// #
+/// # #[derive(Clone)]
+/// # pub struct Book;
+/// #
+/// pub struct BookLoader;
+///
+/// impl MemoryLoader for BookLoader {
+///     // ...
+/// #     type Item = Book;
+/// #     type Query = String;
+/// #     fn load_items(&self, range: Range<usize>, _query: &String) -> Vec<Book> {
+/// #         vec![]
+/// #     }
+/// #     fn item_count(&self, _query: &String) -> usize {
+/// #         0
+/// #     }
+/// }
+///
+/// #[component]
+/// pub fn App() -> impl IntoView {
+///     // Lives as long as the app
+///     provide_loader_cache::<String, Book>();
+///     // ...
+/// #   ()
+/// }
+///
+/// #[component]
+/// pub fn BooksRoute() -> impl IntoView {
+///     // Use this as the loader of the list
+///     let loader = CachedLoader::new(BookLoader, use_loader_cache());
+///     // ...
+/// #   ()
+/// }
+/// ```
+pub struct CachedLoader<L, M>
+where
+    L: InternalLoader<M>,
+    L::Item: Clone + Send + Sync + 'static,
+    L::Query: Eq + Hash + Clone + Send + Sync + 'static,
+{
+    loader: L,
+    cache: LoaderCache<L::Query, L::Item>,
+    _marker: PhantomData<fn() -> M>,
+}
+
+impl<L, M> CachedLoader<L, M>
+where
+    L: InternalLoader<M>,
+    L::Item: Clone + Send + Sync + 'static,
+    L::Query: Eq + Hash + Clone + Send + Sync + 'static,
+{
+    /// Wraps `loader` and keeps the loaded items in `cache`.
+    pub fn new(loader: L, cache: LoaderCache<L::Query, L::Item>) -> Self {
+        Self {
+            loader,
+            cache,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The cache that the loaded items are kept in.
+    pub fn cache(&self) -> LoaderCache<L::Query, L::Item> {
+        self.cache
+    }
+}
+
+impl<L, M> Loader for CachedLoader<L, M>
+where
+    L: InternalLoader<M>,
+    L::Item: Clone + Send + Sync + 'static,
+    L::Query: Eq + Hash + Clone + Send + Sync + 'static,
+{
+    const CHUNK_SIZE: Option<usize> = L::CHUNK_SIZE;
+
+    type Item = L::Item;
+    type Query = L::Query;
+    type Error = L::Error;
+
+    async fn load_items(
+        &self,
+        range: Range<usize>,
+        query: &Self::Query,
+    ) -> Result<LoadedItems<Self::Item>, Self::Error> {
+        if let Some(loaded) = self.cache.get(range.clone(), query) {
+            return Ok(loaded);
+        }
+
+        let loaded = self.loader.load_items(range, query).await?;
+        self.cache.insert(loaded.range.start, &loaded.items, query);
+
+        Ok(loaded)
+    }
+
+    async fn item_count(&self, query: &Self::Query) -> Result<Option<usize>, Self::Error> {
+        if let Some(count) = self.cache.item_count(query) {
+            return Ok(Some(count));
+        }
+
+        let count = self.loader.item_count(query).await?;
+        if let Some(count) = count {
+            self.cache.set_item_count(count, query);
+        }
+
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use futures::executor::block_on;
+
+    use super::*;
+    use crate::MemoryLoader;
+
+    struct CountingLoader<'a> {
+        load_count: &'a Cell<usize>,
+    }
+
+    impl MemoryLoader for CountingLoader<'_> {
+        type Item = usize;
+        type Query = usize;
+
+        fn load_items(&self, range: Range<usize>, offset: &usize) -> Vec<usize> {
+            self.load_count.set(self.load_count.get() + 1);
+            range
+                .map(|index| index + offset)
+                .take_while(|i| *i < 10 + offset)
+                .collect()
+        }
+
+        fn item_count(&self, _offset: &usize) -> usize {
+            10
+        }
+    }
+
+    #[test]
+    fn test_reuse_loaded_items() {
+        let load_count = Cell::new(0);
+        let cache = LoaderCache::new();
+
+        let load = |range: Range<usize>, query: usize| {
+            let loader = CachedLoader::new(
+                CountingLoader {
+                    load_count: &load_count,
+                },
+                cache,
+            );
+
+            block_on(async {
+                Loader::item_count(&loader, &query).await.unwrap();
+                Loader::load_items(&loader, range, &query)
+                    .await
+                    .unwrap()
+                    .items
+            })
+        };
+
+        assert_eq!(load(0..4, 0), [0, 1, 2, 3]);
+        assert_eq!(load_count.get(), 1);
+
+        // A new loader, e.g. after the list has been mounted again
+        assert_eq!(load(1..3, 0), [1, 2]);
+        assert_eq!(load(8..12, 0), [8, 9]);
+        assert_eq!(load(8..12, 0), [8, 9]);
+        assert_eq!(load_count.get(), 2);
+
+        assert_eq!(load(0..2, 100), [100, 101]);
+        assert_eq!(load_count.get(), 3);
+
+        cache.invalidate(&0);
+        assert_eq!(load(0..2, 0), [0, 1]);
+        assert_eq!(load_count.get(), 4);
+    }
+
+    #[test]
+    fn test_limits() {
+        let load_count = Cell::new(0);
+
+        let load = |cache: LoaderCache<usize, usize>, query: usize| {
+            let loader = CachedLoader::new(
+                CountingLoader {
+                    load_count: &load_count,
+                },
+                cache,
+            );
+
+            block_on(Loader::load_items(&loader, 0..2, &query))
+                .unwrap()
+                .items
+        };
+
+        let cache = LoaderCache::new().with_max_queries(2);
+        load(cache, 0);
+        load(cache, 100);
+        // Uses query 0 so 100 is the least recently used one
+        load(cache, 0);
+        load(cache, 200);
+        assert_eq!(load_count.get(), 3);
+
+        load(cache, 0);
+        assert_eq!(load_count.get(), 3);
+        load(cache, 100);
+        assert_eq!(load_count.get(), 4);
+
+        load_count.set(0);
+        let cache = LoaderCache::new().with_ttl(Duration::ZERO);
+        load(cache, 0);
+        load(cache, 0);
+        assert_eq!(load_count.get(), 2);
+    }
+}
@@ -0,0 +1,505 @@
+use std::{marker::PhantomData, ops::Range, rc::Rc, sync::Mutex};
+
+use futures::{StreamExt, stream::LocalBoxStream};
+
+use super::{InternalLoader, LiveUpdate, LoadedItems};
+
+/// Combinators to adapt an existing loader without implementing a loader trait again.
+///
+/// This is implemented for every loader.
+///
+/// ```
+/// # use leptos_windowing::{LoaderExt, MemoryLoader};
+/// #
+/// #[derive(Clone)]
+/// pub struct Book {
+///     title: String,
+/// }
+///
+/// let titles = vec!["Dune".to_string(), "Emma".to_string()];
+///
+/// let loader = titles
+///     .filter_ok(|title: &String| title.starts_with('E'))
+///     .map(|title| Book { title })
+///     .chain(vec![Book { title: "Ulysses".to_string() }]);
+/// ```
+pub trait LoaderExt<M>: InternalLoader<M> + Sized {
+    /// Converts every loaded item with `f`, e.g. into a view model.
+    fn map<U, F>(self, f: F) -> MapLoader<Self, M, F>
+    where
+        F: Fn(Self::Item) -> U,
+    {
+        MapLoader {
+            loader: self,
+            f: Rc::new(f),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Only keeps the items for which `predicate` returns `true`.
+    ///
+    /// The indices of the matching items in the wrapped loader are remembered so jumping back
+    /// doesn't scan the data again. Jumping ahead loads the items before the target range to find
+    /// the matching ones. They're forgotten when the query changes. The item count is unknown until
+    /// the end of the data has been reached.
+    fn filter_ok<F>(self, predicate: F) -> FilterLoader<Self, M, F>
+    where
+        F: Fn(&Self::Item) -> bool,
+        Self::Query: PartialEq + Clone,
+    {
+        FilterLoader {
+            loader: self,
+            predicate,
+            positions: Mutex::new(FilterPositions::default()),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Appends the items of `other` after the items of this loader as one list.
+    ///
+    /// The item count of this loader is used to tell where `other` starts. If it's unknown, the
+    /// end is detected when fewer items than requested are loaded.
+    fn chain<B, MB>(self, other: B) -> ChainLoader<Self, B, M, MB>
+    where
+        B: InternalLoader<MB, Item = Self::Item, Query = Self::Query, Error = Self::Error>,
+        Self::Query: PartialEq + Clone,
+    {
+        ChainLoader {
+            first: self,
+            second: other,
+            first_len: Mutex::new(None),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<L, M> LoaderExt<M> for L where L: InternalLoader<M> {}
+
+/// Loader returned by [`LoaderExt::map`].
+pub struct MapLoader<L, M, F> {
+    loader: L,
+    f: Rc<F>,
+    _marker: PhantomData<fn() -> M>,
+}
+
+pub struct MapLoaderMarker<M>(PhantomData<M>);
+
+impl<L, M, F, U> InternalLoader<MapLoaderMarker<M>> for MapLoader<L, M, F>
+where
+    L: InternalLoader<M, Item: 'static>,
+    F: Fn(L::Item) -> U + 'static,
+    U: 'static,
+{
+    const CHUNK_SIZE: Option<usize> = L::CHUNK_SIZE;
+
+    type Item = U;
+    type Query = L::Query;
+    type Error = L::Error;
+
+    async fn load_items_inner(
+        &self,
+        range: Range<usize>,
+        query: &Self::Query,
+    ) -> Result<LoadedItems<Self::Item>, Self::Error> {
+        let LoadedItems { items, range } = self.loader.load_items_inner(range, query).await?;
+
+        Ok(LoadedItems {
+            items: items.into_iter().map(&*self.f).collect(),
+            range,
+        })
+    }
+
+    async fn load_items_streaming(
+        &self,
+        range: Range<usize>,
+        query: &Self::Query,
+        mut on_loaded: impl FnMut(LoadedItems<Self::Item>),
+    ) -> Result<Range<usize>, Self::Error> {
+        self.loader
+            .load_items_streaming(range, query, |LoadedItems { items, range }| {
+                on_loaded(LoadedItems {
+                    items: items.into_iter().map(&*self.f).collect(),
+                    range,
+                })
+            })
+            .await
+    }
+
+    #[inline]
+    async fn item_count(&self, query: &Self::Query) -> Result<Option<usize>, Self::Error> {
+        self.loader.item_count(query).await
+    }
+
+    fn updates(
+        &self,
+        query: &Self::Query,
+    ) -> Option<LocalBoxStream<'static, LiveUpdate<Self::Item>>> {
+        let f = Rc::clone(&self.f);
+
+        Some(
+            self.loader
+                .updates(query)?
+                .map(move |update| update.map(&*f))
+                .boxed_local(),
+        )
+    }
+}
+
+/// Loader returned by [`LoaderExt::filter_ok`].
+pub struct FilterLoader<L, M, F>
+where
+    L: InternalLoader<M>,
+{
+    loader: L,
+    predicate: F,
+    positions: Mutex<FilterPositions<L::Query>>,
+    _marker: PhantomData<fn() -> M>,
+}
+
+/// Where the matching items of a [`FilterLoader`] are in the wrapped loader.
+struct FilterPositions<Q> {
+    query: Option<Q>,
+    /// `matches[i]` is the index in the wrapped loader of the `i`-th matching item.
+    matches: Vec<usize>,
+    /// All items before this index of the wrapped loader have been checked.
+    scanned: usize,
+    /// Whether the end of the wrapped loader has been reached.
+    is_exhausted: bool,
+}
+
+impl<Q> Default for FilterPositions<Q> {
+    fn default() -> Self {
+        Self {
+            query: None,
+            matches: Vec::new(),
+            scanned: 0,
+            is_exhausted: false,
+        }
+    }
+}
+
+pub struct FilterLoaderMarker<M>(PhantomData<M>);
+
+impl<L, M, F> InternalLoader<FilterLoaderMarker<M>> for FilterLoader<L, M, F>
+where
+    L: InternalLoader<M>,
+    L::Query: PartialEq + Clone,
+    F: Fn(&L::Item) -> bool,
+{
+    type Item = L::Item;
+    type Query = L::Query;
+    type Error = L::Error;
+
+    async fn load_items_inner(
+        &self,
+        range: Range<usize>,
+        query: &Self::Query,
+    ) -> Result<LoadedItems<Self::Item>, Self::Error> {
+        // Start at the first requested item if its position is known or else where the scan stopped.
+        let (mut match_index, mut position) = {
+            let Ok(mut positions) = self.positions.lock() else {
+                return Ok(LoadedItems {
+                    items: vec![],
+                    range: range.start..range.start,
+                });
+            };
+
+            if positions.query.as_ref() != Some(query) {
+                *positions = FilterPositions {
+                    query: Some(query.clone()),
+                    ..FilterPositions::default()
+                };
+            }
+
+            match positions.matches.get(range.start) {
+                Some(position) => (range.start, *position),
+                None => (positions.matches.len(), positions.scanned),
+            }
+        };
+
+        let batch_size = range.len().max(1);
+        let mut items = Vec::with_capacity(range.len());
+
+        'scan: while match_index < range.end {
+            let requested_end = position + batch_size;
+            let loaded = self
+                .loader
+                .load_items(position..requested_end, query)
+                .await?;
+            // Memory loaders report the requested range even if there are fewer items.
+            let loaded_end = loaded.range.start + loaded.items.len();
+
+            for (index, item) in (loaded.range.start..).zip(loaded.items) {
+                if index < position {
+                    continue;
+                }
+                position = index + 1;
+
+                if !(self.predicate)(&item) {
+                    self.record_scanned(position, None);
+                    continue;
+                }
+
+                self.record_scanned(position, Some((match_index, index)));
+
+                if match_index >= range.start {
+                    items.push(item);
+                }
+                match_index += 1;
+
+                if match_index >= range.end {
+                    break 'scan;
+                }
+            }
+
+            if loaded_end < requested_end {
+                if let Ok(mut positions) = self.positions.lock() {
+                    positions.is_exhausted = true;
+                }
+                break;
+            }
+            position = position.max(loaded_end);
+        }
+
+        let start = range.start.min(match_index.saturating_sub(items.len()));
+
+        Ok(LoadedItems {
+            range: start..start + items.len(),
+            items,
+        })
+    }
+
+    async fn item_count(&self, query: &Self::Query) -> Result<Option<usize>, Self::Error> {
+        Ok(self.positions.lock().ok().and_then(|positions| {
+            (positions.query.as_ref() == Some(query) && positions.is_exhausted)
+                .then_some(positions.matches.len())
+        }))
+    }
+}
+
+impl<L, M, F> FilterLoader<L, M, F>
+where
+    L: InternalLoader<M>,
+{
+    /// Remembers that the items before `scanned` have been checked and, if it matched, that the
+    /// `match_index`-th matching item is at `position`.
+    fn record_scanned(&self, scanned: usize, matched: Option<(usize, usize)>) {
+        let Ok(mut positions) = self.positions.lock() else {
+            return;
+        };
+
+        if let Some((match_index, position)) = matched
+            && match_index == positions.matches.len()
+        {
+            positions.matches.push(position);
+        }
+
+        positions.scanned = positions.scanned.max(scanned);
+    }
+}
+
+/// Loader returned by [`LoaderExt::chain`].
+pub struct ChainLoader<A, B, MA, MB>
+where
+    A: InternalLoader<MA>,
+{
+    first: A,
+    second: B,
+    /// The number of items of `first` for the query.
+    first_len: Mutex<Option<(A::Query, usize)>>,
+    _marker: PhantomData<fn() -> (MA, MB)>,
+}
+
+pub struct ChainLoaderMarker<MA, MB>(PhantomData<(MA, MB)>);
+
+impl<A, B, MA, MB> ChainLoader<A, B, MA, MB>
+where
+    A: InternalLoader<MA>,
+    A::Query: PartialEq + Clone,
+{
+    fn known_first_len(&self, query: &A::Query) -> Option<usize> {
+        self.first_len
+            .lock()
+            .ok()?
+            .as_ref()
+            .filter(|(len_query, _)| len_query == query)
+            .map(|(_, len)| *len)
+    }
+
+    fn set_first_len(&self, query: &A::Query, len: usize) {
+        if let Ok(mut first_len) = self.first_len.lock() {
+            *first_len = Some((query.clone(), len));
+        }
+    }
+
+    async fn first_len(&self, query: &A::Query) -> Result<Option<usize>, A::Error> {
+        if let Some(len) = self.known_first_len(query) {
+            return Ok(Some(len));
+        }
+
+        let len = self.first.item_count(query).await?;
+        if let Some(len) = len {
+            self.set_first_len(query, len);
+        }
+
+        Ok(len)
+    }
+}
+
+impl<A, B, MA, MB> InternalLoader<ChainLoaderMarker<MA, MB>> for ChainLoader<A, B, MA, MB>
+where
+    A: InternalLoader<MA>,
+    A::Query: PartialEq + Clone,
+    B: InternalLoader<MB, Item = A::Item, Query = A::Query, Error = A::Error>,
+{
+    type Item = A::Item;
+    type Query = A::Query;
+    type Error = A::Error;
+
+    async fn load_items_inner(
+        &self,
+        range: Range<usize>,
+        query: &Self::Query,
+    ) -> Result<LoadedItems<Self::Item>, Self::Error> {
+        let mut items = Vec::with_capacity(range.len());
+        let mut position = range.start;
+
+        let first_len = match self.known_first_len(query) {
+            Some(len) => Some(len),
+            None if range.start > 0 => self.first_len(query).await?,
+            None => None,
+        };
+
+        if first_len.is_none_or(|len| position < len) {
+            let end = first_len.map_or(range.end, |len| range.end.min(len));
+            let loaded = trim(
+                self.first.load_items(position..end, query).await?,
+                position..end,
+            );
+
+            let is_short = loaded.range.end < end;
+            position = loaded.range.end;
+            items.extend(loaded.items);
+
+            if is_short {
+                // Past the unknown end of the first loader it's unclear where the second one starts.
+                if first_len.is_none() && items.is_empty() && range.start > 0 {
+                    return Ok(LoadedItems {
+                        items,
+                        range: range.start..range.start,
+                    });
+                }
+                self.set_first_len(query, position);
+            } else if first_len.is_none() {
+                return Ok(LoadedItems {
+                    range: range.start..position,
+                    items,
+                });
+            }
+        }
+
+        let Some(first_len) = self.known_first_len(query).or(first_len) else {
+            return Ok(LoadedItems {
+                range: range.start..position,
+                items,
+            });
+        };
+
+        if position < range.end && position >= first_len {
+            let second_range = position - first_len..range.end - first_len;
+            let loaded = trim(
+                self.second.load_items(second_range.clone(), query).await?,
+                second_range,
+            );
+
+            position += loaded.items.len();
+            items.extend(loaded.items);
+        }
+
+        Ok(LoadedItems {
+            range: range.start..position,
+            items,
+        })
+    }
+
+    async fn item_count(&self, query: &Self::Query) -> Result<Option<usize>, Self::Error> {
+        let Some(first_len) = self.first_len(query).await? else {
+            return Ok(None);
+        };
+
+        Ok(self
+            .second
+            .item_count(query)
+            .await?
+            .map(|second_len| first_len + second_len))
+    }
+}
+
+/// Removes the items outside of `range` that were loaded because of the chunk size of a loader.
+fn trim<T>(loaded: LoadedItems<T>, range: Range<usize>) -> LoadedItems<T> {
+    let LoadedItems {
+        items,
+        range: loaded_range,
+    } = loaded;
+    let loaded_range = loaded_range.start..loaded_range.start + items.len();
+
+    let start = loaded_range.start.max(range.start);
+    let end = loaded_range.end.min(range.end).max(start);
+
+    LoadedItems {
+        items: items
+            .into_iter()
+            .skip(start - loaded_range.start)
+            .take(end - start)
+            .collect(),
+        range: start..end,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+
+    use super::*;
+
+    fn load<L, M>(loader: &L, range: Range<usize>) -> (Vec<L::Item>, Range<usize>)
+    where
+        L: InternalLoader<M, Query = ()>,
+    {
+        let loaded = block_on(loader.load_items(range, &())).unwrap();
+
+        (loaded.items, loaded.range)
+    }
+
+    #[test]
+    fn test_map() {
+        let loader = vec![1, 2, 3].map(|item| item * 10);
+
+        assert_eq!(load(&loader, 0..2), (vec![10, 20], 0..2));
+    }
+
+    #[test]
+    fn test_filter_ok() {
+        let loader = (0..20)
+            .collect::<Vec<usize>>()
+            .filter_ok(|item| item % 3 == 0);
+
+        assert_eq!(load(&loader, 2..4), (vec![6, 9], 2..4));
+        assert_eq!(load(&loader, 0..2), (vec![0, 3], 0..2));
+        assert_eq!(block_on(loader.item_count(&())).ok(), Some(None));
+
+        assert_eq!(load(&loader, 5..10), (vec![15, 18], 5..7));
+        assert_eq!(load(&loader, 9..10), (vec![], 7..7));
+        assert_eq!(block_on(loader.item_count(&())).ok(), Some(Some(7)));
+    }
+
+    #[test]
+    fn test_chain() {
+        let loader = vec![0, 1, 2].chain(vec![10, 11]);
+
+        assert_eq!(load(&loader, 1..4), (vec![1, 2, 10], 1..4));
+        assert_eq!(load(&loader, 4..10), (vec![11], 4..5));
+        assert_eq!(load(&loader, 0..2), (vec![0, 1], 0..2));
+        assert_eq!(block_on(loader.item_count(&())).ok(), Some(Some(5)));
+    }
+}
@@ -0,0 +1,318 @@
+use std::{fmt::Debug, ops::Range, sync::Mutex};
+
+use crate::LoadedItems;
+
+/// Loader trait for loading items on-demand from a data source that paginates by opaque cursor
+/// instead of by offset, like the APIs of GitHub, Stripe or Firestore.
+///
+/// Every call to [`CursorLoader::load_after`] loads one page of items following the given cursor.
+/// The cursors are remembered per page in [`CursorLoader::cursors`] so the items can be addressed
+/// by index for pagination and virtualization without faking offsets.
+///
+/// Jumping to a page whose cursor isn't known yet loads the pages before it one after the other.
+///
+/// ## Example
+///
+/// ```
+/// # use leptos_windowing::{CursorCache, CursorLoader, CursorPage};
+/// #
+/// pub struct IssueLoader {
+///     cursors: CursorCache<String, ()>,
+/// }
+///
+/// impl CursorLoader for IssueLoader {
+///     const PAGE_ITEM_COUNT: usize = 50;
+///
+///     type Item = String;
+///     type Query = ();
+///     type Cursor = String;
+///     type Error = ();
+///
+///     async fn load_after(
+///         &self,
+///         cursor: Option<&Self::Cursor>,
+///         query: &Self::Query,
+///     ) -> Result<CursorPage<Self::Item, Self::Cursor>, Self::Error> {
+///         // Request `?first=50&after={cursor}` from your API here.
+///         # let _ = cursor;
+///         # Ok(CursorPage { items: vec![], next_cursor: None })
+///     }
+///
+///     fn cursors(&self) -> &CursorCache<Self::Cursor, Self::Query> {
+///         &self.cursors
+///     }
+/// }
+/// ```
+pub trait CursorLoader {
+    /// How many items a page of the data source contains.
+    const PAGE_ITEM_COUNT: usize;
+
+    /// The type of items that will be loaded.
+    type Item;
+
+    /// The type of the query data that will be used to load items.
+    ///
+    /// When it changes, the remembered cursors are discarded.
+    type Query: PartialEq + Clone;
+
+    /// The opaque cursor type of the data source.
+    type Cursor: Clone;
+
+    /// The type of errors that can occur during loading.
+    type Error: Debug + 'static;
+
+    /// Loads the page of items after `cursor`. `None` means the first page.
+    ///
+    /// Return `next_cursor: None` if this is the last page.
+    fn load_after(
+        &self,
+        cursor: Option<&Self::Cursor>,
+        query: &Self::Query,
+    ) -> impl Future<Output = Result<CursorPage<Self::Item, Self::Cursor>, Self::Error>>;
+
+    /// The total number of items of this data source with respect to the query.
+    ///
+    /// Returns `Ok(None)` if unknown (which is the default). Many cursor APIs don't provide a count.
+    fn item_count(
+        &self,
+        _query: &Self::Query,
+    ) -> impl Future<Output = Result<Option<usize>, Self::Error>> {
+        async { Ok(None) }
+    }
+
+    /// Storage for the cursors of the pages that have been loaded so far.
+    ///
+    /// Just add a field with a [`CursorCache::default()`] to your loader and return it here.
+    fn cursors(&self) -> &CursorCache<Self::Cursor, Self::Query>;
+}
+
+/// Return type of [`CursorLoader::load_after`].
+pub struct CursorPage<T, C> {
+    /// The loaded items.
+    pub items: Vec<T>,
+
+    /// The cursor to load the next page with. `None` if this is the last page.
+    pub next_cursor: Option<C>,
+}
+
+/// Remembers the cursors of the pages of a [`CursorLoader`] or the boundary keys of a
+/// [`KeysetLoader`](crate::KeysetLoader).
+pub struct CursorCache<C, Q> {
+    inner: Mutex<CursorCacheInner<C, Q>>,
+}
+
+struct CursorCacheInner<C, Q> {
+    query: Option<Q>,
+    /// `cursors[i]` is the cursor to load page `i + 1`.
+    cursors: Vec<C>,
+    /// The number of pages if the last page has been loaded.
+    page_count: Option<usize>,
+    /// The number of items if the last page has been loaded.
+    item_count: Option<usize>,
+}
+
+impl<C, Q> Default for CursorCache<C, Q> {
+    fn default() -> Self {
+        Self {
+            inner: Mutex::new(CursorCacheInner {
+                query: None,
+                cursors: Vec::new(),
+                page_count: None,
+                item_count: None,
+            }),
+        }
+    }
+}
+
+impl<C, Q> CursorCache<C, Q>
+where
+    C: Clone,
+    Q: PartialEq + Clone,
+{
+    /// Forgets all cursors.
+    pub fn clear(&self) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.cursors.clear();
+            inner.page_count = None;
+            inner.item_count = None;
+        }
+    }
+
+    /// Forgets all cursors if `query` is different from the query they were loaded with.
+    fn set_query(&self, query: &Q) {
+        if let Ok(mut inner) = self.inner.lock()
+            && inner.query.as_ref() != Some(query)
+        {
+            inner.query = Some(query.clone());
+            inner.cursors.clear();
+            inner.page_count = None;
+            inner.item_count = None;
+        }
+    }
+
+    /// Returns the page closest before or at `page_index` whose cursor is known, together with that cursor.
+    ///
+    /// Returns `None` if the data source has fewer pages than `page_index`. See [`CursorCache::item_count`].
+    fn closest(&self, page_index: usize) -> Option<(usize, Option<C>)> {
+        let inner = self.inner.lock().ok()?;
+
+        if inner
+            .page_count
+            .is_some_and(|page_count| page_index >= page_count)
+        {
+            return None;
+        }
+
+        let page_index = page_index.min(inner.cursors.len());
+        let cursor = page_index.checked_sub(1).map(|i| inner.cursors[i].clone());
+
+        Some((page_index, cursor))
+    }
+
+    /// Remembers the cursor to load `page_index`.
+    fn insert(&self, page_index: usize, cursor: C) {
+        if let Ok(mut inner) = self.inner.lock()
+            && page_index == inner.cursors.len() + 1
+        {
+            inner.cursors.push(cursor);
+        }
+    }
+
+    /// Remembers that there are exactly `page_count` pages with `item_count` items in total.
+    fn set_end(&self, page_count: usize, item_count: usize) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.page_count = Some(page_count);
+            inner.item_count = Some(item_count);
+        }
+    }
+
+    /// The number of items if the last page has been loaded.
+    fn item_count(&self) -> Option<usize> {
+        self.inner.lock().ok()?.item_count
+    }
+
+    /// Loads the pages that contain `range` by following the cursors with `load_after`.
+    ///
+    /// If the cursor of the first page isn't known yet, the pages before it are loaded as well.
+    pub(crate) async fn load_range<T, E>(
+        &self,
+        range: Range<usize>,
+        page_item_count: usize,
+        query: &Q,
+        load_after: impl AsyncFn(Option<&C>) -> Result<CursorPage<T, C>, E>,
+    ) -> Result<LoadedItems<T>, E> {
+        self.set_query(query);
+
+        let end_page = range.end.div_ceil(page_item_count);
+
+        let Some((mut page_index, mut cursor)) = self.closest(range.start / page_item_count) else {
+            // The range is past the end. Report the exact end instead of `range.start` so the
+            // item count isn't set too high.
+            let end = self.item_count().unwrap_or(range.start).min(range.start);
+
+            return Ok(LoadedItems {
+                items: vec![],
+                range: end..end,
+            });
+        };
+
+        let start = page_index * page_item_count;
+        let mut loaded = Vec::with_capacity(range.end.saturating_sub(start));
+
+        while page_index < end_page {
+            let CursorPage { items, next_cursor } = load_after(cursor.as_ref()).await?;
+            loaded.extend(items);
+            page_index += 1;
+
+            match next_cursor {
+                Some(next_cursor) => {
+                    self.insert(page_index, next_cursor.clone());
+                    cursor = Some(next_cursor);
+                }
+                None => {
+                    self.set_end(page_index, start + loaded.len());
+                    break;
+                }
+            }
+        }
+
+        let len = loaded.len();
+        Ok(LoadedItems {
+            items: loaded,
+            range: start..start + len,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::loaders::test_helpers::load;
+
+    /// 25 items in pages of 10 with the cursor being the index of the next item.
+    struct TestLoader {
+        cursors: CursorCache<usize, ()>,
+        calls: AtomicUsize,
+    }
+
+    impl CursorLoader for TestLoader {
+        const PAGE_ITEM_COUNT: usize = 10;
+
+        type Item = usize;
+        type Query = ();
+        type Cursor = usize;
+        type Error = ();
+
+        async fn load_after(
+            &self,
+            cursor: Option<&usize>,
+            _query: &(),
+        ) -> Result<CursorPage<usize, usize>, ()> {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+
+            let start = cursor.copied().unwrap_or_default();
+            let end = (start + 10).min(25);
+
+            Ok(CursorPage {
+                items: (start..end).collect(),
+                next_cursor: (end < 25).then_some(end),
+            })
+        }
+
+        fn cursors(&self) -> &CursorCache<usize, ()> {
+            &self.cursors
+        }
+    }
+
+    #[test]
+    fn test_cursor_loader() {
+        let loader = TestLoader {
+            cursors: CursorCache::default(),
+            calls: AtomicUsize::new(0),
+        };
+
+        // the first page has to be loaded to get the cursor of the second page
+        let loaded = load(&loader, 12..18);
+        assert_eq!(loaded.range, 0..20);
+        assert_eq!(loaded.items, (0..20).collect::<Vec<_>>());
+        assert_eq!(loader.calls.load(Ordering::Relaxed), 2);
+
+        // the end is reached
+        let loaded = load(&loader, 20..30);
+        assert_eq!(loaded.range, 20..25);
+        assert_eq!(loader.calls.load(Ordering::Relaxed), 3);
+
+        // known cursors are reused
+        let loaded = load(&loader, 10..20);
+        assert_eq!(loaded.items, (10..20).collect::<Vec<_>>());
+        assert_eq!(loader.calls.load(Ordering::Relaxed), 4);
+
+        // there is no page after the last one and the end is the last item, not the requested start
+        let loaded = load(&loader, 30..40);
+        assert!(loaded.items.is_empty());
+        assert_eq!(loaded.range, 25..25);
+        assert_eq!(loader.calls.load(Ordering::Relaxed), 4);
+    }
+}
@@ -0,0 +1,121 @@
+use std::{
+    cell::{Ref, RefCell},
+    ops::Range,
+};
+
+use super::memory_loader::load_slice;
+use crate::MemoryLoader;
+
+/// In-memory loader that only provides the items matching a predicate derived from the query.
+///
+/// The item count is the number of matching items, so a search box over local data works
+/// without writing a custom loader. The matching items are computed once per query and reused
+/// while only the range changes.
+///
+/// ```
+/// # use leptos_windowing::{FilteredMemoryLoader, MemoryLoader};
+/// #
+/// let loader = FilteredMemoryLoader::new(
+///     vec!["Dune", "Emma", "Ulysses"],
+///     |search: &String, title: &&str| title.to_lowercase().contains(&search.to_lowercase()),
+/// );
+///
+/// let search = "U".to_string();
+///
+/// assert_eq!(loader.item_count(&search), 2);
+/// assert_eq!(loader.load_items(0..10, &search), ["Dune", "Ulysses"]);
+/// ```
+pub struct FilteredMemoryLoader<T, Q, F> {
+    items: Vec<T>,
+    predicate: F,
+    matches: RefCell<Option<Matches<Q>>>,
+}
+
+impl<T, Q, F> FilteredMemoryLoader<T, Q, F>
+where
+    Q: Clone + PartialEq,
+    F: Fn(&Q, &T) -> bool,
+{
+    /// Creates a loader for `items` that only contains the ones for which `predicate` returns `true`.
+    pub fn new(items: Vec<T>, predicate: F) -> Self {
+        Self {
+            items,
+            predicate,
+            matches: RefCell::new(None),
+        }
+    }
+
+    /// The indices of the items matching `query`.
+    fn matching_indices(&self, query: &Q) -> Ref<'_, [usize]> {
+        let is_cached = self
+            .matches
+            .borrow()
+            .as_ref()
+            .is_some_and(|matches| matches.query == *query);
+
+        if !is_cached {
+            let indices = self
+                .items
+                .iter()
+                .enumerate()
+                .filter(|(_, item)| (self.predicate)(query, item))
+                .map(|(index, _)| index)
+                .collect();
+
+            *self.matches.borrow_mut() = Some(Matches {
+                query: query.clone(),
+                indices,
+            });
+        }
+
+        Ref::map(self.matches.borrow(), |matches| {
+            matches.as_ref().map_or(&[][..], |matches| &matches.indices)
+        })
+    }
+}
+
+impl<T, Q, F> MemoryLoader for FilteredMemoryLoader<T, Q, F>
+where
+    T: Clone,
+    Q: Clone + PartialEq,
+    F: Fn(&Q, &T) -> bool,
+{
+    type Item = T;
+    type Query = Q;
+
+    fn load_items(&self, range: Range<usize>, query: &Self::Query) -> Vec<Self::Item> {
+        load_slice(&self.matching_indices(query), range)
+            .into_iter()
+            .map(|index| self.items[index].clone())
+            .collect()
+    }
+
+    fn item_count(&self, query: &Self::Query) -> usize {
+        self.matching_indices(query).len()
+    }
+}
+
+/// The indices of the items matching `query`.
+struct Matches<Q> {
+    query: Q,
+    indices: Vec<usize>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter() {
+        let loader = FilteredMemoryLoader::new((0..10).collect(), |divisor: &usize, item| {
+            item % divisor == 0
+        });
+
+        assert_eq!(loader.item_count(&3), 4);
+        assert_eq!(loader.load_items(1..3, &3), [3, 6]);
+        assert_eq!(loader.load_items(3..10, &3), [9]);
+
+        assert_eq!(loader.item_count(&5), 2);
+        assert_eq!(loader.load_items(0..10, &5), [0, 5]);
+    }
+}
@@ -1,9 +1,21 @@
-use std::{fmt::Debug, ops::Range};
+use std::{fmt::Debug, ops::Range, pin::pin};
 
-use super::{ExactLoader, LoadedItems, Loader, MemoryLoader, PaginatedCount, PaginatedLoader};
+use futures::{StreamExt, stream::LocalBoxStream};
+
+use super::{
+    CursorLoader, CursorPage, ExactLoader, KeysetLoader, LiveUpdate, LoadedItems, Loader,
+    MemoryLoader, PaginatedCount, PaginatedLoader, StreamLoader,
+};
 
 /// This is the trait for the actually used internal loaders.
 /// This trait is automatically implemented for all the user facing loader traits.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can't be used as a loader",
+    label = "`{Self}` doesn't implement any of the loader traits",
+    note = "implement exactly one of `MemoryLoader`, `PaginatedLoader`, `ExactLoader`, `CursorLoader`, `KeysetLoader`, `StreamLoader` or `Loader` for `{Self}` or use one of the provided loaders like `RelayLoader`",
+    note = "if it does implement one, check that its `Item` and `Query` match the ones of the list and that `Item` is `Send + Sync + 'static`",
+    note = "use `debug_assert_loader::<{Self}, _>()` to check the loader on its own"
+)]
 pub trait InternalLoader<M> {
     /// If this Some(...) then the data will be loaded in chunks of this size.
     /// This is useful for paginated data sources.
@@ -38,6 +50,26 @@ pub trait InternalLoader<M> {
         self.load_items_inner(corrected_range, query)
     }
 
+    /// Like `load_items` but hands the loaded items to `on_loaded` as soon as they arrive, possibly
+    /// in several consecutive batches.
+    ///
+    /// Returns the range of all loaded items. By default everything is handed over in one batch.
+    fn load_items_streaming(
+        &self,
+        range: Range<usize>,
+        query: &Self::Query,
+        mut on_loaded: impl FnMut(LoadedItems<Self::Item>),
+    ) -> impl Future<Output = Result<Range<usize>, Self::Error>> {
+        async move {
+            let loaded_items = self.load_items(range, query).await?;
+            let range = loaded_items.range.clone();
+
+            on_loaded(loaded_items);
+
+            Ok(range)
+        }
+    }
+
     /// Don't call this directly. Call `load_items` instead.
     ///
     /// Loads the items respecting the given `range` and `query`.
@@ -57,6 +89,99 @@ pub trait InternalLoader<M> {
     ) -> impl Future<Output = Result<Option<usize>, Self::Error>> {
         async { Ok(None) }
     }
+
+    /// A stream of changes of the data source that are applied to the cache as they arrive.
+    ///
+    /// Returns `None` if the data source isn't live (which is the default). See [`LiveLoader`](super::LiveLoader).
+    fn updates(
+        &self,
+        _query: &Self::Query,
+    ) -> Option<LocalBoxStream<'static, LiveUpdate<Self::Item>>> {
+        None
+    }
+}
+
+/// Fails to compile if `L` isn't a loader. Doesn't do anything at runtime.
+///
+/// When passing a loader to a list component fails with a long error about trait bounds, call this
+/// with your loader type to get to the actual problem:
+///
+/// - If `L` doesn't implement any of the loader traits, the error tells you which ones you can
+///   implement.
+/// - If `L` implements several of them, the compiler can't pick one and reports
+///   "type annotations needed: cannot satisfy `L: InternalLoader<_>`" together with the
+///   conflicting implementations like `impl<L> InternalLoader<MemoryLoaderMarker> for L`. Each of
+///   them stands for one of the loader traits. Remove all but one of the implementations.
+///
+/// ```
+/// # use std::ops::Range;
+/// # use leptos_windowing::{MemoryLoader, debug_assert_loader};
+/// #
+/// pub struct BookLoader;
+///
+/// impl MemoryLoader for BookLoader {
+///     // ...
+/// #     type Item = String;
+/// #     type Query = ();
+/// #     fn load_items(&self, range: Range<usize>, _query: &()) -> Vec<String> {
+/// #         vec![]
+/// #     }
+/// #     fn item_count(&self, _query: &()) -> usize {
+/// #         0
+/// #     }
+/// }
+///
+/// debug_assert_loader::<BookLoader, _>();
+/// ```
+///
+/// ```compile_fail
+/// # use leptos_windowing::debug_assert_loader;
+/// #
+/// pub struct NotALoader;
+///
+/// // error: `NotALoader` can't be used as a loader
+/// debug_assert_loader::<NotALoader, _>();
+/// ```
+///
+/// ```compile_fail,E0283
+/// # use std::ops::Range;
+/// # use leptos_windowing::{ExactLoader, MemoryLoader, debug_assert_loader};
+/// #
+/// pub struct BookLoader;
+///
+/// impl MemoryLoader for BookLoader {
+///     // ...
+/// #     type Item = String;
+/// #     type Query = ();
+/// #     fn load_items(&self, range: Range<usize>, _query: &()) -> Vec<String> {
+/// #         vec![]
+/// #     }
+/// #     fn item_count(&self, _query: &()) -> usize {
+/// #         0
+/// #     }
+/// }
+///
+/// impl ExactLoader for BookLoader {
+///     // ...
+/// #     type Item = String;
+/// #     type Query = ();
+/// #     type Error = ();
+/// #     async fn load_items(&self, range: Range<usize>, _query: &()) -> Result<Vec<String>, ()> {
+/// #         Ok(vec![])
+/// #     }
+/// #     async fn item_count(&self, _query: &()) -> Result<Option<usize>, ()> {
+/// #         Ok(None)
+/// #     }
+/// }
+///
+/// // error[E0283]: type annotations needed: cannot satisfy `BookLoader: InternalLoader<_>`
+/// debug_assert_loader::<BookLoader, _>();
+/// ```
+#[inline(always)]
+pub fn debug_assert_loader<L, M>()
+where
+    L: InternalLoader<M>,
+{
 }
 
 pub struct LoaderMarker;
@@ -190,3 +315,153 @@ where
         })
     }
 }
+
+pub struct CursorLoaderMarker;
+
+impl<L> InternalLoader<CursorLoaderMarker> for L
+where
+    L: CursorLoader,
+{
+    const CHUNK_SIZE: Option<usize> = Some(L::PAGE_ITEM_COUNT);
+
+    type Item = L::Item;
+    type Query = L::Query;
+    type Error = L::Error;
+
+    async fn load_items_inner(
+        &self,
+        range: Range<usize>,
+        query: &Self::Query,
+    ) -> Result<LoadedItems<Self::Item>, Self::Error> {
+        self.cursors()
+            .load_range(range, L::PAGE_ITEM_COUNT, query, async |cursor| {
+                self.load_after(cursor, query).await
+            })
+            .await
+    }
+
+    #[inline]
+    async fn item_count(&self, query: &Self::Query) -> Result<Option<usize>, Self::Error> {
+        CursorLoader::item_count(self, query).await
+    }
+}
+
+pub struct KeysetLoaderMarker;
+
+impl<L> InternalLoader<KeysetLoaderMarker> for L
+where
+    L: KeysetLoader,
+{
+    const CHUNK_SIZE: Option<usize> = Some(L::PAGE_ITEM_COUNT);
+
+    type Item = L::Item;
+    type Query = L::Query;
+    type Error = L::Error;
+
+    async fn load_items_inner(
+        &self,
+        range: Range<usize>,
+        query: &Self::Query,
+    ) -> Result<LoadedItems<Self::Item>, Self::Error> {
+        self.keys()
+            .load_range(range, L::PAGE_ITEM_COUNT, query, async |key| {
+                let items = self.load_after_key(key, L::PAGE_ITEM_COUNT, query).await?;
+
+                // A short page is the last one
+                let next_cursor = if items.len() < L::PAGE_ITEM_COUNT {
+                    None
+                } else {
+                    items.last().map(L::key)
+                };
+
+                Ok(CursorPage { items, next_cursor })
+            })
+            .await
+    }
+
+    #[inline]
+    async fn item_count(&self, query: &Self::Query) -> Result<Option<usize>, Self::Error> {
+        KeysetLoader::item_count(self, query).await
+    }
+}
+
+pub struct StreamLoaderMarker;
+
+impl<L> InternalLoader<StreamLoaderMarker> for L
+where
+    L: StreamLoader,
+{
+    type Item = L::Item;
+    type Query = L::Query;
+    type Error = L::Error;
+
+    async fn load_items_inner(
+        &self,
+        range: Range<usize>,
+        query: &Self::Query,
+    ) -> Result<LoadedItems<Self::Item>, Self::Error> {
+        let start = range.start;
+        let mut items = Vec::with_capacity(range.len());
+
+        let mut stream = pin!(StreamLoader::load_items(self, range, query));
+        while let Some(item) = stream.next().await {
+            items.push(item?);
+        }
+
+        let len = items.len();
+        Ok(LoadedItems {
+            items,
+            range: start..start + len,
+        })
+    }
+
+    async fn load_items_streaming(
+        &self,
+        range: Range<usize>,
+        query: &Self::Query,
+        mut on_loaded: impl FnMut(LoadedItems<Self::Item>),
+    ) -> Result<Range<usize>, Self::Error> {
+        let start = range.start;
+        let mut end = start;
+
+        // Everything that is ready at the same time is handed over as one batch
+        let mut chunks = pin!(
+            StreamLoader::load_items(self, range.clone(), query).ready_chunks(range.len().max(1))
+        );
+
+        while let Some(chunk) = chunks.next().await {
+            let mut items = Vec::with_capacity(chunk.len());
+            let mut error = None;
+
+            for item in chunk {
+                match item {
+                    Ok(item) => items.push(item),
+                    Err(err) => {
+                        error = Some(err);
+                        break;
+                    }
+                }
+            }
+
+            if !items.is_empty() {
+                let len = items.len();
+                on_loaded(LoadedItems {
+                    items,
+                    range: end..end + len,
+                });
+                end += len;
+            }
+
+            if let Some(error) = error {
+                return Err(error);
+            }
+        }
+
+        Ok(start..end)
+    }
+
+    #[inline]
+    async fn item_count(&self, query: &Self::Query) -> Result<Option<usize>, Self::Error> {
+        StreamLoader::item_count(self, query).await
+    }
+}
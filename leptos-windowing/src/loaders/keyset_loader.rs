@@ -0,0 +1,154 @@
+use std::fmt::Debug;
+
+use crate::CursorCache;
+
+/// Loader trait for keyset (also called seek) pagination where a page is loaded by the sort key of
+/// the last item of the previous page, e.g. `WHERE id > $last_id ORDER BY id LIMIT $limit`.
+///
+/// This is common for large SQL tables where `OFFSET` is too slow. The boundary keys of the pages are
+/// remembered in [`KeysetLoader::keys`] so arbitrary forward navigation works. Jumping to a page whose
+/// key isn't known yet loads the pages before it one after the other.
+///
+/// ## Example
+///
+/// ```
+/// # use leptos_windowing::{CursorCache, KeysetLoader};
+/// #
+/// pub struct Order {
+///     id: u64,
+/// }
+///
+/// pub struct OrderLoader {
+///     keys: CursorCache<u64, ()>,
+/// }
+///
+/// impl KeysetLoader for OrderLoader {
+///     const PAGE_ITEM_COUNT: usize = 100;
+///
+///     type Item = Order;
+///     type Query = ();
+///     type Key = u64;
+///     type Error = ();
+///
+///     fn key(item: &Self::Item) -> Self::Key {
+///         item.id
+///     }
+///
+///     async fn load_after_key(
+///         &self,
+///         key: Option<&Self::Key>,
+///         limit: usize,
+///         query: &Self::Query,
+///     ) -> Result<Vec<Self::Item>, Self::Error> {
+///         // SELECT * FROM orders WHERE id > $key ORDER BY id LIMIT $limit
+///         # let _ = (key, limit);
+///         # Ok(vec![])
+///     }
+///
+///     fn keys(&self) -> &CursorCache<Self::Key, Self::Query> {
+///         &self.keys
+///     }
+/// }
+/// ```
+pub trait KeysetLoader {
+    /// How many items are loaded per page.
+    const PAGE_ITEM_COUNT: usize;
+
+    /// The type of items that will be loaded.
+    type Item;
+
+    /// The type of the query data that will be used to load items.
+    ///
+    /// When it changes, the remembered keys are discarded.
+    type Query: PartialEq + Clone;
+
+    /// The sort key of the items.
+    type Key: Clone;
+
+    /// The type of errors that can occur during loading.
+    type Error: Debug + 'static;
+
+    /// Returns the sort key of the item.
+    fn key(item: &Self::Item) -> Self::Key;
+
+    /// Loads at most `limit` items that come after `key` in sort order. `None` means from the start.
+    ///
+    /// If you return less than `limit` items, it is assumed that the end of the data has been reached.
+    fn load_after_key(
+        &self,
+        key: Option<&Self::Key>,
+        limit: usize,
+        query: &Self::Query,
+    ) -> impl Future<Output = Result<Vec<Self::Item>, Self::Error>>;
+
+    /// The total number of items of this data source with respect to the query.
+    ///
+    /// Returns `Ok(None)` if unknown (which is the default).
+    fn item_count(
+        &self,
+        _query: &Self::Query,
+    ) -> impl Future<Output = Result<Option<usize>, Self::Error>> {
+        async { Ok(None) }
+    }
+
+    /// Storage for the boundary keys of the pages that have been loaded so far.
+    ///
+    /// Just add a field with a [`CursorCache::default()`] to your loader and return it here.
+    fn keys(&self) -> &CursorCache<Self::Key, Self::Query>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::loaders::test_helpers::load;
+
+    /// The ids 1, 3, 5, ..., 49
+    struct TestLoader {
+        keys: CursorCache<u32, ()>,
+    }
+
+    impl KeysetLoader for TestLoader {
+        const PAGE_ITEM_COUNT: usize = 10;
+
+        type Item = u32;
+        type Query = ();
+        type Key = u32;
+        type Error = ();
+
+        fn key(item: &u32) -> u32 {
+            *item
+        }
+
+        async fn load_after_key(
+            &self,
+            key: Option<&u32>,
+            limit: usize,
+            _query: &(),
+        ) -> Result<Vec<u32>, ()> {
+            Ok((1..50)
+                .step_by(2)
+                .filter(|id| key.is_none_or(|key| id > key))
+                .take(limit)
+                .collect())
+        }
+
+        fn keys(&self) -> &CursorCache<u32, ()> {
+            &self.keys
+        }
+    }
+
+    #[test]
+    fn test_keyset_loader() {
+        let loader = TestLoader {
+            keys: CursorCache::default(),
+        };
+
+        let loaded = load(&loader, 20..30);
+        assert_eq!(loaded.range, 0..25);
+        assert_eq!(loaded.items[20..], [41, 43, 45, 47, 49]);
+
+        let loaded = load(&loader, 10..20);
+        assert_eq!(loaded.range, 10..20);
+        assert_eq!(loaded.items[0], 21);
+    }
+}
@@ -0,0 +1,144 @@
+use std::{marker::PhantomData, ops::Range};
+
+use futures::{Stream, StreamExt, stream::LocalBoxStream};
+
+use super::{InternalLoader, LoadedItems};
+
+/// Wraps a loader and keeps the loaded items in sync with a live feed like a WebSocket.
+///
+/// `updates` is called with the current query and returns a stream of [`LiveUpdate`]s that are
+/// applied to the cache automatically. When the query changes or the list is reloaded, the old stream
+/// is dropped and `updates` is called again.
+///
+/// ```
+/// # use std::ops::Range;
+/// # use futures::stream;
+/// # use leptos_windowing::{LiveLoader, LiveUpdate, MemoryLoader};
+/// #
+/// pub struct Message {
+///     text: String,
+/// }
+///
+/// pub struct MessageLoader;
+///
+/// impl MemoryLoader for MessageLoader {
+///     // ...
+/// #     type Item = Message;
+/// #     type Query = ();
+/// #     fn load_items(&self, range: Range<usize>, _query: &()) -> Vec<Message> {
+/// #         vec![]
+/// #     }
+/// #     fn item_count(&self, _query: &()) -> usize {
+/// #         0
+/// #     }
+/// }
+///
+/// let loader = LiveLoader::new(MessageLoader, |_query: &()| {
+///     // Map the messages of your WebSocket connection to `LiveUpdate`s here.
+///     stream::iter([LiveUpdate::Insert {
+///         index: 0,
+///         item: Message { text: "Hello".to_string() },
+///     }])
+/// });
+/// ```
+pub struct LiveLoader<L, M, F> {
+    loader: L,
+    updates: F,
+    _marker: PhantomData<fn() -> M>,
+}
+
+impl<L, M, F, S> LiveLoader<L, M, F>
+where
+    L: InternalLoader<M>,
+    F: Fn(&L::Query) -> S,
+    S: Stream<Item = LiveUpdate<L::Item>> + 'static,
+{
+    /// Wraps `loader` and applies the updates returned by `updates` to the cache.
+    pub fn new(loader: L, updates: F) -> Self {
+        Self {
+            loader,
+            updates,
+            _marker: PhantomData,
+        }
+    }
+}
+
+pub struct LiveLoaderMarker<M>(PhantomData<M>);
+
+impl<L, M, F, S> InternalLoader<LiveLoaderMarker<M>> for LiveLoader<L, M, F>
+where
+    L: InternalLoader<M>,
+    F: Fn(&L::Query) -> S,
+    S: Stream<Item = LiveUpdate<L::Item>> + 'static,
+{
+    const CHUNK_SIZE: Option<usize> = L::CHUNK_SIZE;
+
+    type Item = L::Item;
+    type Query = L::Query;
+    type Error = L::Error;
+
+    #[inline]
+    async fn load_items_inner(
+        &self,
+        range: Range<usize>,
+        query: &Self::Query,
+    ) -> Result<LoadedItems<Self::Item>, Self::Error> {
+        self.loader.load_items_inner(range, query).await
+    }
+
+    #[inline]
+    async fn load_items_streaming(
+        &self,
+        range: Range<usize>,
+        query: &Self::Query,
+        on_loaded: impl FnMut(LoadedItems<Self::Item>),
+    ) -> Result<Range<usize>, Self::Error> {
+        self.loader
+            .load_items_streaming(range, query, on_loaded)
+            .await
+    }
+
+    #[inline]
+    async fn item_count(&self, query: &Self::Query) -> Result<Option<usize>, Self::Error> {
+        self.loader.item_count(query).await
+    }
+
+    fn updates(
+        &self,
+        query: &Self::Query,
+    ) -> Option<LocalBoxStream<'static, LiveUpdate<Self::Item>>> {
+        Some((self.updates)(query).boxed_local())
+    }
+}
+
+/// A change of the data source that is pushed by a [`LiveLoader`].
+///
+/// The indices refer to the positions in the list with respect to the current query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LiveUpdate<T> {
+    /// A new item has been inserted at `index`.
+    Insert { index: usize, item: T },
+
+    /// The item at `index` has changed.
+    Update { index: usize, item: T },
+
+    /// The item at `index` has been deleted.
+    Delete { index: usize },
+}
+
+impl<T> LiveUpdate<T> {
+    /// Converts the item of the update with `f`.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> LiveUpdate<U> {
+        match self {
+            LiveUpdate::Insert { index, item } => LiveUpdate::Insert {
+                index,
+                item: f(item),
+            },
+            LiveUpdate::Update { index, item } => LiveUpdate::Update {
+                index,
+                item: f(item),
+            },
+            LiveUpdate::Delete { index } => LiveUpdate::Delete { index },
+        }
+    }
+}
@@ -1,5 +1,7 @@
 use std::ops::Range;
 
+use leptos::prelude::*;
+
 /// Loader trait for loading items on-demand from an in-memory data source.
 ///
 /// In this case we don't need async methods and everything is simple and synchronous.
@@ -18,3 +20,75 @@ pub trait MemoryLoader {
     /// The total number of items of this data source with respect to the query.
     fn item_count(&self, query: &Self::Query) -> usize;
 }
+
+/// A vector can be used as a loader directly.
+///
+/// ```
+/// # use leptos_windowing::MemoryLoader;
+/// #
+/// let books = vec!["Dune", "Emma", "Ulysses"];
+///
+/// assert_eq!(books.load_items(1..5, &()), ["Emma", "Ulysses"]);
+/// assert_eq!(books.item_count(&()), 3);
+/// ```
+impl<T: Clone> MemoryLoader for Vec<T> {
+    type Item = T;
+    type Query = ();
+
+    #[inline]
+    fn load_items(&self, range: Range<usize>, _query: &Self::Query) -> Vec<Self::Item> {
+        load_slice(self, range)
+    }
+
+    #[inline]
+    fn item_count(&self, _query: &Self::Query) -> usize {
+        self.len()
+    }
+}
+
+/// A slice can be used as a loader directly. As loaders have to be `'static` this is mostly
+/// useful for `&'static [T]` like constants.
+impl<T: Clone> MemoryLoader for &[T] {
+    type Item = T;
+    type Query = ();
+
+    #[inline]
+    fn load_items(&self, range: Range<usize>, _query: &Self::Query) -> Vec<Self::Item> {
+        load_slice(self, range)
+    }
+
+    #[inline]
+    fn item_count(&self, _query: &Self::Query) -> usize {
+        self.len()
+    }
+}
+
+/// A signal of a vector can be used as a loader directly.
+///
+/// The loader doesn't track the signal. Call `reload` on the cache or the window controller
+/// after changing it.
+impl<T> MemoryLoader for Signal<Vec<T>>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    type Item = T;
+    type Query = ();
+
+    #[inline]
+    fn load_items(&self, range: Range<usize>, _query: &Self::Query) -> Vec<Self::Item> {
+        self.with_untracked(|items| load_slice(items, range))
+    }
+
+    #[inline]
+    fn item_count(&self, _query: &Self::Query) -> usize {
+        self.with_untracked(Vec::len)
+    }
+}
+
+/// Clones the items of `range` that exist in `items`.
+pub(crate) fn load_slice<T: Clone>(items: &[T], range: Range<usize>) -> Vec<T> {
+    items
+        .get(range.start.min(items.len())..range.end.min(items.len()))
+        .unwrap_or_default()
+        .to_vec()
+}
@@ -0,0 +1,255 @@
+use std::{
+    ops::Range,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use super::{LoadedItems, Loader};
+
+/// Loader for tests and demos that serves a fixed dataset. Requires the feature `test-utils`.
+///
+/// Every load can be delayed by a `latency` and made to fail, either at random with an
+/// `error_rate` or always for the items in a `failing_range`. The random failures come from a
+/// seeded generator so a test run is always the same.
+///
+/// All calls are recorded. Because clones share the recording, keep a clone of the loader to
+/// check which ranges the list loaded.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use leptos_windowing::{MockLoader, MockLoaderCall};
+/// #
+/// let loader = MockLoader::new((0..100).collect::<Vec<usize>>())
+///     .latency(Duration::from_millis(300))
+///     .error_rate(0.1)
+///     .failing_range(50..60);
+///
+/// // Pass a clone to the list and inspect the calls afterwards
+/// let list_loader = loader.clone();
+///
+/// assert_eq!(loader.calls(), Vec::<MockLoaderCall>::new());
+/// ```
+pub struct MockLoader<T> {
+    items: Arc<Vec<T>>,
+    latency: Duration,
+    error_rate: f64,
+    failing_ranges: Vec<Range<usize>>,
+    state: Arc<Mutex<MockLoaderState>>,
+}
+
+impl<T> Clone for MockLoader<T> {
+    fn clone(&self) -> Self {
+        Self {
+            items: Arc::clone(&self.items),
+            latency: self.latency,
+            error_rate: self.error_rate,
+            failing_ranges: self.failing_ranges.clone(),
+            state: Arc::clone(&self.state),
+        }
+    }
+}
+
+/// A call of a [`MockLoader`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MockLoaderCall {
+    /// Items in this range were requested.
+    LoadItems(Range<usize>),
+    /// The item count was requested.
+    ItemCount,
+}
+
+struct MockLoaderState {
+    calls: Vec<MockLoaderCall>,
+    random_state: u64,
+}
+
+impl<T> MockLoader<T> {
+    /// Creates a loader that serves `items` without latency or errors.
+    pub fn new(items: Vec<T>) -> Self {
+        Self {
+            items: Arc::new(items),
+            latency: Duration::ZERO,
+            error_rate: 0.0,
+            failing_ranges: Vec::new(),
+            state: Arc::new(Mutex::new(MockLoaderState {
+                calls: Vec::new(),
+                random_state: DEFAULT_SEED,
+            })),
+        }
+    }
+
+    /// How long every call takes. Only applied in the browser, on the server the calls return
+    /// right away.
+    pub fn latency(self, latency: Duration) -> Self {
+        Self { latency, ..self }
+    }
+
+    /// The probability between 0 and 1 that a load of items fails.
+    pub fn error_rate(self, error_rate: f64) -> Self {
+        Self { error_rate, ..self }
+    }
+
+    /// Seeds the generator that decides which loads fail with `error_rate`.
+    pub fn seed(self, seed: u64) -> Self {
+        // The generator gets stuck at 0
+        self.state.lock().unwrap().random_state = seed.max(1);
+        self
+    }
+
+    /// Loads of items that overlap with `range` always fail.
+    pub fn failing_range(mut self, range: Range<usize>) -> Self {
+        self.failing_ranges.push(range);
+        self
+    }
+
+    /// All calls so far, in the order they were made.
+    pub fn calls(&self) -> Vec<MockLoaderCall> {
+        self.state.lock().unwrap().calls.clone()
+    }
+
+    /// The ranges of all loads of items so far, in the order they were made.
+    pub fn loaded_ranges(&self) -> Vec<Range<usize>> {
+        self.state
+            .lock()
+            .unwrap()
+            .calls
+            .iter()
+            .filter_map(|call| match call {
+                MockLoaderCall::LoadItems(range) => Some(range.clone()),
+                MockLoaderCall::ItemCount => None,
+            })
+            .collect()
+    }
+
+    /// Forgets the recorded calls.
+    pub fn clear_calls(&self) {
+        self.state.lock().unwrap().calls.clear();
+    }
+
+    /// Records `call` and decides if it fails.
+    fn record(&self, call: MockLoaderCall) -> Result<(), String> {
+        let mut state = self.state.lock().unwrap();
+
+        let result = match &call {
+            MockLoaderCall::LoadItems(range) => {
+                if self
+                    .failing_ranges
+                    .iter()
+                    .any(|failing| failing.start < range.end && range.start < failing.end)
+                {
+                    Err(format!("Mock error in the failing range for {range:?}"))
+                } else if self.error_rate > 0.0 && state.next_random() < self.error_rate {
+                    Err(format!("Random mock error for {range:?}"))
+                } else {
+                    Ok(())
+                }
+            }
+            MockLoaderCall::ItemCount => Ok(()),
+        };
+
+        state.calls.push(call);
+
+        result
+    }
+
+    async fn wait(&self) {
+        #[cfg(not(feature = "ssr"))]
+        if !self.latency.is_zero() {
+            crate::hook::sleep(self.latency).await;
+        }
+    }
+}
+
+const DEFAULT_SEED: u64 = 0x2545_f491_4f6c_dd1d;
+
+impl MockLoaderState {
+    /// Next pseudo random number between 0 and 1 (xorshift).
+    fn next_random(&mut self) -> f64 {
+        let mut x = self.random_state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.random_state = x;
+
+        (x >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+impl<T: Clone> Loader for MockLoader<T> {
+    type Item = T;
+    type Query = ();
+    type Error = String;
+
+    async fn load_items(
+        &self,
+        range: Range<usize>,
+        _query: &Self::Query,
+    ) -> Result<LoadedItems<Self::Item>, Self::Error> {
+        let result = self.record(MockLoaderCall::LoadItems(range.clone()));
+        self.wait().await;
+        result?;
+
+        let end = range.end.min(self.items.len());
+        let range = range.start.min(end)..end;
+
+        Ok(LoadedItems {
+            items: self.items[range.clone()].to_vec(),
+            range,
+        })
+    }
+
+    async fn item_count(&self, _query: &Self::Query) -> Result<Option<usize>, Self::Error> {
+        let result = self.record(MockLoaderCall::ItemCount);
+        self.wait().await;
+        result?;
+
+        Ok(Some(self.items.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+
+    use super::*;
+
+    #[test]
+    fn test_mock_loader() {
+        let loader = MockLoader::new((0..10).collect::<Vec<usize>>()).failing_range(4..6);
+        let list_loader = loader.clone();
+
+        let loaded = block_on(Loader::load_items(&list_loader, 8..12, &())).unwrap();
+        assert_eq!((loaded.items, loaded.range), (vec![8, 9], 8..10));
+
+        assert!(block_on(Loader::load_items(&list_loader, 0..5, &())).is_err());
+        assert_eq!(
+            block_on(Loader::item_count(&list_loader, &())),
+            Ok(Some(10))
+        );
+
+        assert_eq!(
+            loader.calls(),
+            [
+                MockLoaderCall::LoadItems(8..12),
+                MockLoaderCall::LoadItems(0..5),
+                MockLoaderCall::ItemCount,
+            ]
+        );
+        assert_eq!(loader.loaded_ranges(), [8..12, 0..5]);
+    }
+
+    #[test]
+    fn test_error_rate_is_deterministic() {
+        let failures = |seed| {
+            let loader = MockLoader::new(vec![0; 100]).error_rate(0.5).seed(seed);
+
+            (0..20)
+                .map(|i| block_on(Loader::load_items(&loader, i..i + 1, &())).is_err())
+                .collect::<Vec<_>>()
+        };
+
+        let first = failures(7);
+        assert_eq!(first, failures(7));
+        assert!(first.contains(&true) && first.contains(&false));
+    }
+}
@@ -1,11 +1,51 @@
+mod cached_loader;
+mod combinators;
+mod cursor_loader;
 mod exact_loader;
+mod filtered_memory_loader;
 mod internal_loader;
+mod keyset_loader;
+mod live_loader;
 mod loader;
 mod memory_loader;
+#[cfg(feature = "test-utils")]
+mod mock_loader;
+mod mutable_loader;
+mod normalized_loader;
+#[cfg(feature = "odata")]
+mod odata_loader;
 mod paginated_loader;
+mod relay_loader;
+mod sortable_memory_loader;
+#[cfg(feature = "start-after")]
+mod start_after_loader;
+mod stream_loader;
+#[cfg(test)]
+mod test_helpers;
+#[cfg(feature = "worker")]
+mod worker_loader;
 
+pub use cached_loader::*;
+pub use combinators::*;
+pub use cursor_loader::*;
 pub use exact_loader::*;
+pub use filtered_memory_loader::*;
 pub use internal_loader::*;
+pub use keyset_loader::*;
+pub use live_loader::*;
 pub use loader::*;
 pub use memory_loader::*;
+#[cfg(feature = "test-utils")]
+pub use mock_loader::*;
+pub use mutable_loader::*;
+pub use normalized_loader::*;
+#[cfg(feature = "odata")]
+pub use odata_loader::*;
 pub use paginated_loader::*;
+pub use relay_loader::*;
+pub use sortable_memory_loader::*;
+#[cfg(feature = "start-after")]
+pub use start_after_loader::*;
+pub use stream_loader::*;
+#[cfg(feature = "worker")]
+pub use worker_loader::*;
@@ -0,0 +1,24 @@
+use std::fmt::Debug;
+
+use super::LiveUpdate;
+
+/// Loader trait for data sources that can be changed from the client.
+///
+/// This is used by [`use_mutation_outbox`](crate::use_mutation_outbox) to write the changes that
+/// were made to the cache back to the data source. It's usually implemented by the same struct
+/// as the loader of the list.
+pub trait MutableLoader {
+    /// The type of the items that are changed.
+    type Item;
+
+    /// The type of errors that can occur while applying a change.
+    type Error: Debug + 'static;
+
+    /// Applies the change to the data source, e.g. by sending it to your API.
+    ///
+    /// The indices refer to the positions in the list at the time the change was made.
+    fn apply(
+        &self,
+        change: &LiveUpdate<Self::Item>,
+    ) -> impl Future<Output = Result<(), Self::Error>>;
+}
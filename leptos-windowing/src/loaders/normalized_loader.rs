@@ -0,0 +1,106 @@
+use std::{hash::Hash, marker::PhantomData, ops::Range};
+
+use super::{InternalLoader, LoadedItems, Loader};
+use crate::{Entity, EntityStore};
+
+/// Wraps a loader and puts all loaded items into an [`EntityStore`].
+///
+/// The lists then only contain [`Entity`] handles. This way the same entity appearing in multiple lists
+/// stays consistent when it's updated once in the store.
+///
+/// ```
+/// # use std::ops::Range;
+/// # use leptos_windowing::{EntityStore, MemoryLoader, NormalizedLoader};
+/// #
+/// pub struct Order {
+///     id: u32,
+/// }
+///
+/// pub struct OrderLoader;
+///
+/// impl MemoryLoader for OrderLoader {
+///     // ...
+/// #     type Item = Order;
+/// #     type Query = ();
+/// #     fn load_items(&self, range: Range<usize>, _query: &()) -> Vec<Order> {
+/// #         vec![]
+/// #     }
+/// #     fn item_count(&self, _query: &()) -> usize {
+/// #         0
+/// #     }
+/// }
+///
+/// let store = EntityStore::new();
+///
+/// // Use this as the loader for a list. Use the same store for all lists that should be consistent.
+/// let loader = NormalizedLoader::new(OrderLoader, store, |order: &Order| order.id);
+/// ```
+pub struct NormalizedLoader<L, M, K, F>
+where
+    L: InternalLoader<M>,
+    L::Item: Send + Sync + 'static,
+    K: Eq + Hash + Clone + Send + Sync + 'static,
+    F: Fn(&L::Item) -> K,
+{
+    loader: L,
+    store: EntityStore<K, L::Item>,
+    key: F,
+    _marker: PhantomData<fn() -> M>,
+}
+
+impl<L, M, K, F> NormalizedLoader<L, M, K, F>
+where
+    L: InternalLoader<M>,
+    L::Item: Send + Sync + 'static,
+    K: Eq + Hash + Clone + Send + Sync + 'static,
+    F: Fn(&L::Item) -> K,
+{
+    /// Wraps `loader` and stores all loaded items in `store` by the key returned by `key`.
+    pub fn new(loader: L, store: EntityStore<K, L::Item>, key: F) -> Self {
+        Self {
+            loader,
+            store,
+            key,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The store that the loaded items are put into.
+    pub fn store(&self) -> EntityStore<K, L::Item> {
+        self.store
+    }
+}
+
+impl<L, M, K, F> Loader for NormalizedLoader<L, M, K, F>
+where
+    L: InternalLoader<M>,
+    L::Item: Send + Sync + 'static,
+    K: Eq + Hash + Clone + Send + Sync + 'static,
+    F: Fn(&L::Item) -> K,
+{
+    const CHUNK_SIZE: Option<usize> = L::CHUNK_SIZE;
+
+    type Item = Entity<K, L::Item>;
+    type Query = L::Query;
+    type Error = L::Error;
+
+    async fn load_items(
+        &self,
+        range: Range<usize>,
+        query: &Self::Query,
+    ) -> Result<LoadedItems<Self::Item>, Self::Error> {
+        let LoadedItems { items, range } = self.loader.load_items(range, query).await?;
+
+        Ok(LoadedItems {
+            items: items
+                .into_iter()
+                .map(|item| self.store.upsert((self.key)(&item), item))
+                .collect(),
+            range,
+        })
+    }
+
+    async fn item_count(&self, query: &Self::Query) -> Result<Option<usize>, Self::Error> {
+        self.loader.item_count(query).await
+    }
+}
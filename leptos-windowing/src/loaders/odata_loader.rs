@@ -0,0 +1,292 @@
+use std::{fmt::Debug, marker::PhantomData, ops::Range};
+
+use serde::{Deserialize, de::DeserializeOwned};
+
+use crate::{ListQuery, LoadedItems, Loader, SortMode, odata_order_by};
+
+/// Loader for OData services which covers a large class of enterprise APIs.
+///
+/// The range, sorting, filter and search are mapped to the `$skip`, `$top`, `$orderby`, `$filter`, `$search`
+/// and `$count` query parameters of the given entity set URL. The items are deserialized from the `value` array of
+/// the response and the total count is taken from `@odata.count`.
+///
+/// The HTTP request is up to you: `fetch` is called with the complete URL and returns the response body.
+///
+/// This is only available with the feature `odata`.
+///
+/// ## Example
+///
+/// ```
+/// # use leptos_windowing::ODataLoader;
+/// # use serde::Deserialize;
+/// #
+/// #[derive(Deserialize)]
+/// pub struct Customer {
+///     #[serde(rename = "CompanyName")]
+///     company_name: String,
+/// }
+///
+/// let loader = ODataLoader::<Customer, String, _>::new(
+///     "https://services.odata.org/V4/Northwind/Northwind.svc/Customers",
+///     async |url: String| -> Result<String, String> {
+///         // Use your HTTP client here, e.g. `gloo_net::http::Request::get(&url).send().await?.text().await`
+///         # let _ = url;
+///         # Ok(String::new())
+///     },
+/// );
+/// ```
+pub struct ODataLoader<T, E, F> {
+    url: String,
+    fetch: F,
+    _marker: PhantomData<fn() -> (T, E)>,
+}
+
+impl<T, E, F> ODataLoader<T, E, F>
+where
+    T: DeserializeOwned,
+    E: Debug + 'static,
+    F: AsyncFn(String) -> Result<String, E>,
+{
+    /// Creates a new loader for the entity set at `url`.
+    ///
+    /// `fetch` performs a GET request to the given URL and returns the response body.
+    pub fn new(url: impl Into<String>, fetch: F) -> Self {
+        Self {
+            url: url.into(),
+            fetch,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the URL for loading the items in `range`. If `range` is `None` only the count is requested.
+    fn request_url(&self, range: Option<Range<usize>>, query: &ODataQuery) -> String {
+        let mut params = match range {
+            Some(range) => vec![
+                format!("$skip={}", range.start),
+                format!("$top={}", range.len()),
+            ],
+            None => vec!["$top=0".to_string()],
+        };
+        params.push("$count=true".to_string());
+
+        if let Some(order_by) = odata_order_by(&query.order_by, |column| Some(column.as_str())) {
+            params.push(format!("$orderby={}", encode_query_value(&order_by)));
+        }
+
+        if let Some(filter) = &query.filter {
+            params.push(format!("$filter={}", encode_query_value(filter)));
+        }
+
+        if let Some(search) = query.search.as_ref().filter(|search| !search.is_empty()) {
+            params.push(format!("$search={}", encode_query_value(search)));
+        }
+
+        let separator = if self.url.contains('?') { '&' } else { '?' };
+
+        format!("{}{separator}{}", self.url, params.join("&"))
+    }
+
+    async fn request(
+        &self,
+        range: Option<Range<usize>>,
+        query: &ODataQuery,
+    ) -> Result<ODataResponse<T>, ODataError<E>> {
+        let body = (self.fetch)(self.request_url(range, query))
+            .await
+            .map_err(ODataError::Fetch)?;
+
+        serde_json::from_str(&body).map_err(ODataError::Parse)
+    }
+}
+
+impl<T, E, F> Loader for ODataLoader<T, E, F>
+where
+    T: DeserializeOwned,
+    E: Debug + 'static,
+    F: AsyncFn(String) -> Result<String, E>,
+{
+    type Item = T;
+    type Query = ODataQuery;
+    type Error = ODataError<E>;
+
+    async fn load_items(
+        &self,
+        range: Range<usize>,
+        query: &Self::Query,
+    ) -> Result<LoadedItems<Self::Item>, Self::Error> {
+        let start = range.start;
+        let items = self.request(Some(range), query).await?.value;
+
+        Ok(LoadedItems {
+            range: start..start + items.len(),
+            items,
+        })
+    }
+
+    async fn item_count(&self, query: &Self::Query) -> Result<Option<usize>, Self::Error> {
+        Ok(self.request(None, query).await?.count)
+    }
+}
+
+/// The query of an [`ODataLoader`].
+///
+/// A [`ListQuery`] can be converted into this with [`TryFrom`]. Its filters are combined with `and` as `eq`
+/// comparisons. As the field names end up in `$filter` and `$orderby` unescaped, only names matching
+/// `[A-Za-z_][A-Za-z0-9_/]*` are accepted and anything else is rejected with an [`InvalidODataField`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ODataQuery {
+    /// The properties to sort by. Mapped to `$orderby`.
+    pub order_by: Vec<(String, SortMode)>,
+
+    /// An OData filter expression like `"Country eq 'Germany'"`. Mapped to `$filter`.
+    pub filter: Option<String>,
+
+    /// A free-text search term. Mapped to `$search`.
+    pub search: Option<String>,
+}
+
+impl<X> TryFrom<ListQuery<X>> for ODataQuery {
+    type Error = InvalidODataField;
+
+    fn try_from(query: ListQuery<X>) -> Result<Self, Self::Error> {
+        if let Some(field) = query
+            .filters
+            .keys()
+            .chain(query.sorting.iter().map(|(column, _)| column))
+            .find(|field| !is_valid_field(field))
+        {
+            return Err(InvalidODataField(field.clone()));
+        }
+
+        let filter = query
+            .filters
+            .iter()
+            .map(|(field, value)| format!("{field} eq '{}'", value.replace('\'', "''")))
+            .collect::<Vec<_>>()
+            .join(" and ");
+
+        Ok(Self {
+            order_by: query.sorting,
+            filter: (!filter.is_empty()).then_some(filter),
+            search: query.search,
+        })
+    }
+}
+
+/// Error of converting a [`ListQuery`] into an [`ODataQuery`]. Contains the field name that isn't a valid
+/// OData property path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidODataField(pub String);
+
+/// Whether `field` matches `[A-Za-z_][A-Za-z0-9_/]*` so it can't inject anything into an expression.
+fn is_valid_field(field: &str) -> bool {
+    let mut chars = field.chars();
+
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '/')
+}
+
+/// Error type of [`ODataLoader`].
+#[derive(Debug)]
+pub enum ODataError<E> {
+    /// The `fetch` function returned an error.
+    Fetch(E),
+
+    /// The response couldn't be parsed.
+    Parse(serde_json::Error),
+}
+
+#[derive(Deserialize)]
+#[serde(bound = "T: DeserializeOwned")]
+struct ODataResponse<T> {
+    #[serde(default = "Vec::new")]
+    value: Vec<T>,
+
+    #[serde(rename = "@odata.count", default)]
+    count: Option<usize>,
+}
+
+/// Percent-encodes everything except the unreserved characters of RFC 3986.
+fn encode_query_value(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_url_and_response() {
+        let loader = ODataLoader::<String, (), _>::new(
+            "https://example.com/odata/Customers",
+            async |_url: String| -> Result<String, ()> { Ok(String::new()) },
+        );
+
+        let query = ODataQuery {
+            order_by: vec![
+                ("Name".to_string(), SortMode::Ascending),
+                ("City".to_string(), SortMode::Descending),
+            ],
+            filter: Some("Country eq 'Germany'".to_string()),
+            search: None,
+        };
+
+        assert_eq!(
+            loader.request_url(Some(20..30), &query),
+            "https://example.com/odata/Customers?$skip=20&$top=10&$count=true\
+             &$orderby=Name%20asc%2CCity%20desc&$filter=Country%20eq%20%27Germany%27"
+        );
+        assert_eq!(
+            loader.request_url(None, &ODataQuery::default()),
+            "https://example.com/odata/Customers?$top=0&$count=true"
+        );
+
+        let query = ODataQuery::try_from(
+            ListQuery::new()
+                .search("GmbH")
+                .filter("City", "Köln")
+                .filter("Country", "Germany's"),
+        )
+        .unwrap();
+        assert_eq!(
+            query.filter.as_deref(),
+            Some("City eq 'Köln' and Country eq 'Germany''s'")
+        );
+        assert_eq!(
+            loader.request_url(Some(0..10), &query),
+            "https://example.com/odata/Customers?$skip=0&$top=10&$count=true\
+             &$filter=City%20eq%20%27K%C3%B6ln%27%20and%20Country%20eq%20%27Germany%27%27s%27&$search=GmbH"
+        );
+
+        assert_eq!(
+            ODataQuery::try_from(ListQuery::new().filter("Address/City", "Köln"))
+                .map(|query| query.filter),
+            Ok(Some("Address/City eq 'Köln'".to_string()))
+        );
+        assert_eq!(
+            ODataQuery::try_from(ListQuery::new().filter("City eq 'x' or true", "Köln")),
+            Err(InvalidODataField("City eq 'x' or true".to_string()))
+        );
+        assert_eq!(
+            ODataQuery::try_from(ListQuery::new().sort("1Name", SortMode::Ascending)),
+            Err(InvalidODataField("1Name".to_string()))
+        );
+
+        let response: ODataResponse<String> =
+            serde_json::from_str(r#"{"@odata.count": 42, "value": ["a", "b"]}"#).unwrap();
+        assert_eq!(response.count, Some(42));
+        assert_eq!(response.value, vec!["a", "b"]);
+    }
+}
@@ -50,3 +50,109 @@ pub enum PaginatedCount {
     /// If your data source tells you how many items there are, then use this.
     Items(usize),
 }
+
+impl PaginatedCount {
+    /// Reads the count from the headers of a REST response. Many APIs only return the total there.
+    ///
+    /// `header` returns the value of the header with the given name. The headers are tried in this
+    /// order:
+    /// - `X-Total-Count`: The number of items. See [`PaginatedCount::from_total_count_header`].
+    /// - `Link`: The number of pages from the link to the last page like GitHub does.
+    ///   See [`PaginatedCount::from_link_header`].
+    ///
+    /// ```
+    /// # use leptos_windowing::PaginatedCount;
+    /// #
+    /// let header = |name: &str| match name {
+    ///     "Link" => Some(r#"<https://api.example.com/items?page=2>; rel="next", <https://api.example.com/items?page=34>; rel="last""#.to_string()),
+    ///     _ => None,
+    /// };
+    ///
+    /// assert_eq!(PaginatedCount::from_headers(header, "page"), Some(PaginatedCount::Pages(34)));
+    /// ```
+    ///
+    /// With `gloo-net` for example you would call it like this in [`PaginatedLoader::count`]:
+    ///
+    /// ```ignore
+    /// let response = Request::get(url).send().await?;
+    /// Ok(PaginatedCount::from_headers(|name| response.headers().get(name), "page"))
+    /// ```
+    pub fn from_headers(header: impl Fn(&str) -> Option<String>, page_param: &str) -> Option<Self> {
+        header("X-Total-Count")
+            .and_then(|value| Self::from_total_count_header(&value))
+            .or_else(|| header("Link").and_then(|value| Self::from_link_header(&value, page_param)))
+    }
+
+    /// Parses the value of a header that contains the number of items like `X-Total-Count: 1234`.
+    pub fn from_total_count_header(value: &str) -> Option<Self> {
+        value.trim().parse().ok().map(PaginatedCount::Items)
+    }
+
+    /// Parses the value of a `Link` header (RFC 8288) and returns the number of pages from the
+    /// link with `rel="last"`. The page number is read from the query parameter `page_param` of
+    /// its URL. Pages are assumed to be counted from 1 like most APIs do.
+    ///
+    /// Returns `None` if there is no link to the last page, e.g. because the current page is
+    /// the last one.
+    pub fn from_link_header(value: &str, page_param: &str) -> Option<Self> {
+        value
+            .split(',')
+            .filter_map(|link| {
+                let (url, params) = link.trim().strip_prefix('<')?.split_once('>')?;
+
+                let is_last = params.split(';').any(|param| {
+                    param.trim().strip_prefix("rel=").is_some_and(|rel| {
+                        rel.trim_matches('"').split(' ').any(|rel| rel == "last")
+                    })
+                });
+
+                is_last.then_some(url)
+            })
+            .find_map(|url| {
+                let (_, query) = url.split_once('?')?;
+                let query = query.split('#').next().unwrap_or_default();
+
+                query
+                    .split('&')
+                    .filter_map(|pair| pair.split_once('='))
+                    .find(|(name, _)| *name == page_param)
+                    .and_then(|(_, page)| page.parse().ok())
+            })
+            .map(PaginatedCount::Pages)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_link_header() {
+        let link = r#"<https://api.github.com/repositories/1/issues?per_page=20&page=2>; rel="next", <https://api.github.com/repositories/1/issues?per_page=20&page=5>; rel="last""#;
+
+        assert_eq!(
+            PaginatedCount::from_link_header(link, "page"),
+            Some(PaginatedCount::Pages(5))
+        );
+        assert_eq!(PaginatedCount::from_link_header(link, "p"), None);
+
+        // On the last page there is no link to it
+        let link = r#"<https://api.example.com/items?page=4>; rel="prev", <https://api.example.com/items?page=1>; rel="first""#;
+        assert_eq!(PaginatedCount::from_link_header(link, "page"), None);
+    }
+
+    #[test]
+    fn test_from_headers() {
+        let header = |name: &str| match name {
+            "X-Total-Count" => Some(" 1234 ".to_string()),
+            "Link" => Some(r#"<https://api.example.com/items?page=34>; rel="last""#.to_string()),
+            _ => None,
+        };
+
+        assert_eq!(
+            PaginatedCount::from_headers(header, "page"),
+            Some(PaginatedCount::Items(1234))
+        );
+        assert_eq!(PaginatedCount::from_headers(|_| None, "page"), None);
+    }
+}
@@ -0,0 +1,212 @@
+use std::{fmt::Debug, marker::PhantomData};
+
+use serde::Deserialize;
+
+use crate::{CursorCache, CursorLoader, CursorPage};
+
+/// Adapter for GraphQL APIs that follow the [Relay connection spec](https://relay.dev/graphql/connections.htm).
+///
+/// You only have to provide a `fetch` function that runs your query with the `first` and `after`
+/// arguments and returns the [`Connection`]. The cursors are handled by [`CursorLoader`].
+///
+/// `PAGE_ITEM_COUNT` is passed as `first` to every query.
+///
+/// ## Example
+///
+/// ```
+/// # use leptos_windowing::{Connection, RelayArgs, RelayLoader};
+/// # use serde::Deserialize;
+/// #
+/// #[derive(Deserialize)]
+/// pub struct Repository {
+///     name: String,
+/// }
+///
+/// let loader = RelayLoader::<Repository, String, (), _, 50>::new(
+///     async |args: RelayArgs<'_>, owner: &String| -> Result<Connection<Repository>, ()> {
+///         // query($owner: String!, $first: Int!, $after: String) {
+///         //   repositoryOwner(login: $owner) {
+///         //     repositories(first: $first, after: $after) {
+///         //       edges { cursor node { name } }
+///         //       pageInfo { hasNextPage endCursor }
+///         //     }
+///         //   }
+///         // }
+///         # let _ = (args, owner);
+///         # Ok(Connection::default())
+///     },
+/// );
+/// ```
+pub struct RelayLoader<T, Q, E, F, const PAGE_ITEM_COUNT: usize> {
+    fetch: F,
+    cursors: CursorCache<String, Q>,
+    _marker: PhantomData<fn() -> (T, E)>,
+}
+
+impl<T, Q, E, F, const PAGE_ITEM_COUNT: usize> RelayLoader<T, Q, E, F, PAGE_ITEM_COUNT>
+where
+    Q: PartialEq + Clone,
+    E: Debug + 'static,
+    F: AsyncFn(RelayArgs<'_>, &Q) -> Result<Connection<T>, E>,
+{
+    /// Creates a new loader.
+    ///
+    /// `fetch` runs the GraphQL query with the given connection arguments.
+    pub fn new(fetch: F) -> Self {
+        Self {
+            fetch,
+            cursors: CursorCache::default(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T, Q, E, F, const PAGE_ITEM_COUNT: usize> CursorLoader
+    for RelayLoader<T, Q, E, F, PAGE_ITEM_COUNT>
+where
+    Q: PartialEq + Clone,
+    E: Debug + 'static,
+    F: AsyncFn(RelayArgs<'_>, &Q) -> Result<Connection<T>, E>,
+{
+    const PAGE_ITEM_COUNT: usize = PAGE_ITEM_COUNT;
+
+    type Item = T;
+    type Query = Q;
+    type Cursor = String;
+    type Error = E;
+
+    async fn load_after(
+        &self,
+        cursor: Option<&Self::Cursor>,
+        query: &Self::Query,
+    ) -> Result<CursorPage<Self::Item, Self::Cursor>, Self::Error> {
+        let args = RelayArgs {
+            first: PAGE_ITEM_COUNT,
+            after: cursor.map(String::as_str),
+        };
+
+        let Connection {
+            edges,
+            nodes,
+            page_info,
+        } = (self.fetch)(args, query).await?;
+
+        let last_edge_cursor = edges.last().map(|edge| edge.cursor.clone());
+
+        let items = if edges.is_empty() {
+            nodes
+        } else {
+            edges.into_iter().map(|edge| edge.node).collect()
+        };
+
+        let next_cursor = if page_info.has_next_page {
+            page_info.end_cursor.or(last_edge_cursor)
+        } else {
+            None
+        };
+
+        Ok(CursorPage { items, next_cursor })
+    }
+
+    fn cursors(&self) -> &CursorCache<Self::Cursor, Self::Query> {
+        &self.cursors
+    }
+}
+
+/// The connection arguments that are passed to the `fetch` function of a [`RelayLoader`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RelayArgs<'a> {
+    /// The number of items to load.
+    pub first: usize,
+
+    /// Load the items after this cursor. `None` means from the start.
+    pub after: Option<&'a str>,
+}
+
+/// A Relay connection as returned by a GraphQL query.
+///
+/// It can be deserialized directly from the JSON response. Either `edges` or `nodes` have to be queried.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Connection<T> {
+    /// The edges of the connection.
+    #[serde(default = "Vec::new")]
+    pub edges: Vec<Edge<T>>,
+
+    /// The nodes of the connection. Only used if `edges` is empty.
+    #[serde(default = "Vec::new")]
+    pub nodes: Vec<T>,
+
+    /// Information about the pagination.
+    pub page_info: PageInfo,
+}
+
+impl<T> Default for Connection<T> {
+    fn default() -> Self {
+        Self {
+            edges: Vec::new(),
+            nodes: Vec::new(),
+            page_info: PageInfo::default(),
+        }
+    }
+}
+
+/// An edge of a Relay [`Connection`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct Edge<T> {
+    /// The item.
+    pub node: T,
+
+    /// The cursor of this item.
+    pub cursor: String,
+}
+
+/// The `pageInfo` of a Relay [`Connection`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageInfo {
+    /// Whether there are more items after this page.
+    pub has_next_page: bool,
+
+    /// The cursor of the last item of this page.
+    #[serde(default)]
+    pub end_cursor: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::loaders::test_helpers::load;
+
+    #[test]
+    fn test_relay_loader() {
+        let loader = RelayLoader::<u32, (), (), _, 4>::new(
+            async |args: RelayArgs<'_>, _query: &()| -> Result<Connection<u32>, ()> {
+                let start = args
+                    .after
+                    .map_or(0, |cursor| cursor.parse::<u32>().unwrap() + 1);
+                let edges = (start..10)
+                    .take(args.first)
+                    .map(|node| Edge {
+                        node,
+                        cursor: node.to_string(),
+                    })
+                    .collect::<Vec<_>>();
+
+                Ok(Connection {
+                    page_info: PageInfo {
+                        has_next_page: edges.last().is_some_and(|edge| edge.node < 9),
+                        end_cursor: None,
+                    },
+                    edges,
+                    nodes: vec![],
+                })
+            },
+        );
+
+        let loaded = load(&loader, 4..12);
+
+        assert_eq!(loaded.range, 0..10);
+        assert_eq!(loaded.items, (0..10).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,145 @@
+use std::{
+    cell::{Ref, RefCell},
+    marker::PhantomData,
+    ops::Range,
+};
+
+use super::memory_loader::load_slice;
+use crate::{MemoryLoader, SortMode, Sortable, compare_sorted};
+
+/// In-memory loader that sorts its items by the sorting in the query.
+///
+/// The items are compared with their [`Sortable`](trait@Sortable) implementation. The query has
+/// to provide the sorted columns via `AsRef<[(usize, SortMode)]>` which is the case for
+/// `Vec<(usize, SortMode)>`. Implement `AsRef` for your own query type to combine the sorting
+/// with other parameters.
+///
+/// The sorted order is computed once per sorting and reused while only the range changes.
+///
+/// ```
+/// # use leptos_windowing::{MemoryLoader, SortMode, Sortable, SortableMemoryLoader};
+/// #
+/// #[derive(Sortable, Clone)]
+/// pub struct Book {
+///     #[sortable]
+///     title: String,
+///     #[sortable]
+///     year: u16,
+/// }
+///
+/// let loader = SortableMemoryLoader::new(vec![
+///     Book { title: "Emma".to_string(), year: 1815 },
+///     Book { title: "Dune".to_string(), year: 1965 },
+/// ]);
+///
+/// let books = loader.load_items(0..2, &vec![(0, SortMode::Ascending)]);
+///
+/// assert_eq!(books[0].title, "Dune");
+/// ```
+pub struct SortableMemoryLoader<T, Q = Vec<(usize, SortMode)>> {
+    items: Vec<T>,
+    order: RefCell<SortedOrder>,
+    _marker: PhantomData<fn(Q)>,
+}
+
+impl<T, Q> SortableMemoryLoader<T, Q>
+where
+    T: Sortable,
+{
+    /// Creates a loader for `items` in their original order.
+    pub fn new(items: Vec<T>) -> Self {
+        Self {
+            order: RefCell::new(SortedOrder {
+                sorting: Vec::new(),
+                indices: (0..items.len()).collect(),
+            }),
+            items,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The indices of the items sorted by `sorting`.
+    fn sorted_order(&self, sorting: &[(usize, SortMode)]) -> Ref<'_, Vec<usize>> {
+        if self.order.borrow().sorting != sorting {
+            let mut indices = (0..self.items.len()).collect::<Vec<_>>();
+            indices.sort_by(|a, b| compare_sorted(&self.items[*a], &self.items[*b], sorting));
+
+            *self.order.borrow_mut() = SortedOrder {
+                sorting: sorting.to_vec(),
+                indices,
+            };
+        }
+
+        Ref::map(self.order.borrow(), |order| &order.indices)
+    }
+}
+
+/// The indices of the items in sorted order together with the sorting they're sorted by.
+struct SortedOrder {
+    sorting: Vec<(usize, SortMode)>,
+    indices: Vec<usize>,
+}
+
+impl<T, Q> MemoryLoader for SortableMemoryLoader<T, Q>
+where
+    T: Sortable + Clone,
+    Q: AsRef<[(usize, SortMode)]>,
+{
+    type Item = T;
+    type Query = Q;
+
+    fn load_items(&self, range: Range<usize>, query: &Self::Query) -> Vec<Self::Item> {
+        load_slice(&self.sorted_order(query.as_ref()), range)
+            .into_iter()
+            .map(|index| self.items[index].clone())
+            .collect()
+    }
+
+    #[inline]
+    fn item_count(&self, _query: &Self::Query) -> usize {
+        self.items.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Row(u32, &'static str);
+
+    impl Sortable for Row {
+        fn compare(a: &Self, b: &Self, column: usize) -> Ordering {
+            match column {
+                0 => a.0.cmp(&b.0),
+                1 => a.1.cmp(b.1),
+                _ => Ordering::Equal,
+            }
+        }
+    }
+
+    #[test]
+    fn test_load_sorted() {
+        let loader = SortableMemoryLoader::<_, Vec<(usize, SortMode)>>::new(vec![
+            Row(2, "b"),
+            Row(1, "c"),
+            Row(2, "a"),
+        ]);
+
+        assert_eq!(loader.load_items(0..3, &vec![]), loader.items);
+
+        let sorting = vec![(0, SortMode::Descending), (1, SortMode::Ascending)];
+        assert_eq!(
+            loader.load_items(0..2, &sorting),
+            [Row(2, "a"), Row(2, "b")]
+        );
+        assert_eq!(loader.load_items(2..10, &sorting), [Row(1, "c")]);
+
+        assert_eq!(
+            loader.load_items(0..1, &vec![(1, SortMode::Descending)]),
+            [Row(1, "c")]
+        );
+    }
+}
@@ -0,0 +1,115 @@
+use std::{fmt::Debug, marker::PhantomData};
+
+use crate::{CursorCache, CursorLoader, CursorPage};
+
+/// Adapter for document databases that paginate with `startAfter(document)` cursors like Firestore.
+///
+/// The cursor of a page is the last document of the previous page, so you only have to provide a
+/// `fetch` function that runs the query starting after the given document with the given limit.
+/// Everything else is handled by [`CursorLoader`].
+///
+/// `PAGE_ITEM_COUNT` is the limit of every query.
+///
+/// This is only available with the feature `start-after`.
+///
+/// ## Example
+///
+/// ```
+/// # use leptos_windowing::StartAfterLoader;
+/// #
+/// #[derive(Clone)]
+/// pub struct Message {
+///     id: String,
+///     text: String,
+/// }
+///
+/// let loader = StartAfterLoader::<Message, String, (), _, 25>::new(
+///     async |start_after: Option<&Message>, limit: usize, channel: &String| -> Result<Vec<Message>, ()> {
+///         // collection("channels/{channel}/messages").orderBy("sentAt").startAfter(start_after).limit(limit)
+///         # let _ = (start_after, limit, channel);
+///         # Ok(vec![])
+///     },
+/// );
+/// ```
+pub struct StartAfterLoader<T, Q, E, F, const PAGE_ITEM_COUNT: usize> {
+    fetch: F,
+    cursors: CursorCache<T, Q>,
+    _marker: PhantomData<fn() -> E>,
+}
+
+impl<T, Q, E, F, const PAGE_ITEM_COUNT: usize> StartAfterLoader<T, Q, E, F, PAGE_ITEM_COUNT>
+where
+    T: Clone,
+    Q: PartialEq + Clone,
+    E: Debug + 'static,
+    F: AsyncFn(Option<&T>, usize, &Q) -> Result<Vec<T>, E>,
+{
+    /// Creates a new loader.
+    ///
+    /// `fetch` loads at most `limit` documents after the given document or from the start if it's `None`.
+    pub fn new(fetch: F) -> Self {
+        Self {
+            fetch,
+            cursors: CursorCache::default(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T, Q, E, F, const PAGE_ITEM_COUNT: usize> CursorLoader
+    for StartAfterLoader<T, Q, E, F, PAGE_ITEM_COUNT>
+where
+    T: Clone,
+    Q: PartialEq + Clone,
+    E: Debug + 'static,
+    F: AsyncFn(Option<&T>, usize, &Q) -> Result<Vec<T>, E>,
+{
+    const PAGE_ITEM_COUNT: usize = PAGE_ITEM_COUNT;
+
+    type Item = T;
+    type Query = Q;
+    type Cursor = T;
+    type Error = E;
+
+    async fn load_after(
+        &self,
+        cursor: Option<&Self::Cursor>,
+        query: &Self::Query,
+    ) -> Result<CursorPage<Self::Item, Self::Cursor>, Self::Error> {
+        let items = (self.fetch)(cursor, PAGE_ITEM_COUNT, query).await?;
+
+        // A short page is the last one
+        let next_cursor = if items.len() < PAGE_ITEM_COUNT {
+            None
+        } else {
+            items.last().cloned()
+        };
+
+        Ok(CursorPage { items, next_cursor })
+    }
+
+    fn cursors(&self) -> &CursorCache<Self::Cursor, Self::Query> {
+        &self.cursors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::loaders::test_helpers::load;
+
+    #[test]
+    fn test_start_after_loader() {
+        let loader = StartAfterLoader::<u32, (), (), _, 4>::new(
+            async |start_after: Option<&u32>, limit: usize, _query: &()| -> Result<Vec<u32>, ()> {
+                let start = start_after.map_or(0, |doc| doc + 1);
+                Ok((start..10).take(limit).collect())
+            },
+        );
+
+        let loaded = load(&loader, 4..12);
+
+        assert_eq!(loaded.range, 0..10);
+        assert_eq!(loaded.items, (0..10).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,148 @@
+use std::{fmt::Debug, ops::Range};
+
+use futures::Stream;
+
+/// Loader trait for data sources that deliver items one by one, like a streamed HTTP response
+/// (NDJSON, server-sent events) or a database cursor.
+///
+/// Items are written to the cache as soon as they arrive so long responses render progressively
+/// instead of all at once.
+///
+/// ## Example
+///
+/// ```
+/// # use std::ops::Range;
+/// # use futures::{Stream, stream};
+/// # use leptos_windowing::StreamLoader;
+/// #
+/// pub struct LogLoader;
+///
+/// impl StreamLoader for LogLoader {
+///     type Item = String;
+///     type Query = ();
+///     type Error = ();
+///
+///     fn load_items(
+///         &self,
+///         range: Range<usize>,
+///         _query: &Self::Query,
+///     ) -> impl Stream<Item = Result<Self::Item, Self::Error>> {
+///         // Parse the lines of a streamed response here.
+///         stream::iter(range.map(|i| Ok(format!("Line {i}"))))
+///     }
+/// }
+/// ```
+pub trait StreamLoader {
+    /// The type of items that will be loaded.
+    type Item;
+
+    /// The type of the query data that will be used to load items.
+    ///
+    /// Can be used to filter or sort the items for example.
+    type Query;
+
+    /// The type of errors that can occur during loading.
+    type Error: Debug + 'static;
+
+    /// Returns a stream of the items in `range` in order, starting at `range.start`.
+    ///
+    /// If the stream ends before `range.len()` items have been yielded, it is assumed that the end
+    /// of the data has been reached. After an error the rest of the range is marked as failed and
+    /// the stream isn't polled anymore. The items that arrived before the error are kept.
+    fn load_items(
+        &self,
+        range: Range<usize>,
+        query: &Self::Query,
+    ) -> impl Stream<Item = Result<Self::Item, Self::Error>>;
+
+    /// The total number of items of this data source with respect to the query.
+    ///
+    /// Returns `Ok(None)` if unknown (which is the default).
+    fn item_count(
+        &self,
+        _query: &Self::Query,
+    ) -> impl Future<Output = Result<Option<usize>, Self::Error>> {
+        async { Ok(None) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, ops::Range, task::Poll};
+
+    use futures::{Stream, StreamExt, stream};
+
+    use super::*;
+    use crate::{
+        InternalLoader,
+        loaders::test_helpers::{load, poll_ready},
+    };
+
+    /// Yields the indices, but every fourth item only after the stream has been pending once.
+    struct TestLoader;
+
+    impl StreamLoader for TestLoader {
+        type Item = usize;
+        type Query = ();
+        type Error = usize;
+
+        fn load_items(
+            &self,
+            range: Range<usize>,
+            _query: &(),
+        ) -> impl Stream<Item = Result<usize, usize>> {
+            stream::iter(range.filter(|i| *i < 10)).then(async |i| {
+                if i % 4 == 3 {
+                    yield_once().await;
+                }
+                if i == 8 { Err(i) } else { Ok(i) }
+            })
+        }
+    }
+
+    async fn yield_once() {
+        let mut yielded = false;
+        std::future::poll_fn(|_| {
+            if yielded {
+                Poll::Ready(())
+            } else {
+                yielded = true;
+                Poll::Pending
+            }
+        })
+        .await
+    }
+
+    #[test]
+    fn test_stream_loader_writes_batches() {
+        let batches = RefCell::new(vec![]);
+
+        let result = poll_ready(InternalLoader::load_items_streaming(
+            &TestLoader,
+            0..20,
+            &(),
+            |loaded| {
+                batches.borrow_mut().push((loaded.range, loaded.items));
+            },
+        ));
+
+        // Every pending item starts a new batch and the items before the error are kept
+        assert_eq!(result, Err(8));
+        assert_eq!(
+            *batches.borrow(),
+            [
+                (0..3, vec![0, 1, 2]),
+                (3..7, vec![3, 4, 5, 6]),
+                (7..8, vec![7]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_stream_loader_collects_items() {
+        let loaded = load(&TestLoader, 0..3);
+
+        assert_eq!(loaded.range, 0..3);
+        assert_eq!(loaded.items, [0, 1, 2]);
+    }
+}
@@ -0,0 +1,31 @@
+use std::{
+    ops::Range,
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+
+use crate::{InternalLoader, LoadedItems};
+
+/// Polls the given future with a noop waker until it's ready.
+///
+/// Only use this with futures that don't need to be woken up to make progress.
+pub(crate) fn poll_ready<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+
+    loop {
+        if let Poll::Ready(output) = future
+            .as_mut()
+            .poll(&mut Context::from_waker(Waker::noop()))
+        {
+            return output;
+        }
+    }
+}
+
+/// Loads the given `range` with `loader` and the unit query and unwraps the result.
+pub(crate) fn load<L, M>(loader: &L, range: Range<usize>) -> LoadedItems<L::Item>
+where
+    L: InternalLoader<M, Query = ()>,
+{
+    poll_ready(InternalLoader::load_items(loader, range, &())).unwrap()
+}
@@ -0,0 +1,318 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    marker::PhantomData,
+    ops::Range,
+    rc::Rc,
+};
+
+use futures::channel::oneshot;
+use leptos::wasm_bindgen::{JsCast, JsValue, closure::Closure};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use web_sys::{DedicatedWorkerGlobalScope, MessageEvent, Worker, js_sys::Uint8Array};
+
+use crate::{Json, LoadedItems, Loader, MemoryLoader, TransferError, TransferFormat};
+
+/// Loader that runs a [`MemoryLoader`] in a Web Worker so filtering and sorting large in-memory
+/// datasets doesn't block the UI.
+///
+/// The worker is a separate wasm entry point of your app that calls [`serve_memory_loader`] with
+/// the actual loader. Items and queries are sent to and from the worker serialized with the
+/// [`TransferFormat`] `F`. Use a binary format like `Postcard` for large chunks of items. The
+/// worker has to use the same format.
+///
+/// This is only available with the feature `worker`.
+///
+/// ## Example
+///
+/// ```no_run
+/// # use leptos_windowing::WorkerLoader;
+/// # use serde::{Deserialize, Serialize};
+/// #
+/// # #[derive(Serialize, Deserialize)]
+/// # pub struct Row;
+/// #
+/// // Main thread
+/// let loader = WorkerLoader::<Row, String>::from_url("./worker.js").unwrap();
+///
+/// // Worker entry point
+/// // serve_memory_loader::<_, Json>(RowLoader::new(rows));
+/// ```
+pub struct WorkerLoader<T, Q, F = Json> {
+    worker: Worker,
+    next_id: Cell<u64>,
+    pending: PendingResponses,
+    _on_message: Closure<dyn FnMut(MessageEvent)>,
+    _marker: PhantomData<fn(Q, F) -> T>,
+}
+
+/// The receivers of the responses to the requests by id.
+type PendingResponses = Rc<RefCell<HashMap<u64, oneshot::Sender<Vec<u8>>>>>;
+
+impl<T, Q, F> WorkerLoader<T, Q, F>
+where
+    T: DeserializeOwned,
+    Q: Serialize,
+    F: TransferFormat,
+{
+    /// Uses `worker` that has to call [`serve_memory_loader`] on startup.
+    pub fn new(worker: Worker) -> Self {
+        let pending = Rc::new(RefCell::new(HashMap::<u64, oneshot::Sender<Vec<u8>>>::new()));
+
+        let on_message = Closure::<dyn FnMut(MessageEvent)>::new({
+            let pending = Rc::clone(&pending);
+
+            move |event: MessageEvent| {
+                let message = Uint8Array::new(&event.data()).to_vec();
+                let Some((id, body)) = split_message(&message) else {
+                    return;
+                };
+
+                if let Some(sender) = pending.borrow_mut().remove(&id) {
+                    let _ = sender.send(body.to_vec());
+                }
+            }
+        });
+        worker.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+
+        Self {
+            worker,
+            next_id: Cell::new(0),
+            pending,
+            _on_message: on_message,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Starts a module worker from the script at `url`.
+    pub fn from_url(url: &str) -> Result<Self, JsValue> {
+        let options = web_sys::WorkerOptions::new();
+        options.set_type(web_sys::WorkerType::Module);
+
+        Ok(Self::new(Worker::new_with_options(url, &options)?))
+    }
+
+    async fn request<R>(&self, request: WorkerRequest<&Q>) -> Result<R, WorkerError>
+    where
+        R: DeserializeOwned,
+    {
+        let id = self.next_id.get();
+        self.next_id.set(id.wrapping_add(1));
+
+        let message = join_message(id, F::encode(&request).map_err(WorkerError::Serialize)?);
+
+        let (sender, receiver) = oneshot::channel();
+        self.pending.borrow_mut().insert(id, sender);
+
+        if let Err(error) = self.worker.post_message(&Uint8Array::from(&message[..])) {
+            self.pending.borrow_mut().remove(&id);
+            return Err(WorkerError::Post(format!("{error:?}")));
+        }
+
+        let response = receiver.await.map_err(|_| WorkerError::Disconnected)?;
+
+        F::decode::<Result<R, String>>(&response)
+            .map_err(WorkerError::Deserialize)?
+            .map_err(WorkerError::Worker)
+    }
+}
+
+impl<T, Q, F> Loader for WorkerLoader<T, Q, F>
+where
+    T: DeserializeOwned,
+    Q: Serialize,
+    F: TransferFormat,
+{
+    type Item = T;
+    type Query = Q;
+    type Error = WorkerError;
+
+    async fn load_items(
+        &self,
+        range: Range<usize>,
+        query: &Self::Query,
+    ) -> Result<LoadedItems<Self::Item>, Self::Error> {
+        let start = range.start;
+        let items: Vec<T> = self
+            .request(WorkerRequest::LoadItems { range, query })
+            .await?;
+
+        Ok(LoadedItems {
+            range: start..start + items.len(),
+            items,
+        })
+    }
+
+    async fn item_count(&self, query: &Self::Query) -> Result<Option<usize>, Self::Error> {
+        Ok(Some(
+            self.request(WorkerRequest::ItemCount { query }).await?,
+        ))
+    }
+}
+
+impl<T, Q, F> Drop for WorkerLoader<T, Q, F> {
+    fn drop(&mut self) {
+        self.worker.terminate();
+    }
+}
+
+/// Error type of [`WorkerLoader`].
+#[derive(Debug)]
+pub enum WorkerError {
+    /// The request couldn't be serialized.
+    Serialize(TransferError),
+
+    /// The message couldn't be posted to the worker.
+    Post(String),
+
+    /// The response of the worker couldn't be deserialized.
+    Deserialize(TransferError),
+
+    /// The worker couldn't answer the request, e.g. because it couldn't deserialize it.
+    Worker(String),
+
+    /// The loader was dropped before the worker responded.
+    Disconnected,
+}
+
+/// Answers the requests of a [`WorkerLoader`] with `loader`. Call this once in the entry point of
+/// the worker. The format `F` has to be the same as the one of the `WorkerLoader`.
+///
+/// This is only available with the feature `worker`.
+pub fn serve_memory_loader<L, F>(loader: L)
+where
+    L: MemoryLoader + 'static,
+    L::Item: Serialize,
+    L::Query: DeserializeOwned,
+    F: TransferFormat + 'static,
+{
+    let scope = web_sys::js_sys::global().unchecked_into::<DedicatedWorkerGlobalScope>();
+
+    let on_message = Closure::<dyn FnMut(MessageEvent)>::new({
+        let scope = scope.clone();
+
+        move |event: MessageEvent| {
+            let request = Uint8Array::new(&event.data()).to_vec();
+
+            if let Some(response) = handle_request::<L, F>(&loader, &request) {
+                let _ = scope.post_message(&Uint8Array::from(&response[..]));
+            }
+        }
+    });
+    scope.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+
+    // The worker answers requests for as long as it lives.
+    on_message.forget();
+}
+
+/// Runs the serialized request with `loader` and returns the serialized response.
+///
+/// Every request with an id is answered, with an error if it couldn't be handled, so the loader
+/// doesn't wait forever. Only messages that are too short to contain an id are ignored.
+fn handle_request<L, F>(loader: &L, message: &[u8]) -> Option<Vec<u8>>
+where
+    L: MemoryLoader,
+    L::Item: Serialize,
+    L::Query: DeserializeOwned,
+    F: TransferFormat,
+{
+    let (id, body) = split_message(message)?;
+
+    let response = match F::decode::<WorkerRequest<L::Query>>(body) {
+        Ok(WorkerRequest::LoadItems { range, query }) => {
+            encode_response::<F, _>(Ok(loader.load_items(range, &query)))
+        }
+        Ok(WorkerRequest::ItemCount { query }) => {
+            encode_response::<F, _>(Ok(loader.item_count(&query)))
+        }
+        Err(error) => encode_response::<F, ()>(Err(format!("Invalid request: {error}"))),
+    };
+
+    Some(join_message(id, response))
+}
+
+/// Serializes the response to a request. If that fails an error is sent instead.
+fn encode_response<F, R>(response: Result<R, String>) -> Vec<u8>
+where
+    F: TransferFormat,
+    R: Serialize,
+{
+    F::encode(&response).unwrap_or_else(|error| {
+        F::encode(&Err::<(), _>(format!("Invalid response: {error}"))).unwrap_or_default()
+    })
+}
+
+/// Messages between a [`WorkerLoader`] and its worker start with the id of the request so
+/// responses can be matched independently of the format.
+fn join_message(id: u64, body: Vec<u8>) -> Vec<u8> {
+    let mut message = id.to_le_bytes().to_vec();
+    message.extend(body);
+    message
+}
+
+fn split_message(message: &[u8]) -> Option<(u64, &[u8])> {
+    let (id, body) = message.split_first_chunk::<8>()?;
+    Some((u64::from_le_bytes(*id), body))
+}
+
+#[derive(Serialize, Deserialize)]
+enum WorkerRequest<Q> {
+    LoadItems { range: Range<usize>, query: Q },
+    ItemCount { query: Q },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct NumberLoader;
+
+    impl MemoryLoader for NumberLoader {
+        type Item = usize;
+        type Query = usize;
+
+        fn load_items(&self, range: Range<usize>, query: &Self::Query) -> Vec<Self::Item> {
+            range.map(|i| i * query).collect()
+        }
+
+        fn item_count(&self, _query: &Self::Query) -> usize {
+            10
+        }
+    }
+
+    #[test]
+    fn test_handle_request() {
+        let request = |body: WorkerRequest<&usize>| join_message(7, Json::encode(&body).unwrap());
+        let handle = |message: &[u8]| handle_request::<_, Json>(&NumberLoader, message);
+
+        let response = handle(&request(WorkerRequest::LoadItems {
+            range: 1..4,
+            query: &2,
+        }))
+        .unwrap();
+        let (id, body) = split_message(&response).unwrap();
+        assert_eq!(id, 7);
+        assert_eq!(
+            Json::decode::<Result<Vec<usize>, String>>(body).unwrap(),
+            Ok(vec![2, 4, 6])
+        );
+
+        let response = handle(&request(WorkerRequest::ItemCount { query: &2 })).unwrap();
+        let (id, body) = split_message(&response).unwrap();
+        assert_eq!(id, 7);
+        assert_eq!(Json::decode::<Result<usize, String>>(body).unwrap(), Ok(10));
+
+        // Invalid requests are answered with an error so the loader doesn't wait forever
+        let response = handle(&join_message(8, b"not a request".to_vec())).unwrap();
+        let (id, body) = split_message(&response).unwrap();
+        assert_eq!(id, 8);
+        assert!(
+            Json::decode::<Result<usize, String>>(body)
+                .unwrap()
+                .is_err()
+        );
+
+        // Without an id there is nobody to answer
+        assert_eq!(handle(b"short"), None);
+    }
+}
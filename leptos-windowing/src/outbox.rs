@@ -0,0 +1,227 @@
+use std::{collections::VecDeque, sync::Arc};
+
+use default_struct_builder::DefaultBuilder;
+use leptos::prelude::*;
+
+use crate::{
+    LiveUpdate, MutableLoader, Spawner,
+    cache::Cache,
+    spawner::{resolve_spawner, spawn_with},
+};
+
+/// Applies changes to the cache right away and writes them to the data source with `loader`.
+///
+/// While the browser is offline the changes are queued in an outbox. When the connection returns,
+/// they're replayed in order. If a change is rejected while online, `on_conflict` of the options
+/// decides what happens with it. See [`ConflictResolution`].
+///
+/// The outbox is kept in memory so changes that weren't sent are lost when the page is closed.
+///
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos_windowing::{LiveUpdate, MutableLoader, UseMutationOutboxOptions, WindowLink, use_mutation_outbox};
+/// #
+/// # #[derive(Clone)]
+/// # pub struct Book { title: String }
+/// pub struct BookLoader;
+///
+/// impl MutableLoader for BookLoader {
+///     type Item = Book;
+///     type Error = String;
+///
+///     async fn apply(&self, change: &LiveUpdate<Book>) -> Result<(), String> {
+///         // Send the change to your API
+/// #       Ok(())
+///     }
+/// }
+///
+/// # #[component]
+/// # pub fn App() -> impl IntoView {
+/// let link = WindowLink::<Book>::new();
+/// let outbox = use_mutation_outbox(link.cache(), BookLoader, UseMutationOutboxOptions::default());
+///
+/// // Pass `link` to the list and change the items through the outbox
+/// outbox.update_item(0, Book { title: "The Hobbit".to_string() });
+///
+/// view! { <p>{move || outbox.pending_count().get()} " changes not saved yet"</p> }
+/// # }
+/// ```
+pub fn use_mutation_outbox<T, L>(
+    cache: Cache<T>,
+    loader: L,
+    options: UseMutationOutboxOptions<T>,
+) -> MutationOutbox<T>
+where
+    T: Clone + Send + Sync + 'static,
+    L: MutableLoader<Item = T> + 'static,
+{
+    let UseMutationOutboxOptions {
+        on_conflict,
+        spawner,
+    } = options;
+
+    let spawner = resolve_spawner(spawner);
+
+    let loader = StoredValue::new_local(loader);
+    let queue = RwSignal::new(VecDeque::<LiveUpdate<T>>::new());
+    let is_replaying = StoredValue::new(false);
+
+    // Sends the queued changes one after the other until the queue is empty or the browser is offline.
+    let replay = Callback::new(move |_| {
+        if is_replaying.get_value() || !is_online() {
+            return;
+        }
+        is_replaying.set_value(true);
+
+        spawn_with(spawner, async move {
+            while let Some(Some(change)) = queue.try_with_untracked(|queue| queue.front().cloned())
+            {
+                let result = loader.read_value().apply(&change).await;
+
+                match result {
+                    Ok(()) => {}
+                    // Keep the change until the connection returns
+                    Err(_) if !is_online() => break,
+                    Err(error) => match on_conflict.run((change, format!("{error:?}"))) {
+                        ConflictResolution::Retry => break,
+                        ConflictResolution::Discard => {}
+                        ConflictResolution::DiscardAndReload => cache.reload(),
+                    },
+                }
+
+                queue.try_update(|queue| queue.pop_front());
+            }
+
+            is_replaying.try_set_value(false);
+        });
+    });
+
+    // There are no online events outside of the browser, e.g. in tests with `test-utils`
+    #[cfg(target_arch = "wasm32")]
+    Effect::new(move || {
+        let handle = window_event_listener(leptos::ev::online, move |_| replay.run(()));
+        on_cleanup(move || handle.remove());
+    });
+
+    MutationOutbox {
+        cache,
+        queue,
+        replay,
+    }
+}
+
+/// Options for [`use_mutation_outbox`].
+#[derive(DefaultBuilder)]
+pub struct UseMutationOutboxOptions<T>
+where
+    T: Send + Sync + 'static,
+{
+    /// Called with the change and the formatted error when the data source rejects a change
+    /// while the browser is online. See [`ConflictResolution`].
+    /// Defaults to [`ConflictResolution::DiscardAndReload`] so the data source wins.
+    on_conflict: Callback<(LiveUpdate<T>, String), ConflictResolution>,
+
+    /// Runs the replays. See [`Spawner`].
+    /// Defaults to `None` which uses the spawner installed with
+    /// [`provide_spawner`](crate::provide_spawner) or Leptos' `spawn_local`.
+    #[builder(keep_type)]
+    spawner: Option<Arc<dyn Spawner>>,
+}
+
+impl<T> Default for UseMutationOutboxOptions<T>
+where
+    T: Send + Sync + 'static,
+{
+    fn default() -> Self {
+        Self {
+            on_conflict: Callback::new(|_| ConflictResolution::DiscardAndReload),
+            spawner: None,
+        }
+    }
+}
+
+/// What to do with a change that has been rejected by the data source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictResolution {
+    /// Keep the change and the ones after it in the outbox and try again when the browser comes
+    /// online again or the next change is made.
+    Retry,
+    /// Drop the change but keep it in the cache.
+    Discard,
+    /// Drop the change and reload the cache so it shows the data source's version again.
+    DiscardAndReload,
+}
+
+/// Return type of [`use_mutation_outbox`].
+pub struct MutationOutbox<T>
+where
+    T: Send + Sync + 'static,
+{
+    cache: Cache<T>,
+    queue: RwSignal<VecDeque<LiveUpdate<T>>>,
+    replay: Callback<()>,
+}
+
+impl<T> Clone for MutationOutbox<T>
+where
+    T: Send + Sync + 'static,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for MutationOutbox<T> where T: Send + Sync + 'static {}
+
+impl<T> MutationOutbox<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    /// Updates the item at `index` in the cache and in the data source.
+    pub fn update_item(&self, index: usize, item: T) {
+        self.push(LiveUpdate::Update { index, item });
+    }
+
+    /// Inserts an item at `index` into the cache and into the data source.
+    pub fn insert_item(&self, index: usize, item: T) {
+        self.push(LiveUpdate::Insert { index, item });
+    }
+
+    /// Removes the item at `index` from the cache and from the data source.
+    pub fn remove_item(&self, index: usize) {
+        self.push(LiveUpdate::Delete { index });
+    }
+
+    /// Applies `change` to the cache right away and sends it to the data source when possible.
+    pub fn push(&self, change: LiveUpdate<T>) {
+        self.cache.apply_live_update(change.clone());
+        self.queue.update(|queue| queue.push_back(change));
+        self.replay.run(());
+    }
+
+    /// The number of changes that haven't been applied to the data source yet.
+    pub fn pending_count(&self) -> Signal<usize> {
+        let queue = self.queue;
+        Signal::derive(move || queue.read().len())
+    }
+
+    /// Sends the queued changes again, e.g. after they have been rejected with
+    /// [`ConflictResolution::Retry`].
+    pub fn replay(&self) {
+        self.replay.run(());
+    }
+}
+
+/// Whether the browser is online. Outside of a browser window, e.g. in a worker, on the server or
+/// in tests, the changes are always sent right away.
+fn is_online() -> bool {
+    #[cfg(target_arch = "wasm32")]
+    {
+        web_sys::window().is_none_or(|window| window.navigator().on_line())
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        true
+    }
+}
@@ -0,0 +1,238 @@
+use std::{
+    hash::{Hash, Hasher},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use crate::{LoadedItems, cache::Cache, item_state::ItemState};
+
+/// The web storage that a cache is persisted to. See [`use_persisted_cache`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PersistStorage {
+    /// `window.sessionStorage`: Kept while the tab is open.
+    #[default]
+    Session,
+    /// `window.localStorage`: Kept across browser sessions.
+    Local,
+}
+
+/// Persists the loaded items and the item count of `cache` to web storage so that navigating
+/// back to a list restores it instantly without loading it again.
+///
+/// The snapshot is stored under a key made of `name` and a hash of the query. The hash is the same
+/// in every build so the snapshots survive deployments. Only the snapshot for the query at the time
+/// of calling this is restored. Changes are written at most once per animation frame. Requires the
+/// feature `persist`.
+///
+/// Call this before creating the windows that use the cache. It is restored after hydration so
+/// the server rendered HTML still matches.
+///
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos_windowing::{PersistStorage, WindowLink, use_persisted_cache};
+/// # use serde::{Deserialize, Serialize};
+/// #
+/// # #[derive(Serialize, Deserialize)]
+/// # pub struct Book;
+/// #
+/// # #[component]
+/// # pub fn App() -> impl IntoView {
+/// let query = RwSignal::new("tolkien".to_string());
+/// let link = WindowLink::<Book>::new();
+///
+/// use_persisted_cache(link.cache(), "books", query, PersistStorage::Session);
+///
+/// // Pass `link` to the windows
+/// # }
+/// ```
+pub fn use_persisted_cache<T>(
+    cache: Cache<T>,
+    name: &str,
+    query: impl WithUntracked<Value: Hash> + Clone + Send + Sync + 'static,
+    storage: PersistStorage,
+) where
+    T: Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+    let name = name.to_string();
+
+    let key = move || {
+        let mut hasher = StableHasher::default();
+        query.with_untracked(|query| query.hash(&mut hasher));
+
+        format!("leptos-windowing:{name}:{:x}", hasher.finish())
+    };
+
+    // Effects only run in the browser and after hydration.
+    Effect::new(move || {
+        let Some(web_storage) = open_storage(storage) else {
+            return;
+        };
+
+        if let Some(snapshot) = web_storage
+            .get_item(&key())
+            .ok()
+            .flatten()
+            .and_then(|json| serde_json::from_str::<CacheSnapshot<T>>(&json).ok())
+        {
+            for (start, items) in snapshot.pages {
+                let range = start..start + items.len();
+                cache.write_loaded(
+                    Ok(LoadedItems {
+                        items,
+                        range: range.clone(),
+                    }),
+                    range,
+                );
+            }
+            // A count that has been loaded in the meantime is more recent
+            if cache.item_count().get_untracked().is_none() {
+                cache.set_item_count(snapshot.item_count);
+            }
+        }
+
+        // Loading a page emits many events. Only the state after the last one is stored.
+        let is_save_scheduled = Arc::new(AtomicBool::new(false));
+        let key = key.clone();
+
+        cache.on_change(move |_| {
+            if is_save_scheduled.swap(true, Ordering::Relaxed) {
+                return;
+            }
+
+            let is_save_scheduled = Arc::clone(&is_save_scheduled);
+            let key = key.clone();
+
+            request_animation_frame(move || {
+                is_save_scheduled.store(false, Ordering::Relaxed);
+                save_snapshot(cache, &key(), storage);
+            });
+        });
+    });
+}
+
+/// Writes the loaded items and the item count of `cache` to `storage` under `key`.
+fn save_snapshot<T>(cache: Cache<T>, key: &str, storage: PersistStorage)
+where
+    T: Serialize + Send + Sync + 'static,
+{
+    // The cache is gone if the list has been unmounted in the meantime.
+    let Some(items) = cache.items().try_read_untracked() else {
+        return;
+    };
+
+    let snapshot = CacheSnapshot {
+        item_count: cache.item_count().get_untracked(),
+        pages: loaded_pages(&items),
+    };
+
+    if let (Some(web_storage), Ok(json)) = (open_storage(storage), serde_json::to_string(&snapshot))
+    {
+        // Exceeding the quota only means that the list isn't restored.
+        let _ = web_storage.set_item(key, &json);
+    }
+}
+
+/// The 64 bit FNV-1a hash. Unlike `DefaultHasher` it's the same in every build so the keys of
+/// the stored snapshots stay valid across deployments.
+struct StableHasher(u64);
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for StableHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+fn open_storage(storage: PersistStorage) -> Option<web_sys::Storage> {
+    let window = web_sys::window()?;
+
+    match storage {
+        PersistStorage::Session => window.session_storage(),
+        PersistStorage::Local => window.local_storage(),
+    }
+    .ok()
+    .flatten()
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheSnapshot<I> {
+    item_count: Option<usize>,
+    /// Runs of loaded items with the index of their first item.
+    pages: Vec<(usize, Vec<I>)>,
+}
+
+/// Collects the runs of loaded items.
+fn loaded_pages<T>(items: &[ItemState<T>]) -> Vec<(usize, Vec<&T>)>
+where
+    T: Send + Sync + 'static,
+{
+    let mut pages = Vec::<(usize, Vec<&T>)>::new();
+
+    for (index, row) in items.iter().enumerate() {
+        let ItemState::Loaded(item) = row else {
+            continue;
+        };
+
+        match pages.last_mut() {
+            Some((start, page)) if *start + page.len() == index => page.push(item.as_ref()),
+            _ => pages.push((index, vec![item.as_ref()])),
+        }
+    }
+
+    pages
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+
+    #[test]
+    fn test_loaded_pages() {
+        let items = [
+            ItemState::Loaded(Arc::new(0)),
+            ItemState::Loaded(Arc::new(1)),
+            ItemState::Loading,
+            ItemState::Loaded(Arc::new(3)),
+            ItemState::Error("error".into()),
+        ];
+
+        let json = serde_json::to_string(&CacheSnapshot {
+            item_count: Some(5),
+            pages: loaded_pages(&items),
+        })
+        .unwrap();
+
+        assert_eq!(json, r#"{"item_count":5,"pages":[[0,[0,1]],[3,[3]]]}"#);
+
+        let snapshot = serde_json::from_str::<CacheSnapshot<i32>>(&json).unwrap();
+        assert_eq!(snapshot.pages, [(0, vec![0, 1]), (3, vec![3])]);
+    }
+
+    #[test]
+    fn test_stable_hasher() {
+        let mut hasher = StableHasher::default();
+        "tolkien".hash(&mut hasher);
+
+        // Must never change or the stored snapshots are lost
+        assert_eq!(hasher.finish(), 0x3b00_d9ab_b785_4d74);
+    }
+}
@@ -0,0 +1,169 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{SortMode, rest_sort_param, strict::misuse};
+
+/// A generic query of a list consisting of a search term, filters and sorting.
+///
+/// Use this as the `Query` of your loader instead of defining a nearly identical struct for every list.
+/// Anything specific to your app can go into `extra`.
+///
+/// It can be serialized so it can be passed to server functions directly. For REST APIs use
+/// [`ListQuery::query_pairs`] and for OData convert it into an `ODataQuery` with `TryFrom`.
+///
+/// ```
+/// # use leptos_windowing::{ListQuery, SortMode};
+/// #
+/// let query = ListQuery::new()
+///     .search("tolkien")
+///     .filter("language", "en")
+///     .sort("year", SortMode::Descending);
+///
+/// assert_eq!(
+///     query.query_pairs(),
+///     [
+///         ("search".to_string(), "tolkien".to_string()),
+///         ("language".to_string(), "en".to_string()),
+///         ("sort".to_string(), "year:desc".to_string()),
+///     ]
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ListQuery<X = ()> {
+    /// The search term. `None` or an empty string means no search.
+    pub search: Option<String>,
+
+    /// The filters by field name. Each one means "field equals value".
+    pub filters: BTreeMap<String, String>,
+
+    /// The columns to sort by in order of priority.
+    pub sorting: Vec<(String, SortMode)>,
+
+    /// App specific query data.
+    pub extra: X,
+}
+
+impl ListQuery {
+    /// Creates an empty query.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<X> ListQuery<X> {
+    /// Sets the search term.
+    pub fn search(mut self, search: impl Into<String>) -> Self {
+        self.search = Some(search.into());
+        self
+    }
+
+    /// Adds a filter that only keeps the items whose `field` equals `value`.
+    pub fn filter(mut self, field: impl Into<String>, value: impl Into<String>) -> Self {
+        self.filters.insert(field.into(), value.into());
+        self
+    }
+
+    /// Adds a column to sort by. It has a lower priority than the columns added before.
+    pub fn sort(mut self, column: impl Into<String>, mode: SortMode) -> Self {
+        self.sorting.push((column.into(), mode));
+        self
+    }
+
+    /// Sets the app specific query data.
+    pub fn extra<Y>(self, extra: Y) -> ListQuery<Y> {
+        ListQuery {
+            search: self.search,
+            filters: self.filters,
+            sorting: self.sorting,
+            extra,
+        }
+    }
+
+    /// The search term if it's not empty.
+    pub fn search_term(&self) -> Option<&str> {
+        self.search.as_deref().filter(|search| !search.is_empty())
+    }
+
+    /// The query parameters for a REST API. Encode them with your HTTP client.
+    ///
+    /// The search term is put into `search`, every filter into a parameter with the field name
+    /// and the sorting into `sort` like `"name:asc,city:desc"` (see [`rest_sort_param`]).
+    ///
+    /// Filters on fields called `search` or `sort` would clash with these parameters, so they are
+    /// left out and reported as a misuse.
+    pub fn query_pairs(&self) -> Vec<(String, String)> {
+        let mut pairs = Vec::with_capacity(self.filters.len() + 2);
+
+        if let Some(search) = self.search_term() {
+            pairs.push(("search".to_string(), search.to_string()));
+        }
+
+        pairs.extend(
+            self.filters
+                .iter()
+                .filter(|(field, _)| {
+                    let reserved = matches!(field.as_str(), "search" | "sort");
+                    if reserved {
+                        misuse!(
+                            "The filter on `{field}` clashes with the parameter of the same name in the query pairs and is left out."
+                        );
+                    }
+                    !reserved
+                })
+                .map(|(field, value)| (field.clone(), value.clone())),
+        );
+
+        if let Some(sort) = rest_sort_param(&self.sorting, |column| Some(column.as_str())) {
+            pairs.push(("sort".to_string(), sort));
+        }
+
+        pairs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_pairs() {
+        assert!(ListQuery::new().search("").query_pairs().is_empty());
+
+        let query = ListQuery::new()
+            .filter("city", "Berlin")
+            .filter("country", "DE")
+            .sort("name", SortMode::Ascending)
+            .sort("age", SortMode::None)
+            .extra(42);
+
+        assert_eq!(query.extra, 42);
+        assert_eq!(
+            query.query_pairs(),
+            [
+                ("city".to_string(), "Berlin".to_string()),
+                ("country".to_string(), "DE".to_string()),
+                ("sort".to_string(), "name:asc".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(all(feature = "strict", debug_assertions), should_panic)]
+    fn test_query_pairs_leave_out_clashing_filters() {
+        let query = ListQuery::new()
+            .search("tolkien")
+            .filter("search", "martin")
+            .filter("sort", "name:desc")
+            .sort("name", SortMode::Ascending);
+
+        assert_eq!(
+            query.query_pairs(),
+            [
+                ("search".to_string(), "tolkien".to_string()),
+                ("sort".to_string(), "name:asc".to_string()),
+            ]
+        );
+    }
+}
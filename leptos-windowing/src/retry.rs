@@ -0,0 +1,127 @@
+use std::time::Duration;
+
+/// How failed loads are retried. See [`UseLoadOnDemandOptions`](crate::hook::UseLoadOnDemandOptions).
+///
+/// The delay before the `n`-th retry is `initial_delay * multiplier^(n - 1)` capped at `max_delay`.
+/// With `jitter` each delay is randomly shortened by up to this fraction so that many clients that
+/// failed at the same time don't retry at the same time.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use leptos_windowing::RetryPolicy;
+/// #
+/// let retry = RetryPolicy::default()
+///     .max_attempts(5)
+///     .initial_delay(Duration::from_millis(200));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// How many times a load is tried in total. `1` means that failed loads aren't retried.
+    pub max_attempts: u32,
+
+    /// The delay before the first retry.
+    pub initial_delay: Duration,
+
+    /// Every retry waits this much longer than the previous one.
+    pub multiplier: f64,
+
+    /// The upper limit of the delay between retries.
+    pub max_delay: Duration,
+
+    /// The fraction between 0 and 1 by which every delay is randomly shortened.
+    pub jitter: f64,
+}
+
+impl RetryPolicy {
+    /// Failed loads aren't retried. This is the default of the hooks.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Sets `max_attempts`.
+    pub fn max_attempts(self, max_attempts: u32) -> Self {
+        Self {
+            max_attempts,
+            ..self
+        }
+    }
+
+    /// Sets `initial_delay`.
+    pub fn initial_delay(self, initial_delay: Duration) -> Self {
+        Self {
+            initial_delay,
+            ..self
+        }
+    }
+
+    /// Sets `multiplier`.
+    pub fn multiplier(self, multiplier: f64) -> Self {
+        Self { multiplier, ..self }
+    }
+
+    /// Sets `max_delay`.
+    pub fn max_delay(self, max_delay: Duration) -> Self {
+        Self { max_delay, ..self }
+    }
+
+    /// Sets `jitter`.
+    pub fn jitter(self, jitter: f64) -> Self {
+        Self { jitter, ..self }
+    }
+
+    /// Whether another attempt should be made after `attempt` attempts have failed.
+    #[inline]
+    pub fn should_retry(&self, attempt: u32) -> bool {
+        attempt < self.max_attempts
+    }
+
+    /// The delay before the next attempt after `attempt` attempts have failed.
+    ///
+    /// `random` is a random number between 0 and 1 that is used for the jitter.
+    pub fn delay(&self, attempt: u32, random: f64) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let delay = (self.initial_delay.as_secs_f64() * self.multiplier.powi(exponent))
+            .min(self.max_delay.as_secs_f64());
+        let jitter = self.jitter.clamp(0.0, 1.0) * random.clamp(0.0, 1.0);
+
+        Duration::from_secs_f64((delay * (1.0 - jitter)).max(0.0))
+    }
+}
+
+/// Three attempts in total with a delay of 500ms that doubles up to 10s and 20% jitter.
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_delay: Duration::from_millis(500),
+            multiplier: 2.0,
+            max_delay: Duration::from_secs(10),
+            jitter: 0.2,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delay() {
+        let retry = RetryPolicy::default().max_delay(Duration::from_secs(3));
+
+        assert!(retry.should_retry(2));
+        assert!(!retry.should_retry(3));
+        assert!(!RetryPolicy::none().should_retry(1));
+
+        assert_eq!(retry.delay(1, 0.0), Duration::from_millis(500));
+        assert_eq!(retry.delay(2, 0.0), Duration::from_secs(1));
+        assert_eq!(retry.delay(3, 0.0), Duration::from_secs(2));
+        assert_eq!(retry.delay(4, 0.0), Duration::from_secs(3));
+
+        assert_eq!(retry.delay(1, 1.0), Duration::from_millis(400));
+        assert_eq!(retry.delay(1, 0.5), Duration::from_millis(450));
+    }
+}
@@ -0,0 +1,153 @@
+use std::{fmt::Debug, ops::Range};
+
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use crate::{LoadedItems, cache::Cache};
+
+/// Loads the items of `range` during server side rendering and writes them into `cache` so the
+/// first page is part of the server rendered HTML instead of a loading skeleton.
+///
+/// The loaders run in `spawn_local` which doesn't happen on the server. This instead uses a
+/// [`Resource`] with `fetch`, usually a server function, that returns the items of the range
+/// and the total item count. The result is serialized into the HTML and written into the cache
+/// again during hydration without calling `fetch` a second time.
+///
+/// Pass the cache to the list, e.g. with a [`WindowLink`](crate::WindowLink), and only render it
+/// after [`ServerPrefill::ready`] inside of a `<Suspense/>`. Windows whose display range is
+/// completely in the cache display it right away. Everything else is loaded by the loader
+/// as usual. If `fetch` fails, nothing is prefilled.
+///
+/// The query is only read once. Later changes are handled by the loader.
+///
+/// ```
+/// # use std::ops::Range;
+/// # use leptos::prelude::*;
+/// # use leptos_windowing::{WindowLink, use_server_prefill};
+/// #
+/// // Usually a server function
+/// async fn first_books(query: String, range: Range<usize>) -> Result<(Vec<String>, Option<usize>), ()> {
+///     // ...
+/// #   Ok((vec![], Some(0)))
+/// }
+///
+/// # #[component]
+/// # pub fn App() -> impl IntoView {
+/// let query = RwSignal::new("tolkien".to_string());
+/// let link = WindowLink::<String>::new();
+///
+/// let prefill = use_server_prefill(link.cache(), query, 0..20, first_books);
+///
+/// view! {
+///     <Suspense>
+///         {move || Suspend::new(async move {
+///             prefill.ready().await;
+///
+///             // Render the list with `link` and `query` here
+///         })}
+///     </Suspense>
+/// }
+/// # }
+/// ```
+pub fn use_server_prefill<T, Q, E, F, Fut>(
+    cache: Cache<T>,
+    query: impl Into<Signal<Q>>,
+    range: Range<usize>,
+    fetch: F,
+) -> ServerPrefill<T>
+where
+    T: Clone + Serialize + DeserializeOwned + Send + Sync + 'static,
+    Q: Clone + Send + Sync + 'static,
+    E: Debug,
+    F: Fn(Q, Range<usize>) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<(Vec<T>, Option<usize>), E>> + Send + 'static,
+{
+    let query = query.into();
+
+    let resource = Resource::new(
+        || (),
+        move |_| {
+            let start = range.start;
+            let items = fetch(query.get_untracked(), range.clone());
+
+            async move {
+                match items.await {
+                    Ok((items, item_count)) => Some(PrefilledItems {
+                        start,
+                        items,
+                        item_count,
+                    }),
+                    Err(error) => {
+                        leptos::logging::warn!(
+                            "[leptos-windowing] Failed to prefill the cache on the server: {error:?}"
+                        );
+                        None
+                    }
+                }
+            }
+        },
+    );
+
+    ServerPrefill { resource, cache }
+}
+
+/// Return type of [`use_server_prefill`].
+pub struct ServerPrefill<T>
+where
+    T: Send + Sync + 'static,
+{
+    resource: Resource<Option<PrefilledItems<T>>>,
+    cache: Cache<T>,
+}
+
+impl<T> Clone for ServerPrefill<T>
+where
+    T: Send + Sync + 'static,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ServerPrefill<T> where T: Send + Sync + 'static {}
+
+impl<T> ServerPrefill<T>
+where
+    T: Clone + Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+    /// Waits for the items and writes them into the cache.
+    ///
+    /// Items that are already in the cache, e.g. because the list has been rendered before, are
+    /// left alone.
+    pub async fn ready(self) {
+        let Some(prefilled) = self.resource.await else {
+            return;
+        };
+
+        let range = prefilled.start..prefilled.start + prefilled.items.len();
+
+        untrack(|| {
+            if self.cache.item_count().get().is_none() {
+                self.cache.item_count().set(prefilled.item_count);
+            }
+
+            if self.cache.loaded_fraction(range.clone()) == 0.0 {
+                self.cache.write_loaded(
+                    Ok(LoadedItems {
+                        items: prefilled.items,
+                        range: range.clone(),
+                    }),
+                    range,
+                );
+            }
+        });
+    }
+}
+
+/// The items that are sent from the server to the client.
+#[derive(Clone, Serialize, Deserialize)]
+struct PrefilledItems<T> {
+    start: usize,
+    items: Vec<T>,
+    item_count: Option<usize>,
+}
@@ -0,0 +1,307 @@
+use std::{cmp::Ordering, fmt::Write};
+
+/// Derives [`Sortable`](trait@Sortable) by comparing the fields marked with `#[sortable]`.
+///
+/// The columns are numbered in the order of the marked fields starting from 0. A field can be given an
+/// explicit column with `#[sortable(column = 3)]`. Fields are compared with `PartialOrd`.
+pub use leptos_windowing_macro::Sortable;
+
+/// The sort direction of a column.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Default, serde::Serialize, serde::Deserialize,
+)]
+pub enum SortMode {
+    Ascending,
+    Descending,
+    /// Not sorted. Columns with this mode are skipped by the encoders below.
+    #[default]
+    None,
+}
+
+impl SortMode {
+    /// Cycles through ascending, descending and none. Useful for clickable table headers.
+    pub fn next(&mut self) {
+        *self = match self {
+            SortMode::Ascending => SortMode::Descending,
+            SortMode::Descending => SortMode::None,
+            SortMode::None => SortMode::Ascending,
+        };
+    }
+
+    /// Applies this sort direction to the ordering of two items. `None` makes everything equal.
+    pub fn apply(self, ordering: Ordering) -> Ordering {
+        match self {
+            SortMode::Ascending => ordering,
+            SortMode::Descending => ordering.reverse(),
+            SortMode::None => Ordering::Equal,
+        }
+    }
+}
+
+/// Items that can be compared by column for in-memory sorting.
+///
+/// Usually you derive this:
+///
+/// ```
+/// # use leptos_windowing::{SortMode, Sortable, sort_items};
+/// #
+/// #[derive(Sortable)]
+/// pub struct Book {
+///     #[sortable]
+///     title: String,
+///     #[sortable]
+///     year: u16,
+///     cover_url: String,
+/// }
+///
+/// let mut books = vec![
+///     Book { title: "B".to_string(), year: 2001, cover_url: String::new() },
+///     Book { title: "A".to_string(), year: 2001, cover_url: String::new() },
+///     Book { title: "C".to_string(), year: 1999, cover_url: String::new() },
+/// ];
+///
+/// // Sort by year descending and then by title
+/// sort_items(&mut books, &[(1, SortMode::Descending), (0, SortMode::Ascending)]);
+///
+/// assert_eq!(books.iter().map(|book| book.title.as_str()).collect::<Vec<_>>(), ["A", "B", "C"]);
+/// ```
+pub trait Sortable {
+    /// Compares `a` and `b` by the given column. Unknown columns compare as equal.
+    fn compare(a: &Self, b: &Self, column: usize) -> Ordering;
+}
+
+/// Compares `a` and `b` by the sorted columns in order. Later columns break ties of earlier ones.
+pub fn compare_sorted<T: Sortable>(a: &T, b: &T, sorting: &[(usize, SortMode)]) -> Ordering {
+    sorting
+        .iter()
+        .map(|(column, mode)| mode.apply(T::compare(a, b, *column)))
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+/// Sorts `items` by the sorted columns in order. The sort is stable.
+pub fn sort_items<T: Sortable>(items: &mut [T], sorting: &[(usize, SortMode)]) {
+    items.sort_by(|a, b| compare_sorted(a, b, sorting));
+}
+
+/// Encodes the sorting as the body of an SQL `ORDER BY` clause like `"name ASC, city DESC"`.
+///
+/// `column_name` is the whitelist of identifiers: it returns the SQL identifier for a column or `None`
+/// if the column must not be sorted by. Identifiers that aren't plain (optionally qualified) SQL identifiers
+/// are skipped as well so the result is always safe to put into a query.
+///
+/// Returns `None` if there is nothing to sort by.
+///
+/// ```
+/// # use leptos_windowing::{SortMode, sql_order_by};
+/// #
+/// #[derive(Clone, Copy)]
+/// enum Column { Name, City, Secret }
+///
+/// let sorting = [(Column::Name, SortMode::Ascending), (Column::Secret, SortMode::Descending)];
+///
+/// let order_by = sql_order_by(&sorting, |column| match column {
+///     Column::Name => Some("name"),
+///     Column::City => Some("city"),
+///     Column::Secret => None,
+/// });
+///
+/// assert_eq!(order_by.as_deref(), Some("name ASC"));
+/// ```
+pub fn sql_order_by<C>(
+    sorting: &[(C, SortMode)],
+    column_name: impl Fn(&C) -> Option<&str>,
+) -> Option<String> {
+    encode_sorting(
+        sorting,
+        |column| column_name(column).filter(|name| is_sql_identifier(name)),
+        ", ",
+        |out, name, mode| {
+            let _ = write!(
+                out,
+                "{name} {}",
+                if mode == SortMode::Ascending {
+                    "ASC"
+                } else {
+                    "DESC"
+                }
+            );
+        },
+    )
+}
+
+/// Encodes the sorting as a REST query parameter value like `"name:asc,city:desc"`.
+///
+/// `column_name` returns the API name of a column or `None` if the column isn't sortable.
+///
+/// Returns `None` if there is nothing to sort by.
+///
+/// ```
+/// # use leptos_windowing::{SortMode, rest_sort_param};
+/// #
+/// let sorting = [("name", SortMode::Ascending), ("city", SortMode::Descending)];
+///
+/// let sort = rest_sort_param(&sorting, |column| Some(*column));
+///
+/// assert_eq!(sort.as_deref(), Some("name:asc,city:desc"));
+/// ```
+pub fn rest_sort_param<C>(
+    sorting: &[(C, SortMode)],
+    column_name: impl Fn(&C) -> Option<&str>,
+) -> Option<String> {
+    encode_sorting(sorting, column_name, ",", |out, name, mode| {
+        let _ = write!(
+            out,
+            "{name}:{}",
+            if mode == SortMode::Ascending {
+                "asc"
+            } else {
+                "desc"
+            }
+        );
+    })
+}
+
+/// Encodes the sorting as an OData `$orderby` value like `"Name asc,City desc"`.
+///
+/// `column_name` returns the OData property name of a column or `None` if the column isn't sortable.
+///
+/// Returns `None` if there is nothing to sort by.
+///
+/// ```
+/// # use leptos_windowing::{SortMode, odata_order_by};
+/// #
+/// let sorting = [("Name", SortMode::Descending), ("City", SortMode::None)];
+///
+/// let order_by = odata_order_by(&sorting, |column| Some(*column));
+///
+/// assert_eq!(order_by.as_deref(), Some("Name desc"));
+/// ```
+pub fn odata_order_by<C>(
+    sorting: &[(C, SortMode)],
+    column_name: impl Fn(&C) -> Option<&str>,
+) -> Option<String> {
+    encode_sorting(sorting, column_name, ",", |out, name, mode| {
+        let _ = write!(
+            out,
+            "{name} {}",
+            if mode == SortMode::Ascending {
+                "asc"
+            } else {
+                "desc"
+            }
+        );
+    })
+}
+
+fn encode_sorting<C>(
+    sorting: &[(C, SortMode)],
+    column_name: impl Fn(&C) -> Option<&str>,
+    separator: &str,
+    encode: impl Fn(&mut String, &str, SortMode),
+) -> Option<String> {
+    let mut out = String::new();
+
+    for (column, mode) in sorting {
+        if *mode == SortMode::None {
+            continue;
+        }
+
+        let Some(name) = column_name(column) else {
+            continue;
+        };
+
+        if !out.is_empty() {
+            out.push_str(separator);
+        }
+        encode(&mut out, name, *mode);
+    }
+
+    (!out.is_empty()).then_some(out)
+}
+
+/// Plain SQL identifiers like `name` or `users.created_at`.
+fn is_sql_identifier(name: &str) -> bool {
+    !name.is_empty()
+        && name.split('.').all(|part| {
+            let mut chars = part.chars();
+
+            chars
+                .next()
+                .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Sortable)]
+    struct Row(
+        #[sortable(column = 1)] f64,
+        #[sortable(column = 0)] &'static str,
+        u8,
+    );
+
+    #[test]
+    fn test_sort_items() {
+        let mut rows = vec![
+            Row(2.0, "b", 0),
+            Row(f64::NAN, "a", 1),
+            Row(1.0, "b", 2),
+            Row(3.0, "a", 3),
+        ];
+
+        sort_items(
+            &mut rows,
+            &[(0, SortMode::Descending), (1, SortMode::Ascending)],
+        );
+        assert_eq!(
+            rows.iter().map(|row| row.2).collect::<Vec<_>>(),
+            [2, 0, 1, 3]
+        );
+
+        // unsorted and unknown columns keep the order
+        sort_items(&mut rows, &[(1, SortMode::None), (2, SortMode::Ascending)]);
+        assert_eq!(
+            rows.iter().map(|row| row.2).collect::<Vec<_>>(),
+            [2, 0, 1, 3]
+        );
+    }
+
+    #[test]
+    fn test_sql_order_by() {
+        let sorting = [
+            ("name", SortMode::Ascending),
+            ("users.created_at", SortMode::Descending),
+            ("city", SortMode::None),
+            ("1; DROP TABLE users", SortMode::Ascending),
+            ("", SortMode::Ascending),
+        ];
+
+        assert_eq!(
+            sql_order_by(&sorting, |column| Some(*column)).as_deref(),
+            Some("name ASC, users.created_at DESC")
+        );
+        assert_eq!(sql_order_by(&sorting, |_| None), None);
+        assert_eq!(sql_order_by::<&str>(&[], |column| Some(*column)), None);
+    }
+
+    #[test]
+    fn test_rest_and_odata() {
+        let sorting = [
+            ("name", SortMode::Descending),
+            ("city", SortMode::Ascending),
+        ];
+
+        assert_eq!(
+            rest_sort_param(&sorting, |column| Some(*column)).as_deref(),
+            Some("name:desc,city:asc")
+        );
+        assert_eq!(
+            odata_order_by(&sorting, |column| Some(*column)).as_deref(),
+            Some("name desc,city asc")
+        );
+    }
+}
@@ -0,0 +1,69 @@
+use std::sync::Arc;
+
+use futures::future::LocalBoxFuture;
+use leptos::prelude::*;
+
+/// Runs the futures of the loaders and other background tasks of this crate.
+///
+/// By default they're run with Leptos' [`spawn_local`](leptos::task::spawn_local). Implement this
+/// to run them somewhere else, e.g. on a custom executor, and install it for a part of your app
+/// with [`provide_spawner`] or for a single list with the `spawner` option of
+/// [`use_load_on_demand`](crate::hook::use_load_on_demand).
+///
+/// ```
+/// # use futures::future::LocalBoxFuture;
+/// # use leptos_windowing::Spawner;
+/// #
+/// pub struct LoggingSpawner;
+///
+/// impl Spawner for LoggingSpawner {
+///     fn spawn_local(&self, future: LocalBoxFuture<'static, ()>) {
+///         leptos::logging::log!("Spawning a load");
+///         leptos::task::spawn_local(future);
+///     }
+/// }
+/// ```
+pub trait Spawner: Send + Sync + 'static {
+    /// Runs `future` to completion on the current thread without blocking.
+    fn spawn_local(&self, future: LocalBoxFuture<'static, ()>);
+}
+
+/// The default [`Spawner`] that uses Leptos' [`spawn_local`](leptos::task::spawn_local).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LeptosSpawner;
+
+impl Spawner for LeptosSpawner {
+    #[inline]
+    fn spawn_local(&self, future: LocalBoxFuture<'static, ()>) {
+        leptos::task::spawn_local(future);
+    }
+}
+
+/// Installs `spawner` for all lists that are created by the current component and its children.
+pub fn provide_spawner(spawner: impl Spawner) {
+    provide_context(SpawnerContext(Arc::new(spawner)));
+}
+
+#[derive(Clone)]
+struct SpawnerContext(Arc<dyn Spawner>);
+
+/// `spawner` if it's set, otherwise the one installed with [`provide_spawner`] or [`LeptosSpawner`].
+///
+/// It's stored so the `Copy` closures of the hooks can use it.
+pub(crate) fn resolve_spawner(spawner: Option<Arc<dyn Spawner>>) -> StoredValue<Arc<dyn Spawner>> {
+    let spawner = spawner
+        .or_else(|| use_context::<SpawnerContext>().map(|context| context.0))
+        .unwrap_or_else(|| Arc::new(LeptosSpawner));
+
+    StoredValue::new(spawner)
+}
+
+/// Runs `future` with `spawner`. Nothing happens if the owner of `spawner` has been disposed.
+pub(crate) fn spawn_with(
+    spawner: StoredValue<Arc<dyn Spawner>>,
+    future: impl Future<Output = ()> + 'static,
+) {
+    if let Some(spawner) = spawner.try_get_value() {
+        spawner.spawn_local(Box::pin(future));
+    }
+}
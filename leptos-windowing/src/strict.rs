@@ -0,0 +1,16 @@
+/// Reports a misuse of the API like a [`CacheController`](crate::cache::CacheController) that is
+/// used before it has been initialized.
+///
+/// With the feature `strict` this panics in debug builds so the mistake surfaces during
+/// development. Otherwise, and always in release builds, the message is logged as an error.
+macro_rules! misuse {
+    ($($arg:tt)*) => {
+        if cfg!(all(feature = "strict", debug_assertions)) {
+            panic!("[leptos-windowing] {}", format_args!($($arg)*));
+        } else {
+            leptos::logging::error!("[leptos-windowing] {}", format_args!($($arg)*));
+        }
+    };
+}
+
+pub(crate) use misuse;
@@ -0,0 +1,97 @@
+use std::sync::{
+    Mutex,
+    atomic::{AtomicUsize, Ordering},
+};
+
+use leptos::prelude::*;
+
+/// The windows that are alive and have been created with the `tags` option.
+static TAGGED_WINDOWS: Mutex<Vec<TaggedWindow>> = Mutex::new(Vec::new());
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+struct TaggedWindow {
+    id: usize,
+    tags: Vec<String>,
+    revalidate: Callback<()>,
+}
+
+/// Reloads every live window that has been tagged with `tag` through the `tags` option of
+/// [`use_load_on_demand`](crate::hook::use_load_on_demand).
+///
+/// The loaded items stay displayed until they're replaced like with the option
+/// `stale_while_revalidate`. Use this after a mutation to refresh all lists that show the changed
+/// data, wherever they are in the app.
+///
+/// With the feature `broadcast` the lists with the tag in the other open tabs of the app are
+/// reloaded as well. Only tags are sent to the other tabs: a reload with
+/// [`Cache::reload`](crate::cache::Cache::reload) only affects the list in the current tab because
+/// the other tabs can't tell which of their lists it is. Tag the list and invalidate the tag to
+/// reload it everywhere. The tabs share the channel `leptos-windowing-invalidation` unless
+/// it's changed with `set_broadcast_channel_name`.
+///
+/// ```
+/// # use leptos_windowing::invalidate_tag;
+/// #
+/// # async fn save_customer() {}
+/// #
+/// # async fn example() {
+/// save_customer().await;
+///
+/// // Every list created with `UseLoadOnDemandOptions::default().tags(vec!["customers".to_string()])`
+/// invalidate_tag("customers");
+/// # }
+/// ```
+pub fn invalidate_tag(tag: &str) {
+    #[cfg(feature = "broadcast")]
+    crate::broadcast::broadcast_tag(tag);
+
+    invalidate_tag_in_this_tab(tag);
+}
+
+pub(crate) fn invalidate_tag_in_this_tab(tag: &str) {
+    // Run the callbacks after releasing the lock so they can create or drop tagged windows.
+    let callbacks = TAGGED_WINDOWS
+        .lock()
+        .map(|windows| {
+            windows
+                .iter()
+                .filter(|window| window.tags.iter().any(|window_tag| window_tag == tag))
+                .map(|window| window.revalidate)
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    for revalidate in callbacks {
+        revalidate.try_run(());
+    }
+}
+
+/// Registers a window with `tags` until the current reactive owner is cleaned up.
+///
+/// Windows aren't registered on the server where nothing is loaded.
+#[cfg_attr(feature = "ssr", allow(dead_code))]
+pub(crate) fn register_tags(tags: Vec<String>, revalidate: Callback<()>) {
+    if tags.is_empty() {
+        return;
+    }
+
+    #[cfg(feature = "broadcast")]
+    crate::broadcast::listen_to_other_tabs();
+
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+
+    if let Ok(mut windows) = TAGGED_WINDOWS.lock() {
+        windows.push(TaggedWindow {
+            id,
+            tags,
+            revalidate,
+        });
+    }
+
+    on_cleanup(move || {
+        if let Ok(mut windows) = TAGGED_WINDOWS.lock() {
+            windows.retain(|window| window.id != id);
+        }
+    });
+}
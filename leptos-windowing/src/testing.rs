@@ -0,0 +1,152 @@
+//! Run the hooks of this crate and the facade crates in plain `cargo test`, without a browser.
+//! Requires the feature `test-utils`.
+//!
+//! [`TestRuntime`] provides the reactive owner and a single threaded executor for the effects
+//! and loads. Together with [`MockLoader`](crate::MockLoader) you can check which ranges
+//! a list loads and what ends up in its cache.
+//!
+//! ```
+//! # use leptos::prelude::*;
+//! # use leptos_windowing::{MockLoader, testing::TestRuntime};
+//! # use leptos_windowing::hook::{use_load_on_demand, UseLoadOnDemandOptions};
+//! #
+//! let runtime = TestRuntime::new();
+//!
+//! let loader = MockLoader::new((0..100).collect::<Vec<usize>>());
+//!
+//! let display = RwSignal::new(0..10);
+//! let result = use_load_on_demand(
+//!     display,
+//!     display,
+//!     loader.clone(),
+//!     (),
+//!     UseLoadOnDemandOptions::default(),
+//! );
+//!
+//! runtime.settle();
+//! assert_eq!(loader.loaded_ranges(), [0..10]);
+//!
+//! display.set(5..15);
+//! runtime.settle();
+//! assert_eq!(loader.loaded_ranges(), [0..10, 10..15]);
+//! assert_eq!(result.item_window.cache.item_count().get_untracked(), Some(100));
+//! ```
+//!
+//! Loads with a latency or retries with a delay depend on browser timers and never finish in
+//! here, so use [`MockLoader`](crate::MockLoader) without `latency` and a
+//! [`RetryPolicy`](crate::RetryPolicy) with a zero `initial_delay`.
+//!
+//! The feature `test-utils` turns on effects outside the browser so that the hooks work at all.
+//! Every effect then needs an executor, so only enable it in your `[dev-dependencies]` and create
+//! a [`TestRuntime`] in every test that calls a hook.
+
+use std::{cell::RefCell, future::Future, pin::Pin};
+
+use any_spawner::{CustomExecutor, Executor};
+use futures::{
+    executor::{LocalPool, LocalSpawner},
+    task::LocalSpawnExt,
+};
+use leptos::prelude::*;
+
+use crate::{cache::Cache, item_state::ItemState};
+
+/// Reactive owner and executor for running hooks in tests.
+///
+/// Create it at the start of a test and call the hooks afterwards. Effects and loads only run
+/// when you call [`TestRuntime::settle`].
+///
+/// The executor is installed for the current thread. This only works if no other global executor
+/// has been initialized in the same test binary, e.g. with `Executor::init_futures_executor`.
+pub struct TestRuntime {
+    owner: Owner,
+}
+
+impl TestRuntime {
+    /// Installs the executor for the current thread and sets a new reactive owner.
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        // Already installed by an earlier runtime on this thread
+        let _ = Executor::init_local_custom_executor(TestExecutor::new());
+
+        let owner = Owner::new();
+        owner.set();
+
+        Self { owner }
+    }
+
+    /// Runs all effects and loads until none of them can make progress anymore.
+    pub fn settle(&self) {
+        Executor::poll_local();
+    }
+
+    /// The reactive owner of this runtime.
+    pub fn owner(&self) -> &Owner {
+        &self.owner
+    }
+}
+
+impl Drop for TestRuntime {
+    fn drop(&mut self) {
+        self.owner.clone().unset();
+    }
+}
+
+/// The items in `cache`. Items that aren't loaded (yet) are `None`.
+///
+/// Use [`Cache::failed_ranges`] to check for errors.
+pub fn cached_items<T>(cache: &Cache<T>) -> Vec<Option<T>>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    cache.items().with_untracked(|items| {
+        items
+            .iter()
+            .map(|item| match item {
+                ItemState::Loaded(item) => Some(T::clone(item)),
+                _ => None,
+            })
+            .collect()
+    })
+}
+
+/// Executor that runs all tasks on the current thread when it's polled.
+struct TestExecutor {
+    pool: RefCell<LocalPool>,
+    spawner: LocalSpawner,
+}
+
+impl TestExecutor {
+    fn new() -> Self {
+        let pool = LocalPool::new();
+        let spawner = pool.spawner();
+
+        Self {
+            pool: RefCell::new(pool),
+            spawner,
+        }
+    }
+
+    fn spawn_boxed(&self, fut: Pin<Box<dyn Future<Output = ()>>>) {
+        self.spawner
+            .spawn_local(fut)
+            .expect("the test executor has been shut down");
+    }
+}
+
+impl CustomExecutor for TestExecutor {
+    fn spawn(&self, fut: any_spawner::PinnedFuture<()>) {
+        self.spawn_boxed(fut);
+    }
+
+    fn spawn_local(&self, fut: any_spawner::PinnedLocalFuture<()>) {
+        self.spawn_boxed(fut);
+    }
+
+    fn poll_local(&self) {
+        // Tasks can call this again while they're polled
+        if let Ok(mut pool) = self.pool.try_borrow_mut() {
+            pool.run_until_stalled();
+        }
+    }
+}
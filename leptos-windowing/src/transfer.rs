@@ -0,0 +1,287 @@
+use std::{fmt::Display, marker::PhantomData};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::DeserializeOwned};
+
+/// A serialization format to move items across a boundary like a Web Worker or a server function.
+///
+/// `Json` is easy to debug while `Postcard` and `Bincode` produce much smaller payloads and
+/// are faster to decode which matters for large chunks of items. The binary formats require the
+/// features `postcard` and `bincode` respectively.
+///
+/// Use a format with `WorkerLoader` or return the items of your server functions as
+/// [`Encoded`] with it.
+///
+/// Run `cargo bench -p leptos-windowing --bench transfer --features postcard,bincode` to compare
+/// the formats with your kind of items.
+pub trait TransferFormat {
+    /// Serializes `value` into bytes.
+    fn encode<V>(value: &V) -> Result<Vec<u8>, TransferError>
+    where
+        V: Serialize + ?Sized;
+
+    /// Deserializes a value from `bytes` that were produced by [`TransferFormat::encode`].
+    fn decode<V>(bytes: &[u8]) -> Result<V, TransferError>
+    where
+        V: DeserializeOwned;
+}
+
+/// Error of a [`TransferFormat`] with the message of the underlying serializer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransferError(pub String);
+
+impl Display for TransferError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for TransferError {}
+
+impl TransferError {
+    fn new(error: impl Display) -> Self {
+        Self(error.to_string())
+    }
+}
+
+/// JSON as text.
+///
+/// ```
+/// # use leptos_windowing::{Json, TransferFormat};
+/// #
+/// let bytes = Json::encode(&vec![1, 2, 3]).unwrap();
+/// let items: Vec<i32> = Json::decode(&bytes).unwrap();
+///
+/// assert_eq!(items, [1, 2, 3]);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Json;
+
+impl TransferFormat for Json {
+    fn encode<V>(value: &V) -> Result<Vec<u8>, TransferError>
+    where
+        V: Serialize + ?Sized,
+    {
+        serde_json::to_vec(value).map_err(TransferError::new)
+    }
+
+    fn decode<V>(bytes: &[u8]) -> Result<V, TransferError>
+    where
+        V: DeserializeOwned,
+    {
+        serde_json::from_slice(bytes).map_err(TransferError::new)
+    }
+}
+
+/// The compact binary format of the `postcard` crate. Requires the feature `postcard`.
+#[cfg(feature = "postcard")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Postcard;
+
+#[cfg(feature = "postcard")]
+impl TransferFormat for Postcard {
+    fn encode<V>(value: &V) -> Result<Vec<u8>, TransferError>
+    where
+        V: Serialize + ?Sized,
+    {
+        postcard::to_allocvec(value).map_err(TransferError::new)
+    }
+
+    fn decode<V>(bytes: &[u8]) -> Result<V, TransferError>
+    where
+        V: DeserializeOwned,
+    {
+        postcard::from_bytes(bytes).map_err(TransferError::new)
+    }
+}
+
+/// The binary format of the `bincode` crate with its standard configuration. Requires the
+/// feature `bincode`.
+#[cfg(feature = "bincode")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Bincode;
+
+#[cfg(feature = "bincode")]
+impl TransferFormat for Bincode {
+    fn encode<V>(value: &V) -> Result<Vec<u8>, TransferError>
+    where
+        V: Serialize + ?Sized,
+    {
+        bincode::serde::encode_to_vec(value, bincode::config::standard())
+            .map_err(TransferError::new)
+    }
+
+    fn decode<V>(bytes: &[u8]) -> Result<V, TransferError>
+    where
+        V: DeserializeOwned,
+    {
+        bincode::serde::decode_from_slice(bytes, bincode::config::standard())
+            .map(|(value, _)| value)
+            .map_err(TransferError::new)
+    }
+}
+
+/// A value that is encoded with the [`TransferFormat`] `F`, e.g. to return the items of a
+/// server function in a compact binary format.
+///
+/// It serializes as bytes. Return it from a server function with a binary output encoding like
+/// `Cbor` so the bytes aren't expanded into a list of numbers again.
+///
+/// ```
+/// # use leptos_windowing::{Encoded, Json};
+/// #
+/// // In the server function
+/// let encoded = Encoded::<Vec<i32>, Json>::new(&vec![1, 2, 3]).unwrap();
+///
+/// // In the loader after awaiting the server function
+/// let items = encoded.decode().unwrap();
+///
+/// assert_eq!(items, [1, 2, 3]);
+/// ```
+pub struct Encoded<V, F = Json> {
+    bytes: Vec<u8>,
+    _marker: PhantomData<fn() -> (V, F)>,
+}
+
+impl<V, F> Encoded<V, F>
+where
+    F: TransferFormat,
+{
+    /// Encodes `value` with the format `F`.
+    pub fn new(value: &V) -> Result<Self, TransferError>
+    where
+        V: Serialize,
+    {
+        Ok(Self {
+            bytes: F::encode(value)?,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Decodes the value.
+    pub fn decode(&self) -> Result<V, TransferError>
+    where
+        V: DeserializeOwned,
+    {
+        F::decode(&self.bytes)
+    }
+
+    /// The encoded bytes.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl<V, F> Clone for Encoded<V, F> {
+    fn clone(&self) -> Self {
+        Self {
+            bytes: self.bytes.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<V, F> std::fmt::Debug for Encoded<V, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Encoded")
+            .field("len", &self.bytes.len())
+            .finish()
+    }
+}
+
+impl<V, F> Serialize for Encoded<V, F> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(&self.bytes)
+    }
+}
+
+impl<'de, V, F> Deserialize<'de> for Encoded<V, F> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct BytesVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for BytesVisitor {
+            type Value = Vec<u8>;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("bytes")
+            }
+
+            fn visit_bytes<E>(self, bytes: &[u8]) -> Result<Self::Value, E> {
+                Ok(bytes.to_vec())
+            }
+
+            fn visit_byte_buf<E>(self, bytes: Vec<u8>) -> Result<Self::Value, E> {
+                Ok(bytes)
+            }
+
+            // Text formats like JSON write the bytes as a list of numbers
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: serde::de::SeqAccess<'de>,
+            {
+                let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+                while let Some(byte) = seq.next_element()? {
+                    bytes.push(byte);
+                }
+                Ok(bytes)
+            }
+        }
+
+        Ok(Self {
+            bytes: deserializer.deserialize_byte_buf(BytesVisitor)?,
+            _marker: PhantomData,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip<F: TransferFormat>() -> usize {
+        let items = (0..100_u32)
+            .map(|i| (i * 1000, format!("item {i}")))
+            .collect::<Vec<_>>();
+
+        let bytes = F::encode(&items).unwrap();
+        assert_eq!(F::decode::<Vec<(u32, String)>>(&bytes).unwrap(), items);
+        assert!(F::decode::<Vec<(u32, String)>>(&bytes[..bytes.len() / 2]).is_err());
+
+        bytes.len()
+    }
+
+    #[test]
+    fn test_json() {
+        round_trip::<Json>();
+    }
+
+    #[test]
+    fn test_encoded() {
+        let encoded = Encoded::<Vec<u32>, Json>::new(&vec![1, 2, 3]).unwrap();
+        assert_eq!(encoded.as_bytes(), b"[1,2,3]");
+
+        // Like the response of a server function
+        let response = serde_json::to_string(&encoded).unwrap();
+        let decoded = serde_json::from_str::<Encoded<Vec<u32>, Json>>(&response).unwrap();
+
+        assert_eq!(decoded.decode().unwrap(), [1, 2, 3]);
+    }
+
+    #[test]
+    #[cfg(feature = "postcard")]
+    fn test_postcard_is_smaller_than_json() {
+        assert!(round_trip::<Postcard>() < round_trip::<Json>());
+    }
+
+    #[test]
+    #[cfg(feature = "bincode")]
+    fn test_bincode_is_smaller_than_json() {
+        assert!(round_trip::<Bincode>() < round_trip::<Json>());
+    }
+}
@@ -2,7 +2,11 @@ use std::{ops::Range, sync::Arc};
 
 use leptos::prelude::*;
 
-use crate::cache::Cache;
+use crate::{
+    cache::{Cache, CacheEvent},
+    item_state::ItemError,
+    strict::misuse,
+};
 
 /// This is bascially a signal of a slice of the internal cache.
 ///
@@ -13,6 +17,46 @@ where
 {
     pub cache: Cache<T>,
     pub range: Signal<Range<usize>>,
+    /// Whether the requested display range extends past the end of the data once the item count
+    /// is known, e.g. on a last page that is shorter than the page size.
+    ///
+    /// Use this to render a "no more items" trailer instead of empty rows.
+    pub overflow_past_end: Signal<bool>,
+    /// Whether the previous items are still displayed while the requested ones are loaded.
+    /// Only happens with the option
+    /// [`keep_previous_data`](crate::hook::UseLoadOnDemandOptions::keep_previous_data).
+    ///
+    /// Use this to dim the stale items.
+    pub is_pending: Signal<bool>,
+    /// Resolves once the items of the range are loaded. Only set with the option
+    /// [`suspense`](crate::hook::UseLoadOnDemandOptions::suspense).
+    ///
+    /// Read it inside of a `<Suspense/>` or `<Transition/>` to show its fallback while loading.
+    /// `PaginatedFor` does this for you. The other list components ignore it.
+    pub suspense: Option<LocalResource<()>>,
+    /// The fraction between 0 and 1 of the displayed items that finished loading.
+    /// See [`Cache::loaded_fraction`].
+    ///
+    /// Use this to render a progress bar while a large range is filled in by several loads.
+    pub loaded_fraction: Signal<f32>,
+    /// Whether any of the displayed items is being loaded.
+    ///
+    /// Use this to disable controls or to show a spinner during loads.
+    pub is_loading: Signal<bool>,
+}
+
+/// The key by which the components render a displayed item. Returned by
+/// [`ItemWindow::displayed_keys`].
+///
+/// With an [`ItemKey`](crate::cache::ItemKey) the loaded items are keyed by identity, so their
+/// views are kept when they shift to another index. Otherwise, and for items that aren't loaded,
+/// they're keyed by index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DisplayKey {
+    /// The hashed [`ItemKey`](crate::cache::ItemKey) of a loaded item.
+    Item(u64),
+    /// The index of an item.
+    Index(usize),
 }
 
 impl<T> Clone for ItemWindow<T>
@@ -53,6 +97,274 @@ where
     pub fn remove_item(&self, index: usize) {
         self.cache.remove_item(index);
     }
+
+    /// Clears the cache and reloads everything with the loader.
+    #[inline]
+    pub fn reload(&self) {
+        self.cache.reload();
+    }
+
+    /// Loads the items in the given range into the cache without displaying them.
+    #[inline]
+    pub fn prefetch(&self, range: Range<usize>) {
+        self.cache.prefetch(range);
+    }
+
+    /// Marks the items in the given range as missing so they are loaded again.
+    #[inline]
+    pub fn invalidate_range(&self, range: Range<usize>) {
+        self.cache.invalidate_range(range);
+    }
+
+    /// Hides an item without changing the indices or the item count. See [`Cache::hide_item`].
+    #[inline]
+    pub fn hide_item(&self, index: usize) {
+        self.cache.hide_item(index);
+    }
+
+    /// Displays an item again that has been hidden with [`ItemWindow::hide_item`].
+    #[inline]
+    pub fn unhide_item(&self, index: usize) {
+        self.cache.unhide_item(index);
+    }
+
+    /// The indices of the range without the hidden items. This is what the components render.
+    pub fn displayed_indices(&self) -> Vec<usize> {
+        self.range
+            .get()
+            .filter(|index| !self.cache.is_hidden(*index))
+            .collect()
+    }
+
+    /// The keys to render the displayed items by. See [`DisplayKey`].
+    ///
+    /// Resolve a key to the current index of its item with [`ItemWindow::index_of`].
+    pub fn displayed_keys(&self) -> Vec<DisplayKey> {
+        self.displayed_indices()
+            .into_iter()
+            .map(|index| match self.cache.key_at(index) {
+                // Only keys that resolve back to this index are used. Otherwise e.g. two items
+                // with colliding keys would be rendered as the same item.
+                Some(key) if self.cache.index_of_key(key) == Some(index) => DisplayKey::Item(key),
+                _ => DisplayKey::Index(index),
+            })
+            .collect()
+    }
+
+    /// The current index of the item that is rendered by `key`.
+    /// `None` if the item isn't in the cache anymore.
+    pub fn index_of(&self, key: DisplayKey) -> Option<usize> {
+        match key {
+            DisplayKey::Item(key) => self.cache.index_of_key(key),
+            DisplayKey::Index(index) => Some(index),
+        }
+    }
+
+    /// A signal of the ranges of items that failed to load.
+    ///
+    /// Use this together with [`ItemWindow::retry_all_failed`] to render a single retry banner.
+    pub fn failed_ranges(&self) -> Signal<Vec<Range<usize>>> {
+        let cache = self.cache;
+        Signal::derive(move || cache.failed_ranges())
+    }
+
+    /// Loads all items that failed to load again.
+    #[inline]
+    pub fn retry_all_failed(&self) {
+        self.cache.retry_all_failed();
+    }
+
+    /// Hands over the loaded items of this window to `target`, a window of the same data and
+    /// query, so it doesn't load them again. See [`Cache::copy_loaded_from`].
+    #[inline]
+    pub fn hand_over_to(&self, target: &ItemWindow<T>) {
+        target.cache.copy_loaded_from(self.cache);
+    }
+
+    /// Whether the first item of the data is displayed.
+    ///
+    /// This is `false` while the data is known to be empty.
+    pub fn is_at_start(&self) -> bool {
+        self.range.get().start == 0 && self.cache.item_count().get() != Some(0)
+    }
+
+    /// Whether the last item of the data is displayed.
+    ///
+    /// This is only `true` once the total number of items is known and not zero.
+    pub fn is_at_end(&self) -> bool {
+        self.cache
+            .item_count()
+            .get()
+            .is_some_and(|count| count > 0 && self.range.get().end >= count)
+    }
+
+    /// Calls `listener` for every change of the cache until the current reactive owner is cleaned up.
+    ///
+    /// See [`Cache::on_change`].
+    #[inline]
+    pub fn on_cache_change(&self, listener: impl Fn(&CacheEvent<T>) + Send + Sync + 'static) {
+        self.cache.on_change(listener);
+    }
+}
+
+/// An imperative handle to control a window from outside of the component that renders it.
+///
+/// Pass this to the `controller` prop of a component like `PaginatedFor`. After the component
+/// has been created, parent components or global shortcuts can use it to drive the list.
+pub struct WindowController<T>
+where
+    T: Send + Sync + 'static,
+{
+    window: RwSignal<Option<ItemWindow<T>>>,
+    go_to_index: StoredValue<Option<Callback<usize>>>,
+    go_to_page: StoredValue<Option<Callback<usize>>>,
+}
+
+impl<T> Clone for WindowController<T>
+where
+    T: Send + Sync + 'static,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for WindowController<T> where T: Send + Sync + 'static {}
+
+impl<T> Default for WindowController<T>
+where
+    T: Send + Sync + 'static,
+{
+    fn default() -> Self {
+        Self {
+            window: RwSignal::new(None),
+            go_to_index: StoredValue::new(None),
+            go_to_page: StoredValue::new(None),
+        }
+    }
+}
+
+impl<T> WindowController<T>
+where
+    T: Send + Sync + 'static,
+{
+    /// Creates a new controller that still has to be passed to a component.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// This is called in the components to init the connection to the window.
+    ///
+    /// `go_to_index` is called by [`WindowController::go_to_index`] and has to make the item with
+    /// the given index visible, i.e. go to its page or scroll to it.
+    pub fn init(&self, window: ItemWindow<T>, go_to_index: Callback<usize>) {
+        self.window.set(Some(window));
+        self.go_to_index.set_value(Some(go_to_index));
+    }
+
+    /// This is called in components with pages like `PaginatedFor` in addition to
+    /// [`WindowController::init`].
+    ///
+    /// `go_to_page` is called by [`WindowController::go_to_page`] with the page counting from 0.
+    pub fn init_pages(&self, go_to_page: Callback<usize>) {
+        self.go_to_page.set_value(Some(go_to_page));
+    }
+
+    fn with_window(&self, method: &str, f: impl FnOnce(ItemWindow<T>)) {
+        if let Some(window) = self.window.get_untracked() {
+            f(window);
+        } else {
+            misuse!(
+                "{method} is called on a window controller before the controller has been initialized."
+            )
+        }
+    }
+
+    /// Clears the cache and reloads everything with the loader.
+    pub fn reload(&self) {
+        self.with_window("Reload", |window| window.reload());
+    }
+
+    /// Loads the items in the given range into the cache without displaying them.
+    pub fn prefetch(&self, range: Range<usize>) {
+        self.with_window("Prefetch", |window| window.prefetch(range));
+    }
+
+    /// Marks the items in the given range as missing so they are loaded again.
+    pub fn invalidate_range(&self, range: Range<usize>) {
+        self.with_window("Invalidate range", |window| window.invalidate_range(range));
+    }
+
+    /// A signal of the ranges of items that failed to load. Empty until the controller has been initialized.
+    pub fn failed_ranges(&self) -> Signal<Vec<Range<usize>>> {
+        let window = self.window;
+        Signal::derive(move || {
+            window
+                .get()
+                .map(|window| window.cache.failed_ranges())
+                .unwrap_or_default()
+        })
+    }
+
+    /// A signal of whether the previous items are still displayed while the requested ones are
+    /// loaded. See [`ItemWindow::is_pending`]. `false` until the controller has been initialized.
+    pub fn is_pending(&self) -> Signal<bool> {
+        let window = self.window;
+        Signal::derive(move || window.get().is_some_and(|window| window.is_pending.get()))
+    }
+
+    /// A signal of the fraction of the displayed items that are loaded from `0.0` to `1.0`, e.g.
+    /// to render a progress bar. See [`ItemWindow::loaded_fraction`]. `0.0` until the controller
+    /// has been initialized.
+    pub fn loaded_fraction(&self) -> Signal<f32> {
+        let window = self.window;
+        Signal::derive(move || {
+            window
+                .get()
+                .map_or(0.0, |window| window.loaded_fraction.get())
+        })
+    }
+
+    /// A signal of whether any displayed item is being loaded, e.g. to disable the controls
+    /// meanwhile. See [`ItemWindow::is_loading`]. `false` until the controller has been
+    /// initialized.
+    pub fn is_loading(&self) -> Signal<bool> {
+        let window = self.window;
+        Signal::derive(move || window.get().is_some_and(|window| window.is_loading.get()))
+    }
+
+    /// Loads all items that failed to load again.
+    pub fn retry_all_failed(&self) {
+        self.with_window("Retry all failed", |window| window.retry_all_failed());
+    }
+
+    /// Makes the item at the given index visible.
+    ///
+    /// For pagination this goes to the page that contains the item. Virtualized lists scroll to it.
+    #[doc(alias = "scroll_to_index")]
+    pub fn go_to_index(&self, index: usize) {
+        if let Some(go_to_index) = self.go_to_index.get_value() {
+            go_to_index.run(index);
+        } else {
+            misuse!(
+                "Go to index is called on a window controller before the controller has been initialized."
+            )
+        }
+    }
+
+    /// Goes to the given page. Counting starts from 0.
+    ///
+    /// This is only supported by components with pages like `PaginatedFor`. Use
+    /// [`WindowController::go_to_index`] for the others.
+    pub fn go_to_page(&self, page: usize) {
+        if let Some(go_to_page) = self.go_to_page.get_value() {
+            go_to_page.run(page);
+        } else {
+            misuse!(
+                "Go to page is called on a window controller that isn't initialized by a component with pages."
+            )
+        }
+    }
 }
 
 /// Item in a [`ItemWindow`].
@@ -99,6 +411,19 @@ where
         self.cache.update_item(self.index, new);
     }
 
+    /// Whether the data of this item is possibly outdated, e.g. because it's being loaded again in
+    /// the background. See [`Cache::is_stale`].
+    #[inline]
+    pub fn is_stale(&self) -> bool {
+        self.cache.is_stale(self.index)
+    }
+
+    /// Loads this item again while it stays displayed. See [`Cache::refresh`].
+    #[inline]
+    pub fn refresh(&self) {
+        self.cache.refresh(self.index..self.index + 1);
+    }
+
     /// Removes the item from the cache.
     ///
     /// The user is responsible for updating the data source accordingly.
@@ -131,3 +456,30 @@ where
         self.cache.insert_item(index, item);
     }
 }
+
+/// An item that failed to load. This is passed to the `LoadError` slot of the list components.
+#[derive(Clone, Debug)]
+pub struct ItemLoadError {
+    /// The index of the item that failed to load.
+    pub index: usize,
+    /// The error of the loader. See [`ItemError::downcast_ref`] to react to specific errors.
+    pub error: ItemError,
+    /// Loads the item again, e.g. from a "Try again" button. See [`Cache::reload_item`].
+    pub retry: Callback<()>,
+}
+
+impl ItemLoadError {
+    /// Creates a new `ItemLoadError` for the item at `index` of the given item window.
+    pub fn new<T>(index: usize, error: ItemError, window: &ItemWindow<T>) -> Self
+    where
+        T: Send + Sync + 'static,
+    {
+        let cache = window.cache;
+
+        Self {
+            index,
+            error,
+            retry: Callback::new(move |_| cache.reload_item(index)),
+        }
+    }
+}
@@ -0,0 +1,280 @@
+use std::{ops::Range, time::Duration};
+
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use leptos_windowing::{
+    AdaptiveChunkSize, MockLoader, MockLoaderCall, RetryPolicy, WindowLink,
+    hook::{UseLoadOnDemandOptions, UseLoadOnDemandResult, use_load_on_demand},
+    invalidate_tag,
+    testing::{TestRuntime, cached_items},
+    use_server_prefill,
+};
+
+fn setup(
+    loader: &MockLoader<usize>,
+    range: Range<usize>,
+    options: UseLoadOnDemandOptions<usize>,
+) -> (
+    RwSignal<Range<usize>>,
+    RwSignal<()>,
+    UseLoadOnDemandResult<usize, String>,
+) {
+    let range = RwSignal::new(range);
+    let query = RwSignal::new(());
+
+    let result = use_load_on_demand(range, range, loader.clone(), query, options);
+
+    (range, query, result)
+}
+
+fn items(count: usize) -> MockLoader<usize> {
+    MockLoader::new((0..count).collect())
+}
+
+#[test]
+fn test_initial_load() {
+    let runtime = TestRuntime::new();
+    let loader = items(100);
+
+    let (_, _, result) = setup(&loader, 0..10, UseLoadOnDemandOptions::default());
+    runtime.settle();
+
+    let calls = loader.calls();
+    assert_eq!(calls.len(), 2);
+    assert!(calls.contains(&MockLoaderCall::ItemCount));
+    assert!(calls.contains(&MockLoaderCall::LoadItems(0..10)));
+
+    let cache = result.item_window.cache;
+    assert_eq!(cache.item_count().get_untracked(), Some(100));
+    assert_eq!(
+        cached_items(&cache)[..10],
+        (0..10).map(Some).collect::<Vec<_>>()
+    );
+    assert_eq!(result.item_window.range.get_untracked(), 0..10);
+}
+
+#[test]
+fn test_only_missing_items_are_loaded() {
+    let runtime = TestRuntime::new();
+    let loader = items(100);
+
+    let (range, _, result) = setup(&loader, 0..10, UseLoadOnDemandOptions::default());
+    runtime.settle();
+
+    range.set(5..20);
+    runtime.settle();
+
+    range.set(0..10);
+    runtime.settle();
+
+    assert_eq!(loader.loaded_ranges(), [0..10, 10..20]);
+    assert_eq!(
+        cached_items(&result.item_window.cache)[..20],
+        (0..20).map(Some).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_range_is_clamped_to_item_count() {
+    let runtime = TestRuntime::new();
+    let loader = items(25);
+
+    let (range, _, result) = setup(&loader, 20..30, UseLoadOnDemandOptions::default());
+    runtime.settle();
+
+    assert_eq!(result.item_window.range.get_untracked(), 20..25);
+
+    range.set(40..50);
+    runtime.settle();
+
+    assert!(result.item_window.range.get_untracked().is_empty());
+}
+
+#[test]
+fn test_query_change_reloads() {
+    let runtime = TestRuntime::new();
+    let loader = items(100);
+
+    let (_, query, result) = setup(&loader, 0..10, UseLoadOnDemandOptions::default());
+    runtime.settle();
+    loader.clear_calls();
+
+    query.set(());
+    runtime.settle();
+
+    let calls = loader.calls();
+    assert_eq!(calls.len(), 2);
+    assert!(calls.contains(&MockLoaderCall::ItemCount));
+    assert!(calls.contains(&MockLoaderCall::LoadItems(0..10)));
+    assert_eq!(cached_items(&result.item_window.cache)[0], Some(0));
+}
+
+#[test]
+fn test_failed_load() {
+    let runtime = TestRuntime::new();
+    let loader = items(100).failing_range(5..6);
+
+    let (_, _, result) = setup(&loader, 0..10, UseLoadOnDemandOptions::default());
+    runtime.settle();
+
+    let cache = result.item_window.cache;
+    assert!(!cache.failed_ranges().is_empty());
+    assert_eq!(cached_items(&cache)[5], None);
+
+    // Only the failed items are loaded again
+    cache.retry_all_failed();
+    runtime.settle();
+
+    assert_eq!(loader.loaded_ranges().len(), 2);
+}
+
+#[test]
+fn test_retry_failed_load() {
+    let runtime = TestRuntime::new();
+    let loader = items(100).failing_range(5..6);
+
+    let (_, _, result) = setup(
+        &loader,
+        0..10,
+        UseLoadOnDemandOptions::default().retry(
+            RetryPolicy::default()
+                .max_attempts(3)
+                .initial_delay(Duration::ZERO),
+        ),
+    );
+    runtime.settle();
+
+    // Every attempt fails so the items are only marked as failed after the last one
+    assert_eq!(loader.loaded_ranges(), vec![0..10, 0..10, 0..10]);
+
+    let cache = result.item_window.cache;
+    assert!(!cache.failed_ranges().is_empty());
+    assert_eq!(cached_items(&cache)[5], None);
+}
+
+#[test]
+fn test_reload_item() {
+    let runtime = TestRuntime::new();
+    let loader = items(100).failing_range(5..6);
+
+    let (_, _, result) = setup(&loader, 0..10, UseLoadOnDemandOptions::default());
+    runtime.settle();
+    loader.clear_calls();
+
+    let cache = result.item_window.cache;
+    cache.update_item(3, 42);
+
+    cache.reload_item(3);
+    runtime.settle();
+
+    assert_eq!(loader.loaded_ranges(), vec![3..4]);
+    assert_eq!(cached_items(&cache)[3], Some(3));
+
+    // Items that failed to load are loaded again as well
+    cache.reload_item(5);
+    runtime.settle();
+
+    assert_eq!(loader.loaded_ranges(), [3..4, 5..6]);
+}
+
+#[test]
+fn test_invalidate_tag() {
+    let runtime = TestRuntime::new();
+    let tagged_loader = items(100);
+    let other_loader = items(100);
+
+    let (_, _, result) = setup(
+        &tagged_loader,
+        0..10,
+        UseLoadOnDemandOptions::default().tags(vec!["numbers".to_string()]),
+    );
+    let _ = setup(&other_loader, 0..10, UseLoadOnDemandOptions::default());
+    runtime.settle();
+    tagged_loader.clear_calls();
+    other_loader.clear_calls();
+
+    invalidate_tag("numbers");
+
+    // The items stay displayed while they're loaded again
+    assert_eq!(cached_items(&result.item_window.cache)[0], Some(0));
+    runtime.settle();
+
+    assert_eq!(tagged_loader.loaded_ranges(), vec![0..10]);
+    assert!(other_loader.calls().is_empty());
+}
+
+#[test]
+fn test_max_cached_items() {
+    let runtime = TestRuntime::new();
+    let loader = items(1000);
+
+    let (range, _, result) = setup(
+        &loader,
+        0..10,
+        UseLoadOnDemandOptions::default().max_cached_items(Some(10)),
+    );
+    runtime.settle();
+
+    range.set(500..510);
+    runtime.settle();
+
+    let cached = cached_items(&result.item_window.cache);
+    assert_eq!(cached[0], None);
+    assert_eq!(cached[500], Some(500));
+
+    range.set(0..10);
+    runtime.settle();
+
+    assert_eq!(loader.loaded_ranges(), [0..10, 500..510, 0..10]);
+}
+
+#[test]
+fn test_server_prefill() {
+    let runtime = TestRuntime::new();
+    let loader = items(100);
+    let link = WindowLink::<usize>::new();
+
+    let prefill = use_server_prefill(
+        link.cache(),
+        (),
+        0..10,
+        |_: (), range: Range<usize>| async move { Ok::<_, ()>((range.collect(), Some(100))) },
+    );
+    spawn_local(prefill.ready());
+    runtime.settle();
+
+    let (_, _, result) = setup(
+        &loader,
+        0..10,
+        UseLoadOnDemandOptions::default().cache(Some(link.cache())),
+    );
+
+    // Displayed right away so hydration matches the server rendered HTML
+    assert_eq!(result.item_window.range.get_untracked(), 0..10);
+
+    runtime.settle();
+
+    assert!(loader.loaded_ranges().is_empty());
+    assert_eq!(
+        cached_items(&result.item_window.cache)[..10],
+        (0..10).map(Some).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_adaptive_chunk_size_adapts_once_per_batch() {
+    let runtime = TestRuntime::new();
+    let loader = items(100);
+
+    let (_, _, result) = setup(
+        &loader,
+        0..40,
+        UseLoadOnDemandOptions::default()
+            .adaptive_chunk_size(Some(AdaptiveChunkSize::default().initial(10).min(10))),
+    );
+    runtime.settle();
+
+    assert_eq!(loader.loaded_ranges(), [0..10, 10..20, 20..30, 30..40]);
+    // The four chunks of the first batch finished quickly but the size is only doubled once
+    assert_eq!(result.chunk_size.get_untracked(), Some(20));
+}
@@ -0,0 +1,192 @@
+use std::sync::{Arc, Mutex};
+
+use leptos::prelude::*;
+use leptos_windowing::{
+    ConflictResolution, LiveUpdate, MockLoader, MockLoaderCall, MutableLoader, MutationOutbox,
+    UseMutationOutboxOptions,
+    cache::Cache,
+    hook::{UseLoadOnDemandOptions, use_load_on_demand},
+    testing::{TestRuntime, cached_items},
+    use_mutation_outbox,
+};
+
+/// Records the applied changes and rejects the first `rejections` of them.
+#[derive(Clone, Default)]
+struct RecordingLoader {
+    applied: Arc<Mutex<Vec<LiveUpdate<usize>>>>,
+    rejections: Arc<Mutex<usize>>,
+}
+
+impl RecordingLoader {
+    fn rejecting(rejections: usize) -> Self {
+        Self {
+            rejections: Arc::new(Mutex::new(rejections)),
+            ..Default::default()
+        }
+    }
+
+    fn applied(&self) -> Vec<LiveUpdate<usize>> {
+        self.applied.lock().unwrap().clone()
+    }
+}
+
+impl MutableLoader for RecordingLoader {
+    type Item = usize;
+    type Error = String;
+
+    async fn apply(&self, change: &LiveUpdate<usize>) -> Result<(), String> {
+        self.applied.lock().unwrap().push(change.clone());
+
+        let mut rejections = self.rejections.lock().unwrap();
+        if *rejections > 0 {
+            *rejections -= 1;
+            return Err("conflict".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+/// A list of the items `0..5` with an outbox for its cache.
+fn setup(
+    runtime: &TestRuntime,
+    mutable_loader: &RecordingLoader,
+    on_conflict: ConflictResolution,
+) -> (MockLoader<usize>, Cache<usize>, MutationOutbox<usize>) {
+    let loader = MockLoader::new((0..5).collect());
+    let range = RwSignal::new(0..5);
+
+    let cache = use_load_on_demand(
+        range,
+        range,
+        loader.clone(),
+        (),
+        UseLoadOnDemandOptions::default(),
+    )
+    .item_window
+    .cache;
+    runtime.settle();
+    loader.clear_calls();
+
+    let outbox = use_mutation_outbox(
+        cache,
+        mutable_loader.clone(),
+        UseMutationOutboxOptions::<usize>::default()
+            .on_conflict(Callback::new(move |_| on_conflict)),
+    );
+
+    (loader, cache, outbox)
+}
+
+#[test]
+fn test_changes_are_applied_in_order() {
+    let runtime = TestRuntime::new();
+    let mutable_loader = RecordingLoader::default();
+    let (_, cache, outbox) = setup(
+        &runtime,
+        &mutable_loader,
+        ConflictResolution::DiscardAndReload,
+    );
+
+    outbox.update_item(0, 10);
+    outbox.insert_item(1, 11);
+    outbox.remove_item(3);
+
+    // The cache is changed right away
+    assert_eq!(
+        cached_items(&cache),
+        [Some(10), Some(11), Some(1), Some(3), Some(4)]
+    );
+    assert_eq!(outbox.pending_count().get_untracked(), 3);
+
+    runtime.settle();
+
+    assert_eq!(
+        mutable_loader.applied(),
+        [
+            LiveUpdate::Update { index: 0, item: 10 },
+            LiveUpdate::Insert { index: 1, item: 11 },
+            LiveUpdate::Delete { index: 3 },
+        ]
+    );
+    assert_eq!(outbox.pending_count().get_untracked(), 0);
+}
+
+#[test]
+fn test_retry_keeps_the_changes_queued() {
+    let runtime = TestRuntime::new();
+    let mutable_loader = RecordingLoader::rejecting(1);
+    let (_, cache, outbox) = setup(&runtime, &mutable_loader, ConflictResolution::Retry);
+
+    outbox.update_item(0, 10);
+    runtime.settle();
+    outbox.update_item(1, 11);
+    runtime.settle();
+
+    // The rejected change is sent again before the next one
+    assert_eq!(
+        mutable_loader.applied(),
+        [
+            LiveUpdate::Update { index: 0, item: 10 },
+            LiveUpdate::Update { index: 0, item: 10 },
+            LiveUpdate::Update { index: 1, item: 11 },
+        ]
+    );
+    assert_eq!(outbox.pending_count().get_untracked(), 0);
+    assert_eq!(cached_items(&cache)[..2], [Some(10), Some(11)]);
+}
+
+#[test]
+fn test_retry_waits_for_replay() {
+    let runtime = TestRuntime::new();
+    let mutable_loader = RecordingLoader::rejecting(2);
+    let (_, _, outbox) = setup(&runtime, &mutable_loader, ConflictResolution::Retry);
+
+    outbox.update_item(0, 10);
+    runtime.settle();
+    assert_eq!(outbox.pending_count().get_untracked(), 1);
+
+    outbox.replay();
+    runtime.settle();
+    assert_eq!(mutable_loader.applied().len(), 2);
+    assert_eq!(outbox.pending_count().get_untracked(), 1);
+
+    outbox.replay();
+    runtime.settle();
+    assert_eq!(mutable_loader.applied().len(), 3);
+    assert_eq!(outbox.pending_count().get_untracked(), 0);
+}
+
+#[test]
+fn test_discard_keeps_the_change_in_the_cache() {
+    let runtime = TestRuntime::new();
+    let mutable_loader = RecordingLoader::rejecting(1);
+    let (loader, cache, outbox) = setup(&runtime, &mutable_loader, ConflictResolution::Discard);
+
+    outbox.update_item(0, 10);
+    outbox.update_item(1, 11);
+    runtime.settle();
+
+    assert_eq!(mutable_loader.applied().len(), 2);
+    assert_eq!(outbox.pending_count().get_untracked(), 0);
+    assert_eq!(cached_items(&cache)[..2], [Some(10), Some(11)]);
+    assert!(loader.calls().is_empty());
+}
+
+#[test]
+fn test_discard_and_reload_loads_the_data_source_version() {
+    let runtime = TestRuntime::new();
+    let mutable_loader = RecordingLoader::rejecting(1);
+    let (loader, cache, outbox) = setup(
+        &runtime,
+        &mutable_loader,
+        ConflictResolution::DiscardAndReload,
+    );
+
+    outbox.update_item(0, 10);
+    runtime.settle();
+
+    assert_eq!(outbox.pending_count().get_untracked(), 0);
+    assert!(loader.calls().contains(&MockLoaderCall::LoadItems(0..5)));
+    assert_eq!(cached_items(&cache)[..1], [Some(0)]);
+}